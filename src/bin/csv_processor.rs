@@ -1,5 +1,18 @@
-use csv_processor::reporter::{generate_info_report, generate_na_report};
-use csv_processor::{parse_config, Command, DataFrame};
+use csv_processor::io::{read_csv, read_csv_rows, read_dialect, ReadReport};
+use csv_processor::output::{write_frame, FrameWriter, TableWriter};
+use csv_processor::reporter::{
+    generate_detailed_na_report, generate_info_report, generate_na_report, generate_outlier_report,
+    generate_quality_report, OutlierOptions,
+};
+use csv_processor::shell;
+use csv_processor::stats::{self, TestKind, TestResult};
+use csv_processor::{
+    cli, parse_config, ColumnArray, Command, Config, ConfigError, DataFrame, DisplayOptions,
+    Encoding, OutputFormat, SqlContext,
+};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
 use std::{env, process};
 
 fn main() {
@@ -7,33 +20,450 @@ fn main() {
 
     let config = match parse_config(&args) {
         Ok(config) => config,
+        Err(ConfigError::DisplayHelp(text)) => {
+            print!("{}", text);
+            return;
+        }
         Err(error) => {
             eprintln!("Error: {}", error);
             process::exit(1);
         }
     };
 
-    if let Ok(df) = DataFrame::from_csv(config.filename()) {
-        match config.command() {
-            Command::CheckNAs => {
-                println!("Checking NAs in file: {}", config.filename());
-                let report = generate_na_report(&df);
-                println!("{}", report)
+    if let Err(error) = run(&config) {
+        eprintln!("Error: {}", error);
+        process::exit(1);
+    }
+}
+
+/// Runs the parsed command, writing its results to `--output` or standard output.
+fn run(config: &Config) -> Result<(), String> {
+    let mut out: Box<dyn Write> = match config.output() {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).map_err(|e| format!("{}: {}", path, e))?,
+        )),
+        None => Box::new(BufWriter::new(io::stdout())),
+    };
+    let io_error = |e: io::Error| e.to_string();
+    let format = config.format();
+    // titles and notes only belong in the human-readable table output
+    let table = format == OutputFormat::Table;
+    let writer = frame_writer(config);
+    let print = |out: &mut Box<dyn Write>, df: &DataFrame| {
+        writer.write_frame(df, out).map_err(|e| e.to_string())
+    };
+
+    match config.command() {
+        Command::Completions => {
+            let shell = config.completions().ok_or("no shell given")?;
+            // generate into memory: clap_complete panics on a failed write
+            let mut script = Vec::new();
+            clap_complete::generate(shell, &mut cli(), "csv_processor", &mut script);
+            out.write_all(&script).map_err(io_error)?;
+        }
+        Command::Dialect => {
+            for file in config.files() {
+                if config.files().len() > 1 {
+                    writeln!(out, "==> {} <==", file).map_err(io_error)?;
+                }
+                let dialect = read_dialect(file, config.read_options())
+                    .map_err(|e| format!("Failed to read file: {}", e))?;
+                writeln!(out, "{}", dialect).map_err(io_error)?;
             }
-            Command::Info => {
-                let report = generate_info_report(&df);
-                println!("{}", report);
+        }
+        Command::Sql => {
+            let df = run_sql(config).map_err(|e| e.to_string())?;
+            if config.csv_rows() {
+                write_frame(&df, OutputFormat::Csv, &mut out).map_err(|e| e.to_string())?;
+            } else {
+                print(&mut out, &df)?;
+            }
+        }
+        Command::CheckNAs | Command::Info => {
+            for file in config.files() {
+                if config.files().len() > 1 && table {
+                    writeln!(out, "==> {} <==", file).map_err(io_error)?;
+                }
+                let df = load(file, config, table, &mut out)?;
+                if *config.command() == Command::CheckNAs {
+                    if table {
+                        writeln!(out, "Checking NAs in file: {}", file).map_err(io_error)?;
+                    }
+                    if config.detailed() {
+                        print_na_details(&df, config, &mut out)?;
+                    } else {
+                        print(&mut out, &generate_na_report(&df))?;
+                    }
+                } else {
+                    print(&mut out, &generate_info_report(&df))?;
+                }
+            }
+        }
+        Command::Head | Command::Tail | Command::Sample => {
+            let selection = config.selection().ok_or("no rows selected")?;
+            let mut options = config.read_options().clone();
+            if !config.columns().is_empty() {
+                options.columns = Some(config.columns().to_vec());
+            }
+            let (df, read_report) = read_csv_rows(config.filename(), &options, selection)
+                .map_err(|e| format!("Failed to read file: {}", e))?;
+            note_read_report(&read_report, table, &mut out)?;
+            print(&mut out, &df)?;
+        }
+        _ => {
+            let df = load(
+                config.filename(),
+                config,
+                table && !writes_data(config),
+                &mut out,
+            )?;
+            run_on_frame(df, config, &mut out)?;
+        }
+    }
+    out.flush().map_err(io_error)
+}
+
+/// The writer for `--format`; tables on a terminal are fitted to its width.
+fn frame_writer(config: &Config) -> Box<dyn FrameWriter> {
+    if config.format() != OutputFormat::Table {
+        return config.format().writer();
+    }
+    let mut options = DisplayOptions::default();
+    if config.output().is_none() {
+        options = options.with_terminal_width();
+    }
+    if config.selection().is_some() {
+        // head, tail and sample print every row they read
+        options = options.with_max_rows(usize::MAX);
+    }
+    Box::new(TableWriter { options })
+}
+
+/// Whether the command writes data rather than a report, so that notes must
+/// go to standard error to keep the output readable as a file.
+fn writes_data(config: &Config) -> bool {
+    match config.command() {
+        Command::Fill | Command::DropNulls | Command::Interpolate => true,
+        Command::Outliers => config.csv_rows(),
+        Command::Dedup => !config.report(),
+        _ => false,
+    }
+}
 
-                // for i in 0..df.shape().1 {
-                //     let json_output = df.get_column(i).unwrap().to_json();
-                //     println!("Json column index {} output: {:?}", i, json_output);
-                // }
-                //
-                // println!("Json output: {}", df.to_json().unwrap());
+/// Reads `file`, noting a non-UTF-8 encoding or replaced bytes in table output.
+fn load(
+    file: &str,
+    config: &Config,
+    table: bool,
+    out: &mut Box<dyn Write>,
+) -> Result<DataFrame, String> {
+    let (df, read_report) =
+        read_csv(file, config.read_options()).map_err(|e| format!("Failed to read file: {}", e))?;
+    note_read_report(&read_report, table, out)?;
+    Ok(df)
+}
+
+fn note_read_report(
+    read_report: &ReadReport,
+    table: bool,
+    out: &mut Box<dyn Write>,
+) -> Result<(), String> {
+    if read_report.encoding != Encoding::Utf8 || read_report.invalid_bytes > 0 {
+        if table {
+            writeln!(out, "{}", read_report).map_err(|e| e.to_string())?;
+        } else {
+            eprintln!("{}", read_report);
+        }
+    }
+    Ok(())
+}
+
+/// Runs the commands that work on a single loaded file.
+fn run_on_frame(df: DataFrame, config: &Config, out: &mut Box<dyn Write>) -> Result<(), String> {
+    let io_error = |e: io::Error| e.to_string();
+    let table = config.format() == OutputFormat::Table;
+    let writer = frame_writer(config);
+    let print = |out: &mut Box<dyn Write>, df: &DataFrame| {
+        writer.write_frame(df, out).map_err(|e| e.to_string())
+    };
+
+    match config.command() {
+        Command::Freq => {
+            let names: Vec<String> = if config.columns().is_empty() {
+                df.headers().to_vec()
+            } else {
+                config.columns().to_vec()
+            };
+            for name in &names {
+                let column = df
+                    .column(name)
+                    .ok_or_else(|| format!("Column '{}' not found", name))?;
+                let counts = column.value_counts(config.normalize(), true, false);
+                if table {
+                    writeln!(out, "{} ({} distinct values)", name, counts.shape().0)
+                        .map_err(io_error)?;
+                }
+                print(out, &counts.head(config.top()))?;
             }
         }
-    } else {
-        eprintln!("Error: Failed to read file");
-        process::exit(1);
+        Command::Hist => {
+            // Without explicit columns, only the numeric ones are drawn.
+            let names: Vec<String> = if config.columns().is_empty() {
+                df.headers()
+                    .iter()
+                    .zip(df.columns())
+                    .filter(|(_, column)| column.dtype().is_numeric())
+                    .map(|(name, _)| name.clone())
+                    .collect()
+            } else {
+                config.columns().to_vec()
+            };
+            for name in &names {
+                let column = df
+                    .column(name)
+                    .ok_or_else(|| format!("Column '{}' not found", name))?;
+                let histogram = column
+                    .histogram(config.bins())
+                    .map_err(|e| format!("Column '{}': {}", name, e))?;
+                if table {
+                    writeln!(out, "{} ({} values)", name, histogram.total()).map_err(io_error)?;
+                    writeln!(out, "{}", histogram).map_err(io_error)?;
+                } else {
+                    print(out, &histogram.to_frame())?;
+                }
+            }
+        }
+        Command::Corr => {
+            let selected = if config.columns().is_empty() {
+                df
+            } else {
+                df.select(config.columns()).map_err(|e| e.to_string())?
+            };
+            let matrix = if config.covariance() {
+                selected.cov()
+            } else {
+                selected.corr(config.method())
+            }
+            .map_err(|e| e.to_string())?;
+            if table {
+                if config.covariance() {
+                    writeln!(out, "Covariance matrix").map_err(io_error)?;
+                } else {
+                    writeln!(out, "Correlation matrix ({})", config.method()).map_err(io_error)?;
+                }
+            }
+            print(out, &matrix)?;
+        }
+        Command::Test => {
+            let result = run_test(&df, config, table, out)?;
+            if table {
+                writeln!(out, "{}", result).map_err(io_error)?;
+            } else {
+                print(out, &result.to_frame())?;
+            }
+        }
+        Command::Outliers => {
+            let checked = if config.columns().is_empty() {
+                df.clone()
+            } else {
+                df.select(config.columns()).map_err(|e| e.to_string())?
+            };
+            let report = generate_outlier_report(&checked, &OutlierOptions::default());
+            let flagged = report.flagged_rows();
+
+            if config.csv_rows() {
+                write_frame(&df.take(&flagged), OutputFormat::Csv, out)
+                    .map_err(|e| e.to_string())?;
+            } else {
+                print(out, &report.to_frame())?;
+                if table {
+                    writeln!(out, "{} of {} rows flagged", flagged.len(), df.shape().0)
+                        .map_err(io_error)?;
+                }
+            }
+        }
+        Command::Dedup if config.report() => {
+            let report =
+                generate_quality_report(&df, config.columns()).map_err(|e| e.to_string())?;
+            print(out, &report.to_frame())?;
+            if table {
+                writeln!(out, "Duplicates").map_err(io_error)?;
+            }
+            print(out, &report.duplicates_to_frame())?;
+        }
+        Command::Dedup => {
+            let unique = df
+                .unique(config.columns(), config.keep())
+                .map_err(|e| e.to_string())?;
+            // like the cleaning commands: CSV unless asked otherwise
+            let format = if table {
+                OutputFormat::Csv
+            } else {
+                config.format()
+            };
+            write_frame(&unique, format, out).map_err(|e| e.to_string())?;
+            eprintln!("{} duplicate rows removed", df.shape().0 - unique.shape().0);
+        }
+        Command::Fill | Command::DropNulls | Command::Interpolate => {
+            let cleaned = match config.command() {
+                Command::Fill => {
+                    let strategy = config.fill().ok_or("no fill strategy given")?;
+                    df.fill_null(config.columns(), strategy)
+                }
+                Command::DropNulls => {
+                    df.drop_nulls(config.columns(), config.how(), config.thresh())
+                }
+                _ => df.interpolate(config.columns()),
+            }
+            .map_err(|e| e.to_string())?;
+            // the cleaned file is data, not a report: CSV unless asked otherwise
+            let format = if table {
+                OutputFormat::Csv
+            } else {
+                config.format()
+            };
+            write_frame(&cleaned, format, out).map_err(|e| e.to_string())?;
+            if *config.command() == Command::DropNulls {
+                eprintln!("{} rows dropped", df.shape().0 - cleaned.shape().0);
+            } else {
+                eprintln!("{} nulls filled", null_cells(&df) - null_cells(&cleaned));
+            }
+        }
+        Command::Shell => {
+            // history survives between sessions, like other interactive tools
+            let history =
+                env::var_os("HOME").map(|home| PathBuf::from(home).join(".csv_processor_history"));
+            shell::run(df, history.as_deref()).map_err(|e| e.to_string())?;
+        }
+        Command::CheckNAs
+        | Command::Info
+        | Command::Dialect
+        | Command::Sql
+        | Command::Head
+        | Command::Tail
+        | Command::Sample
+        | Command::Completions => unreachable!("handled in run"),
+    }
+    Ok(())
+}
+
+/// The `na --detailed` sections, titled in table output.
+fn print_na_details(
+    df: &DataFrame,
+    config: &Config,
+    out: &mut Box<dyn Write>,
+) -> Result<(), String> {
+    let table = config.format() == OutputFormat::Table;
+    let writer = frame_writer(config);
+    let report = generate_detailed_na_report(df);
+    let sections = [
+        ("Columns", report.to_frame()),
+        ("Rows", report.summary_to_frame()),
+        (
+            "Missingness patterns",
+            report.patterns_to_frame(config.top()),
+        ),
+        ("Nullity correlation", report.correlation_to_frame()),
+    ];
+    for (title, frame) in &sections {
+        if table {
+            writeln!(out, "{}", title).map_err(|e| e.to_string())?;
+        }
+        writer.write_frame(frame, out).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn null_cells(df: &DataFrame) -> usize {
+    df.columns().iter().map(|column| column.null_count()).sum()
+}
+
+/// Registers every input file as a table and runs the query over them.
+fn run_sql(config: &Config) -> Result<DataFrame, csv_processor::frame::DataFrameError> {
+    let mut context = SqlContext::new();
+    for file in config.files() {
+        context.register_csv(file, config.read_options())?;
+    }
+    context
+        .execute(config.query().unwrap_or_default())?
+        .collect()
+}
+
+/// Runs the hypothesis test selected by `config`; table output also says which
+/// samples are compared.
+fn run_test(
+    df: &DataFrame,
+    config: &Config,
+    table: bool,
+    out: &mut Box<dyn Write>,
+) -> Result<TestResult, String> {
+    let kind = config.test().ok_or("no test selected")?;
+    let column = |name: &str| {
+        df.column(name)
+            .ok_or_else(|| format!("Column '{}' not found", name))
+    };
+    let columns: Vec<&dyn ColumnArray> = config
+        .columns()
+        .iter()
+        .map(|name| column(name))
+        .collect::<Result<_, _>>()?;
+
+    let result =
+        match (kind, columns.as_slice(), config.by()) {
+            (TestKind::ChiSquare, [a], Some(by)) => stats::chi_square(*a, column(by)?),
+            (TestKind::ChiSquare, [a, b], None) => stats::chi_square(*a, *b),
+            (TestKind::TTest, [sample], None) => {
+                let mu = config.mu().unwrap_or(0.0);
+                if table {
+                    writeln!(out, "{} against mean {}", config.columns()[0], mu)
+                        .map_err(|e| e.to_string())?;
+                }
+                stats::ttest_one_sample(*sample, mu)
+            }
+            (TestKind::Paired, [a, b], None) => stats::ttest_paired(*a, *b),
+            (_, [values], Some(by)) => {
+                let groups = stats::split_groups(*values, column(by)?);
+                let [(label_a, a), (label_b, b)] = groups.as_slice() else {
+                    return Err(format!(
+                        "'{}' must have exactly two groups, found {}",
+                        by,
+                        groups.len()
+                    ));
+                };
+                if table {
+                    writeln!(
+                        out,
+                        "{} by {}: {} (n={}) vs {} (n={})",
+                        config.columns()[0],
+                        by,
+                        label_a,
+                        a.non_null_count(),
+                        label_b,
+                        b.non_null_count()
+                    )
+                    .map_err(|e| e.to_string())?;
+                }
+                two_sample(kind, a.as_ref(), b.as_ref())
+            }
+            (_, [a, b], None) => two_sample(kind, *a, *b),
+            _ => return Err(
+                "expected one column with --by, two columns, or one column for a one-sample t-test"
+                    .to_string(),
+            ),
+        };
+    result.map_err(|error| error.to_string())
+}
+
+fn two_sample(
+    kind: TestKind,
+    a: &dyn ColumnArray,
+    b: &dyn ColumnArray,
+) -> Result<TestResult, csv_processor::frame::DataFrameError> {
+    match kind {
+        TestKind::TTest => stats::ttest_welch(a, b),
+        TestKind::Paired => stats::ttest_paired(a, b),
+        TestKind::ChiSquare => stats::chi_square(a, b),
+        TestKind::MannWhitney => stats::mann_whitney_u(a, b),
+        TestKind::KolmogorovSmirnov => stats::ks_2samp(a, b),
     }
 }
@@ -1,7 +1,35 @@
+use std::error::Error as _;
+
+use clap::error::{ContextKind, ContextValue, ErrorKind};
+use clap::{Args, CommandFactory, Parser};
+
+use crate::frame::{CorrMethod, DropHow, Keep};
+use crate::io::{CsvReadOptions, Encoding, RowSelection};
+use crate::output::OutputFormat;
+use crate::series::{parse_column, Bins, FillStrategy};
+use crate::stats::TestKind;
+use crate::CellValue;
+
 #[derive(Debug, PartialEq)]
 pub enum Command {
     CheckNAs,
     Info,
+    Dialect,
+    Freq,
+    Hist,
+    Corr,
+    Test,
+    Outliers,
+    Sql,
+    Shell,
+    Head,
+    Tail,
+    Sample,
+    Dedup,
+    Fill,
+    DropNulls,
+    Interpolate,
+    Completions,
 }
 
 #[derive(Debug, PartialEq)]
@@ -9,26 +37,67 @@ pub enum ConfigError {
     UnknownCommand(String),
     MissingArguments(String),
     FileNotFound(String),
+    UnknownOption(String),
+    InvalidValue {
+        option: String,
+        message: String,
+    },
+    /// `--help`, `--version` or a bare command: the text to print before exiting successfully.
+    DisplayHelp(String),
 }
 
 #[derive(Debug)]
 pub struct Config {
     command: Command,
     filename: String,
+    read_options: CsvReadOptions,
+    columns: Vec<String>,
+    top: usize,
+    normalize: bool,
+    bins: Bins,
+    method: CorrMethod,
+    covariance: bool,
+    test: Option<TestKind>,
+    by: Option<String>,
+    mu: Option<f64>,
+    csv_rows: bool,
+    query: Option<String>,
+    files: Vec<String>,
+    output: Option<String>,
+    format: OutputFormat,
+    completions: Option<clap_complete::Shell>,
+    selection: Option<RowSelection>,
+    keep: Keep,
+    report: bool,
+    fill: Option<FillStrategy>,
+    how: DropHow,
+    thresh: Option<usize>,
+    detailed: bool,
 }
 
+/// Number of values `freq` (and patterns `na --detailed`) shows unless `--top` says otherwise.
+pub const DEFAULT_TOP: usize = 10;
+
+/// Rows `head`, `tail` and `sample` print unless `-n` says otherwise.
+pub const DEFAULT_ROWS: usize = 10;
+
 impl std::fmt::Display for ConfigError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ConfigError::UnknownCommand(cmd) => {
                 write!(
                     f,
-                    "Unknown command '{}'. Available: na, info",
+                    "Unknown command '{}'. Available: na, info, dialect, head, tail, sample, dedup, fill, dropna, interpolate, freq, hist, corr, test, outliers, sql, shell, completions",
                     cmd
                 )
             }
             ConfigError::MissingArguments(msg) => write!(f, "{}", msg),
             ConfigError::FileNotFound(file) => write!(f, "File '{}' not found", file),
+            ConfigError::UnknownOption(option) => write!(f, "Unknown option '{}'", option),
+            ConfigError::InvalidValue { option, message } => {
+                write!(f, "Invalid value for '{}': {}", option, message)
+            }
+            ConfigError::DisplayHelp(text) => write!(f, "{}", text),
         }
     }
 }
@@ -37,7 +106,152 @@ impl std::error::Error for ConfigError {}
 
 impl Config {
     pub fn new(command: Command, filename: String) -> Config {
-        Config { command, filename }
+        Config {
+            command,
+            files: vec![filename.clone()],
+            filename,
+            read_options: CsvReadOptions::default(),
+            columns: Vec::new(),
+            top: DEFAULT_TOP,
+            normalize: false,
+            bins: Bins::default(),
+            method: CorrMethod::default(),
+            covariance: false,
+            test: None,
+            by: None,
+            mu: None,
+            csv_rows: false,
+            query: None,
+            output: None,
+            format: OutputFormat::default(),
+            completions: None,
+            selection: None,
+            keep: Keep::default(),
+            report: false,
+            fill: None,
+            how: DropHow::default(),
+            thresh: None,
+            detailed: false,
+        }
+    }
+
+    pub fn with_read_options(mut self, read_options: CsvReadOptions) -> Config {
+        self.read_options = read_options;
+        self
+    }
+
+    pub fn with_columns(mut self, columns: Vec<String>) -> Config {
+        self.columns = columns;
+        self
+    }
+
+    pub fn with_top(mut self, top: usize) -> Config {
+        self.top = top;
+        self
+    }
+
+    pub fn with_normalize(mut self, normalize: bool) -> Config {
+        self.normalize = normalize;
+        self
+    }
+
+    pub fn with_bins(mut self, bins: Bins) -> Config {
+        self.bins = bins;
+        self
+    }
+
+    pub fn with_method(mut self, method: CorrMethod) -> Config {
+        self.method = method;
+        self
+    }
+
+    pub fn with_covariance(mut self, covariance: bool) -> Config {
+        self.covariance = covariance;
+        self
+    }
+
+    pub fn with_test(mut self, test: TestKind) -> Config {
+        self.test = Some(test);
+        self
+    }
+
+    pub fn with_by(mut self, by: Option<String>) -> Config {
+        self.by = by;
+        self
+    }
+
+    pub fn with_mu(mut self, mu: Option<f64>) -> Config {
+        self.mu = mu;
+        self
+    }
+
+    pub fn with_csv_rows(mut self, csv_rows: bool) -> Config {
+        self.csv_rows = csv_rows;
+        self
+    }
+
+    pub fn with_query(mut self, query: String) -> Config {
+        self.query = Some(query);
+        self
+    }
+
+    /// Replaces the input files; the first becomes `filename`.
+    pub fn with_files(mut self, files: Vec<String>) -> Config {
+        if let Some(first) = files.first() {
+            self.filename = first.clone();
+        }
+        self.files = files;
+        self
+    }
+
+    pub fn with_output(mut self, output: Option<String>) -> Config {
+        self.output = output;
+        self
+    }
+
+    pub fn with_format(mut self, format: OutputFormat) -> Config {
+        self.format = format;
+        self
+    }
+
+    pub fn with_completions(mut self, shell: clap_complete::Shell) -> Config {
+        self.completions = Some(shell);
+        self
+    }
+
+    pub fn with_selection(mut self, selection: RowSelection) -> Config {
+        self.selection = Some(selection);
+        self
+    }
+
+    pub fn with_keep(mut self, keep: Keep) -> Config {
+        self.keep = keep;
+        self
+    }
+
+    pub fn with_report(mut self, report: bool) -> Config {
+        self.report = report;
+        self
+    }
+
+    pub fn with_fill(mut self, strategy: FillStrategy) -> Config {
+        self.fill = Some(strategy);
+        self
+    }
+
+    pub fn with_how(mut self, how: DropHow) -> Config {
+        self.how = how;
+        self
+    }
+
+    pub fn with_thresh(mut self, thresh: Option<usize>) -> Config {
+        self.thresh = thresh;
+        self
+    }
+
+    pub fn with_detailed(mut self, detailed: bool) -> Config {
+        self.detailed = detailed;
+        self
     }
 
     pub fn command(&self) -> &Command {
@@ -47,44 +261,630 @@ impl Config {
     pub fn filename(&self) -> &str {
         &self.filename
     }
+
+    pub fn read_options(&self) -> &CsvReadOptions {
+        &self.read_options
+    }
+
+    /// Column names given after the file; empty means all columns.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    pub fn top(&self) -> usize {
+        self.top
+    }
+
+    pub fn normalize(&self) -> bool {
+        self.normalize
+    }
+
+    pub fn bins(&self) -> &Bins {
+        &self.bins
+    }
+
+    pub fn method(&self) -> CorrMethod {
+        self.method
+    }
+
+    /// Whether `corr` should print covariances instead of correlations.
+    pub fn covariance(&self) -> bool {
+        self.covariance
+    }
+
+    /// The hypothesis test requested with `test <KIND>`.
+    pub fn test(&self) -> Option<TestKind> {
+        self.test
+    }
+
+    /// Column whose values split the tested column into groups.
+    pub fn by(&self) -> Option<&str> {
+        self.by.as_deref()
+    }
+
+    /// Hypothesized mean for the one-sample t-test.
+    pub fn mu(&self) -> Option<f64> {
+        self.mu
+    }
+
+    /// Whether `outliers` and `sql` should print rows as CSV instead of a table.
+    pub fn csv_rows(&self) -> bool {
+        self.csv_rows
+    }
+
+    /// The statement `sql` runs.
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// Every input file; `sql` registers each as a table named by its file stem.
+    pub fn files(&self) -> &[String] {
+        &self.files
+    }
+
+    /// File results are written to; standard output when `None`.
+    pub fn output(&self) -> Option<&str> {
+        self.output.as_deref()
+    }
+
+    pub fn format(&self) -> OutputFormat {
+        self.format
+    }
+
+    /// Shell whose completion script `completions` prints.
+    pub fn completions(&self) -> Option<clap_complete::Shell> {
+        self.completions
+    }
+
+    /// Which of a set of identical rows `dedup` keeps.
+    pub fn keep(&self) -> Keep {
+        self.keep
+    }
+
+    /// Whether `dedup` should print the duplicates report instead of the rows.
+    pub fn report(&self) -> bool {
+        self.report
+    }
+
+    /// How `fill` replaces nulls.
+    pub fn fill(&self) -> Option<&FillStrategy> {
+        self.fill.as_ref()
+    }
+
+    /// Whether `dropna` drops rows with any null or only all-null rows.
+    pub fn how(&self) -> DropHow {
+        self.how
+    }
+
+    /// Non-null values a row needs to survive `dropna`, overriding `how`.
+    pub fn thresh(&self) -> Option<usize> {
+        self.thresh
+    }
+
+    /// Whether `na` adds row counts, missingness patterns and nullity correlation.
+    pub fn detailed(&self) -> bool {
+        self.detailed
+    }
+
+    /// Rows `head`, `tail` and `sample` read from the file.
+    pub fn selection(&self) -> Option<RowSelection> {
+        self.selection
+    }
 }
 
 pub fn parse_command(command: String) -> Result<Command, ConfigError> {
     match command.to_lowercase().as_str() {
         "na" => Ok(Command::CheckNAs),
         "info" => Ok(Command::Info),
+        "dialect" => Ok(Command::Dialect),
+        "freq" => Ok(Command::Freq),
+        "hist" => Ok(Command::Hist),
+        "corr" => Ok(Command::Corr),
+        "test" => Ok(Command::Test),
+        "outliers" => Ok(Command::Outliers),
+        "sql" => Ok(Command::Sql),
+        "shell" => Ok(Command::Shell),
+        "head" => Ok(Command::Head),
+        "tail" => Ok(Command::Tail),
+        "sample" => Ok(Command::Sample),
+        "dedup" => Ok(Command::Dedup),
+        "fill" => Ok(Command::Fill),
+        "dropna" => Ok(Command::DropNulls),
+        "interpolate" => Ok(Command::Interpolate),
+        "completions" => Ok(Command::Completions),
         _ => Err(ConfigError::UnknownCommand(command)),
     }
 }
 
-pub fn print_help() {
-    println!("CSV Analytics Tool");
-    println!();
-    println!("USAGE:");
-    println!("    csv_processor <COMMAND> <FILE>");
-    println!();
-    println!("COMMANDS:");
-    println!("    na      Check for missing values (NAs) in CSV file");
-    println!("    info    Calculate statistics for CSV file");
-    println!();
-    println!("EXAMPLES:");
-    println!("    csv_processor na sample.csv");
-    println!("    csv_processor info sample.csv");
+/// The command-line interface; also used to generate shell completions.
+pub fn cli() -> clap::Command {
+    Cli::command()
 }
 
+/// Parses the full argument list, program name included.
+///
+/// `--help` and `--version` come back as [`ConfigError::DisplayHelp`] holding
+/// the text to print, so the caller decides how to exit.
 pub fn parse_config(args: &[String]) -> Result<Config, ConfigError> {
-    // Check for help flags
-    if args.len() == 1 || (args.len() == 2 && (args[1] == "--help" || args[1] == "-h" || args[1] == "help")) {
-        print_help();
-        std::process::exit(0);
-    }
+    let cli = Cli::try_parse_from(args).map_err(config_error)?;
+    let global = cli.global;
+    let read_options = CsvReadOptions {
+        encoding: global.encoding,
+        lossy: global.lossy,
+        delimiter: global.delimiter,
+        quote: global.quote,
+        has_header: global.no_header.then_some(false),
+        threads: global.threads,
+        ..CsvReadOptions::default()
+    };
+    let with_file = |command: Command, file: String| {
+        Config::new(command, file)
+            .with_read_options(read_options.clone())
+            .with_output(global.output.clone())
+            .with_format(global.format)
+    };
+    let with_files = |command: Command, files: Vec<String>| {
+        with_file(command, files[0].clone()).with_files(files)
+    };
+
+    let config = match cli.command {
+        Subcommand::Na {
+            files,
+            detailed,
+            top,
+        } => with_files(Command::CheckNAs, files)
+            .with_detailed(detailed)
+            .with_top(top),
+        Subcommand::Info { files } => with_files(Command::Info, files),
+        Subcommand::Dialect { files } => with_files(Command::Dialect, files),
+        Subcommand::Freq {
+            file,
+            columns,
+            top,
+            normalize,
+        } => with_file(Command::Freq, file)
+            .with_columns(columns)
+            .with_top(top)
+            .with_normalize(normalize),
+        Subcommand::Hist {
+            file,
+            columns,
+            bins,
+            bin_width,
+        } => with_file(Command::Hist, file)
+            .with_columns(columns)
+            .with_bins(bin_width.map(Bins::Width).or(bins).unwrap_or_default()),
+        Subcommand::Corr {
+            file,
+            columns,
+            method,
+            cov,
+        } => with_file(Command::Corr, file)
+            .with_columns(columns)
+            .with_method(method)
+            .with_covariance(cov),
+        Subcommand::Test {
+            kind,
+            file,
+            columns,
+            by,
+            mu,
+        } => with_file(Command::Test, file)
+            .with_test(kind)
+            .with_columns(columns)
+            .with_by(by)
+            .with_mu(mu),
+        Subcommand::Outliers { file, columns, csv } => with_file(Command::Outliers, file)
+            .with_columns(columns)
+            .with_csv_rows(csv),
+        Subcommand::Sql { query, files, csv } => with_files(Command::Sql, files)
+            .with_query(query)
+            .with_csv_rows(csv),
+        Subcommand::Dedup {
+            file,
+            columns,
+            keep,
+            report,
+        } => with_file(Command::Dedup, file)
+            .with_columns(columns)
+            .with_keep(keep)
+            .with_report(report),
+        Subcommand::Fill {
+            file,
+            columns,
+            strategy,
+            value,
+        } => with_file(Command::Fill, file)
+            .with_columns(columns)
+            .with_fill(match value {
+                Some(value) => FillStrategy::Value(parse_value(&value)),
+                None => strategy.unwrap_or(FillStrategy::Forward),
+            }),
+        Subcommand::Dropna {
+            file,
+            columns,
+            how,
+            thresh,
+        } => with_file(Command::DropNulls, file)
+            .with_columns(columns)
+            .with_how(how)
+            .with_thresh(thresh),
+        Subcommand::Interpolate { file, columns } => {
+            with_file(Command::Interpolate, file).with_columns(columns)
+        }
+        Subcommand::Shell { file } => with_file(Command::Shell, file),
+        Subcommand::Head { file, columns, n } => with_file(Command::Head, file)
+            .with_columns(columns)
+            .with_selection(RowSelection::Head(n)),
+        Subcommand::Tail { file, columns, n } => with_file(Command::Tail, file)
+            .with_columns(columns)
+            .with_selection(RowSelection::Tail(n)),
+        Subcommand::Sample {
+            file,
+            columns,
+            n,
+            frac,
+            seed,
+        } => with_file(Command::Sample, file)
+            .with_columns(columns)
+            .with_selection(match frac {
+                Some(fraction) => RowSelection::Fraction { fraction, seed },
+                None => RowSelection::Sample {
+                    n: n.unwrap_or(DEFAULT_ROWS),
+                    seed,
+                },
+            }),
+        Subcommand::Completions { shell } => {
+            with_file(Command::Completions, String::new()).with_completions(shell)
+        }
+    };
+    Ok(config)
+}
+
+#[derive(Parser)]
+#[command(
+    name = "csv_processor",
+    version,
+    about = "CSV Analytics Tool",
+    arg_required_else_help = true,
+    after_help = EXAMPLES
+)]
+struct Cli {
+    #[command(flatten)]
+    global: GlobalArgs,
+    #[command(subcommand)]
+    command: Subcommand,
+}
+
+/// Options every command accepts, before or after its name.
+#[derive(Args)]
+struct GlobalArgs {
+    /// Source encoding: utf-8, utf-16le, utf-16be, latin-1, windows-1250..windows-1258
+    /// (detected by default)
+    #[arg(short = 'e', long, global = true, value_name = "NAME")]
+    encoding: Option<Encoding>,
+    /// Replace invalid bytes instead of failing
+    #[arg(long, global = true)]
+    lossy: bool,
+    /// Field separator, e.g. ';' or 'tab' (sniffed by default)
+    #[arg(short = 'd', long, global = true, value_name = "CHAR", value_parser = parse_separator)]
+    delimiter: Option<u8>,
+    /// Quote character (default '"')
+    #[arg(long, global = true, value_name = "CHAR", value_parser = parse_separator)]
+    quote: Option<u8>,
+    /// Treat the first row as data
+    #[arg(long, global = true)]
+    no_header: bool,
+    /// Worker threads for parsing (default: all cores)
+    #[arg(short = 'j', long, global = true, value_name = "N", value_parser = parse_positive)]
+    threads: Option<usize>,
+    /// Write results to PATH instead of standard output
+    #[arg(short = 'o', long, global = true, value_name = "PATH")]
+    output: Option<String>,
+    /// Format of result tables: table, csv, tsv, json, ndjson, markdown, html or latex
+    #[arg(
+        short = 'f',
+        long,
+        global = true,
+        value_name = "FORMAT",
+        default_value_t
+    )]
+    format: OutputFormat,
+}
 
-    if args.len() < 3 {
-        return Err(ConfigError::MissingArguments(
-            "Not enough arguments passed!".to_string(),
-        ));
+#[derive(clap::Subcommand)]
+enum Subcommand {
+    /// Check for missing values (NAs)
+    Na {
+        /// Input files
+        #[arg(required = true, value_name = "FILE")]
+        files: Vec<String>,
+        /// Also show complete rows, missingness patterns and nullity correlation
+        #[arg(long)]
+        detailed: bool,
+        /// Missingness patterns shown with --detailed
+        #[arg(short = 'n', long, value_name = "N", default_value_t = DEFAULT_TOP, value_parser = parse_positive)]
+        top: usize,
+    },
+    /// Calculate statistics for each column
+    Info {
+        /// Input files
+        #[arg(required = true, value_name = "FILE")]
+        files: Vec<String>,
+    },
+    /// Guess delimiter, quote, header and line terminator
+    Dialect {
+        /// Input files
+        #[arg(required = true, value_name = "FILE")]
+        files: Vec<String>,
+    },
+    /// Show the most frequent values of each column
+    Freq {
+        /// Input file
+        file: String,
+        /// Columns to use (default: all)
+        columns: Vec<String>,
+        /// Values shown per column
+        #[arg(short = 'n', long, value_name = "N", default_value_t = DEFAULT_TOP, value_parser = parse_positive)]
+        top: usize,
+        /// Show proportions instead of counts
+        #[arg(long)]
+        normalize: bool,
+    },
+    /// Draw a histogram of each numeric column
+    Hist {
+        /// Input file
+        file: String,
+        /// Columns to use (default: all)
+        columns: Vec<String>,
+        /// A count, 'sturges', 'fd' or edges like '0,18,65,100' (default sturges);
+        /// at most 100000 bins
+        #[arg(long, value_name = "SPEC")]
+        bins: Option<Bins>,
+        /// Bins of a fixed width
+        #[arg(long, value_name = "W", conflicts_with = "bins", value_parser = parse_width)]
+        bin_width: Option<f64>,
+    },
+    /// Correlation matrix of the numeric columns
+    Corr {
+        /// Input file
+        file: String,
+        /// Columns to use (default: all)
+        columns: Vec<String>,
+        /// pearson, spearman or kendall
+        #[arg(long, value_name = "NAME", default_value_t)]
+        method: CorrMethod,
+        /// Print the covariance matrix instead
+        #[arg(long)]
+        cov: bool,
+    },
+    /// Hypothesis test: ttest, paired, chi2, mannwhitney or ks
+    Test {
+        /// ttest, paired, chi2, mannwhitney or ks
+        kind: TestKind,
+        /// Input file
+        file: String,
+        /// One column with --by, two columns, or one for a one-sample t-test
+        #[arg(required = true)]
+        columns: Vec<String>,
+        /// Compare the two groups defined by COLUMN
+        #[arg(long, value_name = "COLUMN")]
+        by: Option<String>,
+        /// Hypothesized mean for a one-sample t-test
+        #[arg(long, value_name = "VALUE", allow_negative_numbers = true)]
+        mu: Option<f64>,
+    },
+    /// Flag outliers by IQR fences, z-score and MAD
+    Outliers {
+        /// Input file
+        file: String,
+        /// Columns to use (default: all)
+        columns: Vec<String>,
+        /// Print the flagged rows as CSV instead of the summary
+        #[arg(long)]
+        csv: bool,
+    },
+    /// Run a SELECT over the files, each a table named by its file stem
+    Sql {
+        /// The SELECT statement
+        query: String,
+        /// Input files
+        #[arg(required = true, value_name = "FILE")]
+        files: Vec<String>,
+        /// Print the result as CSV (same as --format csv)
+        #[arg(long)]
+        csv: bool,
+    },
+    /// Print the first rows
+    Head {
+        /// Input file
+        file: String,
+        /// Columns to show (default: all)
+        columns: Vec<String>,
+        /// Number of rows
+        #[arg(short = 'n', long = "rows", value_name = "N", default_value_t = DEFAULT_ROWS)]
+        n: usize,
+    },
+    /// Print the last rows
+    Tail {
+        /// Input file
+        file: String,
+        /// Columns to show (default: all)
+        columns: Vec<String>,
+        /// Number of rows
+        #[arg(short = 'n', long = "rows", value_name = "N", default_value_t = DEFAULT_ROWS)]
+        n: usize,
+    },
+    /// Print randomly chosen rows, reading the file once
+    Sample {
+        /// Input file
+        file: String,
+        /// Columns to show (default: all)
+        columns: Vec<String>,
+        /// Number of rows (default 10)
+        #[arg(short = 'n', long = "rows", value_name = "N")]
+        n: Option<usize>,
+        /// Keep each row with this probability instead
+        #[arg(long, value_name = "F", conflicts_with = "n", value_parser = parse_fraction)]
+        frac: Option<f64>,
+        /// Seed for a reproducible sample
+        #[arg(long, value_name = "SEED")]
+        seed: Option<u64>,
+    },
+    /// Drop repeated rows, comparing the given columns (default: all)
+    Dedup {
+        /// Input file
+        file: String,
+        /// Columns that identify a row (default: all)
+        columns: Vec<String>,
+        /// Which copy to keep: first, last or none
+        #[arg(long, value_name = "WHICH", default_value_t)]
+        keep: Keep,
+        /// Print the data-quality report with its duplicates section instead
+        #[arg(long)]
+        report: bool,
+    },
+    /// Fill nulls and write the cleaned file (CSV unless --format says otherwise)
+    #[command(group = clap::ArgGroup::new("with").required(true))]
+    Fill {
+        /// Input file
+        file: String,
+        /// Columns to fill (default: every column the strategy applies to)
+        columns: Vec<String>,
+        /// forward, backward, mean, median or mode
+        #[arg(long, value_name = "NAME", group = "with")]
+        strategy: Option<FillStrategy>,
+        /// Fill with this value instead, typed like a CSV cell
+        #[arg(
+            long,
+            value_name = "VALUE",
+            group = "with",
+            allow_negative_numbers = true
+        )]
+        value: Option<String>,
+    },
+    /// Drop rows with nulls and write the cleaned file
+    Dropna {
+        /// Input file
+        file: String,
+        /// Columns checked for nulls (default: all)
+        columns: Vec<String>,
+        /// Drop rows with any null or only rows that are all null
+        #[arg(long, value_name = "any|all", default_value_t)]
+        how: DropHow,
+        /// Keep rows with at least N non-null values instead
+        #[arg(long, value_name = "N", conflicts_with = "how")]
+        thresh: Option<usize>,
+    },
+    /// Linearly interpolate nulls in numeric columns and write the cleaned file
+    Interpolate {
+        /// Input file
+        file: String,
+        /// Columns to interpolate (default: all numeric)
+        columns: Vec<String>,
+    },
+    /// Load the file once and explore it interactively
+    Shell {
+        /// Input file
+        file: String,
+    },
+    /// Print a completion script for bash, zsh, fish, elvish or powershell
+    Completions {
+        /// Shell to complete for
+        shell: clap_complete::Shell,
+    },
+}
+
+const EXAMPLES: &str = "\
+Examples:
+  csv_processor na sample.csv
+  csv_processor na titanic.csv --detailed
+  csv_processor info jan.csv feb.csv --format json -o stats.json
+  csv_processor info export.csv --encoding windows-1252
+  csv_processor dialect unknown.txt
+  csv_processor head titanic.csv -n 5
+  csv_processor sample huge.csv -n 100 --seed 42 --format csv
+  csv_processor dedup crm.csv email --keep last -f csv -o clean.csv
+  csv_processor fill titanic.csv Age --strategy median -o filled.csv
+  csv_processor dropna titanic.csv Age Embarked --how any -o complete.csv
+  csv_processor freq titanic.csv Sex Embarked --top 5
+  csv_processor hist titanic.csv Fare --bins fd
+  csv_processor corr titanic.csv --method spearman
+  csv_processor test ttest titanic.csv Fare --by Survived
+  csv_processor outliers titanic.csv Fare --csv > flagged.csv
+  csv_processor shell titanic.csv
+  csv_processor sql \"SELECT Pclass, AVG(Fare) FROM titanic GROUP BY Pclass\" titanic.csv
+  csv_processor completions bash > /etc/bash_completion.d/csv_processor";
+
+/// A `--value` typed the way a CSV cell would be: integer, float, boolean or text.
+fn parse_value(text: &str) -> CellValue {
+    parse_column(vec![text]).get(0).unwrap_or(CellValue::Null)
+}
+
+/// Maps clap's errors onto [`ConfigError`], keeping its rendered message where
+/// there is nothing more specific.
+fn config_error(error: clap::Error) -> ConfigError {
+    let context = |kind: ContextKind| match error.get(kind) {
+        Some(ContextValue::String(value)) => value.clone(),
+        _ => String::new(),
+    };
+    match error.kind() {
+        ErrorKind::DisplayHelp
+        | ErrorKind::DisplayVersion
+        | ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand => {
+            ConfigError::DisplayHelp(error.render().to_string())
+        }
+        ErrorKind::InvalidSubcommand => {
+            ConfigError::UnknownCommand(context(ContextKind::InvalidSubcommand))
+        }
+        ErrorKind::UnknownArgument => ConfigError::UnknownOption(context(ContextKind::InvalidArg)),
+        ErrorKind::InvalidValue | ErrorKind::ValueValidation => ConfigError::InvalidValue {
+            option: context(ContextKind::InvalidArg),
+            message: match error.source() {
+                Some(source) => source.to_string(),
+                None => format!("'{}'", context(ContextKind::InvalidValue)),
+            },
+        },
+        _ => {
+            let rendered = error.render().to_string();
+            let message = rendered.strip_prefix("error: ").unwrap_or(&rendered);
+            ConfigError::MissingArguments(message.trim_end().to_string())
+        }
     }
+}
 
-    let command = parse_command(args[1].clone())?;
-    Ok(Config::new(command, args[2].clone()))
+fn parse_width(value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|&w| w.is_finite() && w > 0.0)
+        .ok_or_else(|| format!("expected a positive finite number, got '{}'", value))
+}
+
+fn parse_fraction(value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|f| (0.0..=1.0).contains(f))
+        .ok_or_else(|| format!("expected a number between 0 and 1, got '{}'", value))
+}
+
+fn parse_positive(value: &str) -> Result<usize, String> {
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| format!("expected a positive number, got '{}'", value))
+}
+
+fn parse_separator(value: &str) -> Result<u8, String> {
+    match value {
+        "tab" | "\\t" => Ok(b'\t'),
+        "space" => Ok(b' '),
+        _ if value.len() == 1 => Ok(value.as_bytes()[0]),
+        _ => Err(format!(
+            "expected a single ASCII character, got '{}'",
+            value
+        )),
+    }
 }
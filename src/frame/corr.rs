@@ -0,0 +1,227 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::frame::DataFrameError;
+use crate::series::ColumnArray;
+use crate::stats::ranks;
+use crate::{DataFrame, Dtype};
+
+/// Correlation coefficient computed by `DataFrame::corr`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CorrMethod {
+    /// Linear correlation of the values.
+    #[default]
+    Pearson,
+    /// Pearson correlation of the ranks (ties get their average rank).
+    Spearman,
+    /// Kendall's tau-b, based on concordant and discordant pairs.
+    Kendall,
+}
+
+impl fmt::Display for CorrMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CorrMethod::Pearson => write!(f, "pearson"),
+            CorrMethod::Spearman => write!(f, "spearman"),
+            CorrMethod::Kendall => write!(f, "kendall"),
+        }
+    }
+}
+
+impl FromStr for CorrMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pearson" => Ok(CorrMethod::Pearson),
+            "spearman" => Ok(CorrMethod::Spearman),
+            "kendall" => Ok(CorrMethod::Kendall),
+            _ => Err(format!(
+                "unknown method '{}', expected pearson, spearman or kendall",
+                s
+            )),
+        }
+    }
+}
+
+impl DataFrame {
+    /// Pairwise correlation of the integer, float and boolean columns.
+    ///
+    /// Each pair uses only the rows where both values are present. The result
+    /// has a `column` label column followed by one column per numeric column;
+    /// a coefficient is null when a pair has fewer than two rows or no variance.
+    pub fn corr(&self, method: CorrMethod) -> Result<DataFrame, DataFrameError> {
+        let coefficient = match method {
+            CorrMethod::Pearson => pearson,
+            CorrMethod::Spearman => spearman,
+            CorrMethod::Kendall => kendall,
+        };
+        self.pairwise_matrix(coefficient)
+    }
+
+    /// Pairwise sample covariance (`n - 1` denominator) of the integer, float
+    /// and boolean columns, laid out like `corr`.
+    pub fn cov(&self) -> Result<DataFrame, DataFrameError> {
+        self.pairwise_matrix(covariance)
+    }
+
+    fn pairwise_matrix(
+        &self,
+        statistic: fn(&[f64], &[f64]) -> Option<f64>,
+    ) -> Result<DataFrame, DataFrameError> {
+        let numeric: Vec<(&String, &dyn ColumnArray)> = self
+            .headers()
+            .iter()
+            .zip(self.columns().iter().map(|col| col.as_ref()))
+            .filter(|(_, col)| {
+                matches!(col.dtype(), Dtype::Integer | Dtype::Float | Dtype::Boolean)
+            })
+            .collect();
+        if numeric.is_empty() {
+            return Err(DataFrameError::TypeError(
+                "no numeric columns to correlate".to_string(),
+            ));
+        }
+
+        let n = numeric.len();
+        let mut matrix = vec![vec![None; n]; n];
+        for i in 0..n {
+            for j in i..n {
+                let (x, y) = complete_pairs(numeric[i].1, numeric[j].1);
+                let value = statistic(&x, &y);
+                matrix[i][j] = value;
+                matrix[j][i] = value;
+            }
+        }
+
+        let mut headers = vec!["column".to_string()];
+        headers.extend(numeric.iter().map(|(name, _)| (*name).clone()));
+        let labels: Vec<String> = numeric.iter().map(|(name, _)| (*name).clone()).collect();
+        let mut columns: Vec<Box<dyn ColumnArray>> = vec![labels.into()];
+        columns.extend(matrix.into_iter().map(|column| column.into()));
+        DataFrame::from_columns(Some(headers), columns)
+    }
+}
+
+/// Values of the rows where both columns hold a number (not null, not NaN).
+fn complete_pairs(x: &dyn ColumnArray, y: &dyn ColumnArray) -> (Vec<f64>, Vec<f64>) {
+    (0..x.len().min(y.len()))
+        .filter_map(|row| match (x.get_f64(row), y.get_f64(row)) {
+            (Some(a), Some(b)) if !a.is_nan() && !b.is_nan() => Some((a, b)),
+            _ => None,
+        })
+        .unzip()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn covariance(x: &[f64], y: &[f64]) -> Option<f64> {
+    if x.len() < 2 {
+        return None;
+    }
+    let (mx, my) = (mean(x), mean(y));
+    let sum: f64 = x.iter().zip(y).map(|(a, b)| (a - mx) * (b - my)).sum();
+    Some(sum / (x.len() - 1) as f64)
+}
+
+pub(crate) fn pearson(x: &[f64], y: &[f64]) -> Option<f64> {
+    if x.len() < 2 {
+        return None;
+    }
+    let (mx, my) = (mean(x), mean(y));
+    let mut sxy = 0.0;
+    let mut sxx = 0.0;
+    let mut syy = 0.0;
+    for (a, b) in x.iter().zip(y) {
+        sxy += (a - mx) * (b - my);
+        sxx += (a - mx) * (a - mx);
+        syy += (b - my) * (b - my);
+    }
+    if sxx == 0.0 || syy == 0.0 {
+        return None;
+    }
+    Some((sxy / (sxx * syy).sqrt()).clamp(-1.0, 1.0))
+}
+
+fn spearman(x: &[f64], y: &[f64]) -> Option<f64> {
+    pearson(&ranks(x), &ranks(y))
+}
+
+/// Kendall's tau-b, which corrects for ties in either variable.
+///
+/// Uses Knight's algorithm: after sorting the pairs by `x`, the discordant
+/// pairs are the swaps a merge sort by `y` makes, so this runs in
+/// O(n log n) rather than comparing every pair.
+fn kendall(x: &[f64], y: &[f64]) -> Option<f64> {
+    let n = x.len();
+    if n < 2 {
+        return None;
+    }
+    let mut pairs: Vec<(f64, f64)> = x.iter().copied().zip(y.iter().copied()).collect();
+    pairs.sort_by(|a, b| compare(a.0, b.0).then(compare(a.1, b.1)));
+
+    let all = (n * (n - 1) / 2) as i64;
+    let tied_x = tied_pairs(&pairs, |a, b| a.0 == b.0);
+    let tied_both = tied_pairs(&pairs, |a, b| a == b);
+
+    let mut ys: Vec<f64> = pairs.iter().map(|pair| pair.1).collect();
+    let discordant = merge_count_swaps(&mut ys, &mut vec![0.0; n]);
+    let tied_y = tied_pairs(&ys, |a, b| a == b);
+
+    // in f64: the product of two pair counts overflows i64 past ~78k rows
+    let denominator = ((all - tied_x) as f64 * (all - tied_y) as f64).sqrt();
+    if denominator == 0.0 {
+        return None;
+    }
+    let score = all - tied_x - tied_y + tied_both - 2 * discordant;
+    Some(score as f64 / denominator)
+}
+
+fn compare(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
+
+/// Pairs within the runs of equal neighbours of the sorted `values`.
+fn tied_pairs<T>(values: &[T], equal: impl Fn(&T, &T) -> bool) -> i64 {
+    let mut total = 0;
+    let mut run = 1i64;
+    for window in values.windows(2) {
+        if equal(&window[0], &window[1]) {
+            run += 1;
+        } else {
+            total += run * (run - 1) / 2;
+            run = 1;
+        }
+    }
+    total + run * (run - 1) / 2
+}
+
+/// Sorts `values` and returns how many pairs were out of order; equal values
+/// are never counted.
+fn merge_count_swaps(values: &mut [f64], buffer: &mut [f64]) -> i64 {
+    let n = values.len();
+    if n < 2 {
+        return 0;
+    }
+    let middle = n / 2;
+    let mut swaps = merge_count_swaps(&mut values[..middle], &mut buffer[..middle])
+        + merge_count_swaps(&mut values[middle..], &mut buffer[middle..]);
+
+    let (mut left, mut right) = (0, middle);
+    for slot in buffer[..n].iter_mut() {
+        if right == n || (left < middle && values[left] <= values[right]) {
+            *slot = values[left];
+            left += 1;
+        } else {
+            // every value still waiting on the left is larger
+            swaps += (middle - left) as i64;
+            *slot = values[right];
+            right += 1;
+        }
+    }
+    values.copy_from_slice(&buffer[..n]);
+    swaps
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use super::{DataFrame, DataFrameError};
+use crate::scalar::ValueKey;
+use crate::series::BooleanColumn;
+
+/// Which row of a group of identical rows counts as the original.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Keep {
+    /// The first occurrence; later ones are duplicates.
+    #[default]
+    First,
+    /// The last occurrence; earlier ones are duplicates.
+    Last,
+    /// None of them: every row of a repeated group is a duplicate.
+    None,
+}
+
+impl fmt::Display for Keep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Keep::First => "first",
+            Keep::Last => "last",
+            Keep::None => "none",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Keep {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "first" => Ok(Keep::First),
+            "last" => Ok(Keep::Last),
+            "none" | "false" => Ok(Keep::None),
+            _ => Err(format!(
+                "unknown keep '{}', expected first, last or none",
+                s
+            )),
+        }
+    }
+}
+
+impl DataFrame {
+    /// Flags the rows that repeat another row on the `subset` columns (all
+    /// columns when empty); `keep` decides which occurrence is not flagged.
+    ///
+    /// Values are compared as [`ValueKey`]s: floats by bit pattern and nulls
+    /// equal to each other.
+    pub fn duplicated<S: AsRef<str>>(
+        &self,
+        subset: &[S],
+        keep: Keep,
+    ) -> Result<BooleanColumn, DataFrameError> {
+        let flags = self.duplicate_flags(subset, keep)?;
+        Ok(flags.into_iter().map(Some).collect())
+    }
+
+    /// The frame without the rows [`DataFrame::duplicated`] flags, in their
+    /// original order.
+    pub fn unique<S: AsRef<str>>(
+        &self,
+        subset: &[S],
+        keep: Keep,
+    ) -> Result<DataFrame, DataFrameError> {
+        let flags = self.duplicate_flags(subset, keep)?;
+        let indices: Vec<usize> = (0..flags.len()).filter(|&row| !flags[row]).collect();
+        Ok(self.take(&indices))
+    }
+
+    fn duplicate_flags<S: AsRef<str>>(
+        &self,
+        subset: &[S],
+        keep: Keep,
+    ) -> Result<Vec<bool>, DataFrameError> {
+        let mut flags = vec![false; self.rows_count()];
+        for group in self.duplicate_groups(subset)? {
+            let original = match keep {
+                Keep::First => group.first().copied(),
+                Keep::Last => group.last().copied(),
+                Keep::None => None,
+            };
+            for &row in &group {
+                flags[row] = Some(row) != original;
+            }
+        }
+        Ok(flags)
+    }
+
+    /// Row indices of every set of two or more identical rows on `subset`,
+    /// ordered by their first row.
+    pub(crate) fn duplicate_groups<S: AsRef<str>>(
+        &self,
+        subset: &[S],
+    ) -> Result<Vec<Vec<usize>>, DataFrameError> {
+        let keyed = self.subset_indices(subset)?;
+
+        let mut group_of: HashMap<Vec<ValueKey>, usize> = HashMap::new();
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for row in 0..self.rows_count() {
+            let key: Vec<ValueKey> = keyed
+                .iter()
+                .map(|&col| ValueKey::from(self.columns[col].get(row)))
+                .collect();
+            let group = *group_of.entry(key).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(row);
+        }
+        groups.retain(|rows| rows.len() > 1);
+        Ok(groups)
+    }
+}
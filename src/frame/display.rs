@@ -0,0 +1,398 @@
+//! The box-drawing table behind `DataFrame`'s `Display`, configured by
+//! [`DisplayOptions`].
+//!
+//! Widths are measured in terminal columns per grapheme, so wide characters
+//! and combining marks line up and truncation never splits a character.
+
+use std::fmt;
+
+use terminal_size::{terminal_size, Width};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use super::DataFrame;
+use crate::CellValue;
+
+/// Horizontal placement of cell text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    /// Numbers right, everything else left.
+    #[default]
+    Auto,
+    Left,
+    Center,
+    Right,
+}
+
+/// Characters used for the table frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BorderStyle {
+    /// Box-drawing characters.
+    #[default]
+    Unicode,
+    /// `+`, `-` and `|` only, for terminals and logs without Unicode.
+    Ascii,
+}
+
+/// How a frame is laid out as a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayOptions {
+    /// Rows shown before the middle is elided.
+    pub max_rows: usize,
+    /// How many of the shown rows come from the end of the frame.
+    pub tail_rows: usize,
+    /// Columns shown before the middle ones are hidden; all when `None`.
+    pub max_columns: Option<usize>,
+    pub min_col_width: usize,
+    /// Longer cells are truncated with an ellipsis.
+    pub max_col_width: usize,
+    /// Total width the table must fit in; middle columns are hidden to fit.
+    pub width: Option<usize>,
+    pub align: Alignment,
+    /// Digits after the decimal point for floats; shortest round-trip form when `None`.
+    pub float_precision: Option<usize>,
+    pub border: BorderStyle,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        DisplayOptions {
+            max_rows: 10,
+            tail_rows: 1,
+            max_columns: None,
+            min_col_width: 8,
+            max_col_width: 20,
+            width: None,
+            align: Alignment::Auto,
+            float_precision: None,
+            border: BorderStyle::Unicode,
+        }
+    }
+}
+
+impl DisplayOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    pub fn with_tail_rows(mut self, tail_rows: usize) -> Self {
+        self.tail_rows = tail_rows;
+        self
+    }
+
+    pub fn with_max_columns(mut self, max_columns: usize) -> Self {
+        self.max_columns = Some(max_columns);
+        self
+    }
+
+    pub fn with_min_col_width(mut self, min_col_width: usize) -> Self {
+        self.min_col_width = min_col_width;
+        self
+    }
+
+    pub fn with_max_col_width(mut self, max_col_width: usize) -> Self {
+        self.max_col_width = max_col_width;
+        self
+    }
+
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Fits the table to the terminal on standard output, or to `$COLUMNS`;
+    /// leaves the width unlimited when neither is known.
+    pub fn with_terminal_width(mut self) -> Self {
+        self.width = terminal_size()
+            .map(|(Width(width), _)| width as usize)
+            .or_else(|| std::env::var("COLUMNS").ok()?.parse().ok())
+            .or(self.width);
+        self
+    }
+
+    pub fn with_align(mut self, align: Alignment) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn with_float_precision(mut self, precision: usize) -> Self {
+        self.float_precision = Some(precision);
+        self
+    }
+
+    pub fn with_border(mut self, border: BorderStyle) -> Self {
+        self.border = border;
+        self
+    }
+}
+
+/// A frame paired with the options to display it; see [`DataFrame::display`].
+pub struct FrameDisplay<'a> {
+    df: &'a DataFrame,
+    options: &'a DisplayOptions,
+}
+
+impl DataFrame {
+    /// Formats the frame as a table laid out by `options`.
+    ///
+    /// ```rust
+    /// use csv_processor::{DataFrame, DisplayOptions};
+    ///
+    /// let df = DataFrame::from_columns(Some(vec!["x".to_string()]), vec![vec![1.23456].into()])?;
+    /// let text = df.display(&DisplayOptions::new().with_float_precision(2)).to_string();
+    /// assert!(text.contains("1.23 "));
+    /// # Ok::<(), csv_processor::frame::DataFrameError>(())
+    /// ```
+    pub fn display<'a>(&'a self, options: &'a DisplayOptions) -> FrameDisplay<'a> {
+        FrameDisplay { df: self, options }
+    }
+}
+
+/// A column of the laid-out table, or the marker standing for hidden ones.
+#[derive(Clone, Copy, PartialEq)]
+enum Slot {
+    Column(usize),
+    Gap,
+}
+
+struct Glyphs {
+    top: [&'static str; 3],
+    middle: [&'static str; 3],
+    bottom: [&'static str; 3],
+    horizontal: &'static str,
+    vertical: &'static str,
+    truncated: &'static str,
+    hidden_rows: &'static str,
+    hidden_columns: &'static str,
+}
+
+const UNICODE: Glyphs = Glyphs {
+    top: ["┌", "┬", "┐"],
+    middle: ["├", "┼", "┤"],
+    bottom: ["└", "┴", "┘"],
+    horizontal: "─",
+    vertical: "│",
+    truncated: "…",
+    hidden_rows: "⋮",
+    hidden_columns: "…",
+};
+
+const ASCII: Glyphs = Glyphs {
+    top: ["+", "+", "+"],
+    middle: ["+", "+", "+"],
+    bottom: ["+", "+", "+"],
+    horizontal: "-",
+    vertical: "|",
+    truncated: "~",
+    hidden_rows: ":",
+    hidden_columns: "...",
+};
+
+impl fmt::Display for FrameDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (df, options) = (self.df, self.options);
+        let (rows, cols) = df.shape();
+
+        if cols == 0 {
+            return writeln!(f, "0 rows × 0 columns\n(empty)");
+        }
+
+        let glyphs = match options.border {
+            BorderStyle::Unicode => &UNICODE,
+            BorderStyle::Ascii => &ASCII,
+        };
+
+        // rows shown: a head, then a gap and a tail when the frame is longer
+        let (head, tail) = if rows > options.max_rows {
+            let tail = options.tail_rows.min(options.max_rows);
+            (options.max_rows - tail, tail)
+        } else {
+            (rows, 0)
+        };
+        let shown_rows: Vec<usize> = (0..head).chain(rows - tail..rows).collect();
+        let elided = rows > head + tail;
+
+        let headers = df.headers();
+        let max_width = options.max_col_width.max(1);
+        let min_width = options.min_col_width.min(max_width);
+        let widths: Vec<usize> = (0..cols)
+            .map(|col| {
+                let cells = shown_rows
+                    .iter()
+                    .map(|&row| text_width(&self.cell(col, row)));
+                cells
+                    .chain([text_width(&headers[col])])
+                    .max()
+                    .unwrap_or(0)
+                    .clamp(min_width, max_width)
+            })
+            .collect();
+
+        let slots = self.visible_slots(&widths, glyphs);
+        let slot_width = |slot: &Slot| match slot {
+            Slot::Column(col) => widths[*col],
+            Slot::Gap => text_width(glyphs.hidden_columns),
+        };
+        let align = |slot: &Slot| match (slot, options.align) {
+            (Slot::Gap, _) => Alignment::Center,
+            (Slot::Column(col), Alignment::Auto) if df.columns()[*col].dtype().is_numeric() => {
+                Alignment::Right
+            }
+            (_, Alignment::Auto) => Alignment::Left,
+            (_, align) => align,
+        };
+
+        let rule = |f: &mut fmt::Formatter<'_>, [left, mid, right]: [&str; 3]| {
+            write!(f, "{}", left)?;
+            for (i, slot) in slots.iter().enumerate() {
+                if i > 0 {
+                    write!(f, "{}", mid)?;
+                }
+                write!(f, "{}", glyphs.horizontal.repeat(slot_width(slot) + 2))?;
+            }
+            writeln!(f, "{}", right)
+        };
+        let line = |f: &mut fmt::Formatter<'_>, text: &dyn Fn(usize) -> String| {
+            write!(f, "{}", glyphs.vertical)?;
+            for slot in &slots {
+                let width = slot_width(slot);
+                let cell = match slot {
+                    Slot::Column(col) => truncate(&text(*col), width, glyphs.truncated),
+                    Slot::Gap => glyphs.hidden_columns.to_string(),
+                };
+                write!(f, " {} {}", pad(&cell, width, align(slot)), glyphs.vertical)?;
+            }
+            writeln!(f)
+        };
+
+        rule(f, glyphs.top)?;
+        line(f, &|col| sanitize(&headers[col]))?;
+        rule(f, glyphs.middle)?;
+        for (i, &row) in shown_rows.iter().enumerate() {
+            if elided && i == head {
+                write!(f, "{}", glyphs.vertical)?;
+                for slot in &slots {
+                    let width = slot_width(slot);
+                    let gap = pad(glyphs.hidden_rows, width, Alignment::Center);
+                    write!(f, " {} {}", gap, glyphs.vertical)?;
+                }
+                writeln!(f)?;
+            }
+            line(f, &|col| self.cell(col, row))?;
+        }
+        if elided && tail == 0 {
+            write!(f, "{}", glyphs.vertical)?;
+            for slot in &slots {
+                let gap = pad(glyphs.hidden_rows, slot_width(slot), Alignment::Center);
+                write!(f, " {} {}", gap, glyphs.vertical)?;
+            }
+            writeln!(f)?;
+        }
+        rule(f, glyphs.bottom)?;
+
+        write!(f, "{} rows × {} columns", rows, cols)
+    }
+}
+
+impl FrameDisplay<'_> {
+    fn cell(&self, col: usize, row: usize) -> String {
+        match self.df.columns()[col].get(row) {
+            None | Some(CellValue::Null) => "null".to_string(),
+            Some(CellValue::Float(value)) => match self.options.float_precision {
+                Some(precision) => format!("{:.*}", precision, value),
+                None => value.to_string(),
+            },
+            Some(value) => sanitize(&value.to_string()),
+        }
+    }
+
+    /// Columns to draw: all of them, or the outer ones around a gap when
+    /// `max_columns` or the total width doesn't leave room for every column.
+    fn visible_slots(&self, widths: &[usize], glyphs: &Glyphs) -> Vec<Slot> {
+        let cols = widths.len();
+        let mut shown = self.options.max_columns.unwrap_or(cols).clamp(1, cols);
+        let table_width = |shown: usize| {
+            let (left, right) = outer_columns(cols, shown);
+            let columns: usize = left.chain(right).map(|col| widths[col] + 3).sum();
+            let gap = if shown < cols {
+                text_width(glyphs.hidden_columns) + 3
+            } else {
+                0
+            };
+            1 + columns + gap
+        };
+        if let Some(limit) = self.options.width {
+            while shown > 1 && table_width(shown) > limit {
+                shown -= 1;
+            }
+        }
+
+        let (left, right) = outer_columns(cols, shown);
+        let mut slots: Vec<Slot> = left.map(Slot::Column).collect();
+        if shown < cols {
+            slots.push(Slot::Gap);
+        }
+        slots.extend(right.map(Slot::Column));
+        slots
+    }
+}
+
+/// The first and last columns when only `shown` of `cols` fit, the extra one
+/// going to the left.
+fn outer_columns(cols: usize, shown: usize) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+    let left = shown.div_ceil(2);
+    (0..left, cols - (shown - left)..cols)
+}
+
+fn text_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Replaces line breaks and other control characters so a cell stays on one line.
+fn sanitize(text: &str) -> String {
+    if text.chars().any(char::is_control) {
+        text.chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Cuts `text` to at most `width` columns on grapheme boundaries, ending
+/// with `marker` when anything was removed.
+fn truncate(text: &str, width: usize, marker: &str) -> String {
+    if text_width(text) <= width {
+        return text.to_string();
+    }
+    let budget = width.saturating_sub(text_width(marker));
+    let mut used = 0;
+    let mut cut = String::new();
+    for grapheme in text.graphemes(true) {
+        let grapheme_width = text_width(grapheme);
+        if used + grapheme_width > budget {
+            break;
+        }
+        used += grapheme_width;
+        cut.push_str(grapheme);
+    }
+    if text_width(marker) <= width {
+        cut.push_str(marker);
+    }
+    cut
+}
+
+fn pad(text: &str, width: usize, align: Alignment) -> String {
+    let space = width.saturating_sub(text_width(text));
+    let (left, right) = match align {
+        Alignment::Right => (space, 0),
+        Alignment::Center => (space / 2, space - space / 2),
+        Alignment::Left | Alignment::Auto => (0, space),
+    };
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
@@ -20,6 +20,12 @@ pub enum DataFrameError {
     CsvError(String),
     IoError(String),
     JsonError(String),
+    EncodingError(String),
+    TypeError(String),
+    ColumnNotFound(String),
+    InvalidArgument(String),
+    ArithmeticError(String),
+    SqlError(String),
 }
 
 impl fmt::Display for DataFrameError {
@@ -63,6 +69,24 @@ impl fmt::Display for DataFrameError {
             DataFrameError::JsonError(msg) => {
                 write!(f, "Json export error: {}", msg)
             }
+            DataFrameError::EncodingError(msg) => {
+                write!(f, "Encoding error: {}", msg)
+            }
+            DataFrameError::TypeError(msg) => {
+                write!(f, "Type error: {}", msg)
+            }
+            DataFrameError::ColumnNotFound(name) => {
+                write!(f, "Column '{}' not found", name)
+            }
+            DataFrameError::InvalidArgument(msg) => {
+                write!(f, "Invalid argument: {}", msg)
+            }
+            DataFrameError::ArithmeticError(msg) => {
+                write!(f, "Arithmetic error: {}", msg)
+            }
+            DataFrameError::SqlError(msg) => {
+                write!(f, "SQL error: {}", msg)
+            }
         }
     }
 }
@@ -1,13 +1,25 @@
+mod corr;
+mod dedup;
+mod display;
 mod error;
+mod nulls;
+mod resample;
+mod window;
 
 use std::fmt;
 
+use crate::io::{read_csv, write_csv, CsvReadOptions};
 use crate::series::{parse_column, ColumnArray};
-use csv::Reader;
+use crate::{CellValue, Dtype};
+pub(crate) use corr::pearson;
+pub use corr::CorrMethod;
+pub use dedup::Keep;
+pub use display::{Alignment, BorderStyle, DisplayOptions, FrameDisplay};
 pub use error::DataFrameError;
+pub use nulls::DropHow;
 use serde_json::json;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DataFrame {
     headers: Option<Vec<String>>,
     columns: Vec<Box<dyn ColumnArray>>,
@@ -112,49 +124,14 @@ impl DataFrame {
     }
 
     pub fn from_csv(filename: &str) -> Result<Self, DataFrameError> {
-        let mut reader =
-            Reader::from_path(filename).map_err(|e| DataFrameError::IoError(e.to_string()))?;
-
-        let headers: Vec<String> = reader
-            .headers()
-            .map_err(|e| DataFrameError::CsvError(e.to_string()))?
-            .iter()
-            .map(|h| h.to_string())
-            .collect();
-
-        let cols_count = headers.len();
-
-        let mut rows = Vec::new();
-        for result in reader.records() {
-            let record = result.map_err(|e| DataFrameError::CsvError(e.to_string()))?;
-            let row: Vec<String> = record.iter().map(|r| r.to_string()).collect();
-
-            if row.len() != cols_count {
-                return Err(DataFrameError::RowLengthMismatch {
-                    index: rows.len() + 1,
-                    expected: cols_count,
-                    actual: row.len(),
-                });
-            }
-            rows.push(row);
-        }
+        Self::from_csv_with_options(filename, &CsvReadOptions::default())
+    }
 
-        // Convert rows to columns
-        let columns: Vec<Box<dyn ColumnArray>> = if !rows.is_empty() {
-            (0..cols_count)
-                .map(|col_index| {
-                    let raw_column: Vec<&str> = rows
-                        .iter()
-                        .map(|row| row.get(col_index).map(|s| s.as_str()).unwrap_or(""))
-                        .collect();
-                    parse_column(raw_column)
-                })
-                .collect()
-        } else {
-            Vec::new()
-        };
-
-        Self::new(Some(headers), columns)
+    pub fn from_csv_with_options(
+        filename: &str,
+        options: &CsvReadOptions,
+    ) -> Result<Self, DataFrameError> {
+        read_csv(filename, options).map(|(df, _)| df)
     }
 
     pub fn empty() -> Self {
@@ -187,10 +164,130 @@ impl DataFrame {
         &self.columns
     }
 
+    // kept as `&Box` so existing callers don't break; `column` returns `&dyn`
+    #[allow(clippy::borrowed_box)]
     pub fn get_column(&self, column_index: usize) -> Option<&Box<dyn ColumnArray>> {
         self.columns.get(column_index)
     }
 
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.headers().iter().position(|header| header == name)
+    }
+
+    /// Indices of the `subset` columns, or of every column when it is empty.
+    pub(crate) fn subset_indices<S: AsRef<str>>(
+        &self,
+        subset: &[S],
+    ) -> Result<Vec<usize>, DataFrameError> {
+        if subset.is_empty() {
+            return Ok((0..self.columns.len()).collect());
+        }
+        subset
+            .iter()
+            .map(|name| {
+                self.column_index(name.as_ref())
+                    .ok_or_else(|| DataFrameError::ColumnNotFound(name.as_ref().to_string()))
+            })
+            .collect()
+    }
+
+    pub fn column(&self, name: &str) -> Option<&dyn ColumnArray> {
+        self.column_index(name)
+            .and_then(|index| self.get_column(index))
+            .map(|column| column.as_ref())
+    }
+
+    /// Adds `column` under `name`, replacing an existing column of that name.
+    ///
+    /// The column must have as many rows as the frame, unless the frame has no columns yet.
+    pub fn with_column(
+        mut self,
+        name: &str,
+        column: Box<dyn ColumnArray>,
+    ) -> Result<DataFrame, DataFrameError> {
+        if !self.columns.is_empty() && column.len() != self.rows_count() {
+            return Err(DataFrameError::ColumnsLengthMismatch {
+                column: name.to_string(),
+                expected: self.rows_count(),
+                actual: column.len(),
+            });
+        }
+        match self.column_index(name) {
+            Some(index) => self.columns[index] = column,
+            None => {
+                self.headers
+                    .get_or_insert_with(Vec::new)
+                    .push(name.to_string());
+                self.columns.push(column);
+            }
+        }
+        Ok(self)
+    }
+
+    /// A new frame with only the named columns, in the given order.
+    pub fn select<S: AsRef<str>>(&self, names: &[S]) -> Result<DataFrame, DataFrameError> {
+        let mut headers = Vec::with_capacity(names.len());
+        let mut columns = Vec::with_capacity(names.len());
+        for name in names {
+            let name = name.as_ref();
+            let index = self
+                .column_index(name)
+                .ok_or_else(|| DataFrameError::ColumnNotFound(name.to_string()))?;
+            headers.push(name.to_string());
+            columns.push(self.columns[index].clone());
+        }
+        DataFrame::from_columns(Some(headers), columns)
+    }
+
+    /// A new frame with the rows at `indices`, in that order.
+    pub fn take(&self, indices: &[usize]) -> DataFrame {
+        DataFrame {
+            headers: self.headers.clone(),
+            columns: self.columns.iter().map(|col| col.take(indices)).collect(),
+        }
+    }
+
+    /// A new frame with the rows where the boolean `mask` is true; null counts as false.
+    pub fn filter(&self, mask: &dyn ColumnArray) -> Result<DataFrame, DataFrameError> {
+        if mask.dtype() != Dtype::Boolean {
+            return Err(DataFrameError::TypeError(format!(
+                "filter mask must be boolean, got {:?}",
+                mask.dtype()
+            )));
+        }
+        if mask.len() != self.rows_count() {
+            return Err(DataFrameError::ColumnsLengthMismatch {
+                column: "filter mask".to_string(),
+                expected: self.rows_count(),
+                actual: mask.len(),
+            });
+        }
+        let indices: Vec<usize> = (0..mask.len())
+            .filter(|&row| mask.get(row) == Some(CellValue::Boolean(true)))
+            .collect();
+        Ok(self.take(&indices))
+    }
+
+    /// The first `n` rows.
+    pub fn head(&self, n: usize) -> DataFrame {
+        let indices: Vec<usize> = (0..n.min(self.rows_count())).collect();
+        self.take(&indices)
+    }
+
+    /// The last `n` rows.
+    pub fn tail(&self, n: usize) -> DataFrame {
+        let rows = self.rows_count();
+        let indices: Vec<usize> = (rows - n.min(rows)..rows).collect();
+        self.take(&indices)
+    }
+
+    /// The frame as comma-separated text with a header row.
+    pub fn to_csv(&self) -> Result<String, DataFrameError> {
+        let mut buffer = Vec::new();
+        write_csv(self, &mut buffer, b',')?;
+        String::from_utf8(buffer).map_err(|e| DataFrameError::CsvError(e.to_string()))
+    }
+
     pub fn to_json(&self) -> Result<String, DataFrameError> {
         let headers = self.headers.as_ref().unwrap().clone();
         let mut columns: Vec<Vec<serde_json::Value>> = Vec::new();
@@ -219,130 +316,6 @@ impl DataFrame {
 
 impl fmt::Display for DataFrame {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (rows, cols) = self.shape();
-
-        if cols == 0 {
-            return writeln!(f, "0 rows × 0 columns\n(empty)");
-        }
-
-        let headers = self.headers();
-        let max_display_rows = 10;
-        let show_truncated = rows > max_display_rows;
-        let display_rows = if show_truncated {
-            max_display_rows - 1
-        } else {
-            rows
-        };
-
-        let mut col_widths = Vec::new();
-
-        for (i, header) in headers.iter().enumerate() {
-            let mut max_width = header.len();
-
-            let column = &self.columns[i];
-            for row_idx in 0..display_rows {
-                if let Some(cell_value) = column.get(row_idx) {
-                    let cell_str = format!("{}", cell_value);
-                    max_width = max_width.max(cell_str.len());
-                }
-            }
-
-            if show_truncated && display_rows < rows - 1 {
-                if let Some(cell_value) = column.get(rows - 1) {
-                    let cell_str = format!("{}", cell_value);
-                    max_width = max_width.max(cell_str.len());
-                }
-            }
-
-            // max_width = max_width.max(8).min(20);
-            max_width = max_width.clamp(8, 20);
-            col_widths.push(max_width);
-        }
-
-        write!(f, "┌")?;
-        for (i, &width) in col_widths.iter().enumerate() {
-            write!(f, "{}", "─".repeat(width + 2))?;
-            if i < col_widths.len() - 1 {
-                write!(f, "┬")?;
-            }
-        }
-        writeln!(f, "┐")?;
-
-        write!(f, "│")?;
-        for (header, &width) in headers.iter().zip(&col_widths) {
-            let truncated_header = if header.len() > width {
-                format!("{}…", &header[..width - 1])
-            } else {
-                header.clone()
-            };
-            write!(f, " {:^width$} │", truncated_header, width = width)?;
-        }
-        writeln!(f)?;
-
-        write!(f, "├")?;
-        for (i, &width) in col_widths.iter().enumerate() {
-            write!(f, "{}", "─".repeat(width + 2))?;
-            if i < col_widths.len() - 1 {
-                write!(f, "┼")?;
-            }
-        }
-        writeln!(f, "┤")?;
-
-        for row_idx in 0..display_rows {
-            write!(f, "│")?;
-            for (col_idx, &width) in col_widths.iter().enumerate() {
-                let cell_str = if let Some(cell_value) = self.columns[col_idx].get(row_idx) {
-                    let full_str = format!("{}", cell_value);
-                    if full_str.len() > width {
-                        format!("{}…", &full_str[..width - 1])
-                    } else {
-                        full_str
-                    }
-                } else {
-                    "null".to_string()
-                };
-
-                write!(f, " {:^width$} │", cell_str, width = width)?;
-            }
-            writeln!(f)?;
-        }
-
-        if show_truncated {
-            write!(f, "│")?;
-            for &width in &col_widths {
-                write!(f, " {:^width$} │", "⋮", width = width)?;
-            }
-            writeln!(f)?;
-
-            write!(f, "│")?;
-            for (col_idx, &width) in col_widths.iter().enumerate() {
-                let cell_str = if let Some(cell_value) = self.columns[col_idx].get(rows - 1) {
-                    let full_str = format!("{}", cell_value);
-                    if full_str.len() > width {
-                        format!("{}…", &full_str[..width - 1])
-                    } else {
-                        full_str
-                    }
-                } else {
-                    "null".to_string()
-                };
-
-                write!(f, " {:^width$} │", cell_str, width = width)?;
-            }
-            writeln!(f)?;
-        }
-
-        write!(f, "└")?;
-        for (i, &width) in col_widths.iter().enumerate() {
-            write!(f, "{}", "─".repeat(width + 2))?;
-            if i < col_widths.len() - 1 {
-                write!(f, "┴")?;
-            }
-        }
-        writeln!(f, "┘")?;
-
-        write!(f, "{} rows × {} columns", rows, cols)?;
-
-        Ok(())
+        write!(f, "{}", self.display(&DisplayOptions::default()))
     }
 }
@@ -0,0 +1,118 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::{DataFrame, DataFrameError};
+use crate::series::{ColumnArray, FillStrategy};
+
+/// Which rows [`DataFrame::drop_nulls`] drops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DropHow {
+    /// Rows with a null in any of the checked columns.
+    #[default]
+    Any,
+    /// Rows whose checked columns are all null.
+    All,
+}
+
+impl fmt::Display for DropHow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DropHow::Any => "any",
+            DropHow::All => "all",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for DropHow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "any" => Ok(DropHow::Any),
+            "all" => Ok(DropHow::All),
+            _ => Err(format!("unknown how '{}', expected any or all", s)),
+        }
+    }
+}
+
+impl DataFrame {
+    /// The frame without rows that have nulls in the `subset` columns (all
+    /// columns when empty).
+    ///
+    /// With `thresh`, `how` is ignored and a row is kept when it has at least
+    /// that many non-null values among the checked columns.
+    pub fn drop_nulls<S: AsRef<str>>(
+        &self,
+        subset: &[S],
+        how: DropHow,
+        thresh: Option<usize>,
+    ) -> Result<DataFrame, DataFrameError> {
+        let checked = self.subset_indices(subset)?;
+        let indices: Vec<usize> = (0..self.rows_count())
+            .filter(|&row| {
+                let present = checked
+                    .iter()
+                    .filter(|&&col| self.columns[col].get(row).is_some())
+                    .count();
+                match (thresh, how) {
+                    (Some(thresh), _) => present >= thresh,
+                    (None, DropHow::Any) => present == checked.len(),
+                    (None, DropHow::All) => present > 0 || checked.is_empty(),
+                }
+            })
+            .collect();
+        Ok(self.take(&indices))
+    }
+
+    /// The frame with the nulls of the `columns` filled by `strategy`.
+    ///
+    /// Named columns must support the strategy; when `columns` is empty every
+    /// column that does is filled and the others are left as they are.
+    pub fn fill_null<S: AsRef<str>>(
+        &self,
+        columns: &[S],
+        strategy: &FillStrategy,
+    ) -> Result<DataFrame, DataFrameError> {
+        self.map_columns(
+            columns,
+            |column| strategy.supports(&column.dtype()),
+            |column| column.fill_null(strategy),
+        )
+    }
+
+    /// The frame with interior nulls of the numeric `columns` (all numeric
+    /// columns when empty) linearly interpolated.
+    pub fn interpolate<S: AsRef<str>>(&self, columns: &[S]) -> Result<DataFrame, DataFrameError> {
+        self.map_columns(
+            columns,
+            |column| column.dtype().is_numeric(),
+            |column| column.interpolate(),
+        )
+    }
+
+    /// Replaces the named columns by `map` of them, or, when `columns` is
+    /// empty, every column `applies` to.
+    fn map_columns<S: AsRef<str>>(
+        &self,
+        columns: &[S],
+        applies: impl Fn(&dyn ColumnArray) -> bool,
+        map: impl Fn(&dyn ColumnArray) -> Result<Box<dyn ColumnArray>, DataFrameError>,
+    ) -> Result<DataFrame, DataFrameError> {
+        let mut result = self.clone();
+        for index in self.subset_indices(columns)? {
+            let column = self.columns[index].as_ref();
+            if columns.is_empty() && !applies(column) {
+                continue;
+            }
+            result.columns[index] =
+                map(column).map_err(|e| match (e, self.headers().get(index)) {
+                    (DataFrameError::TypeError(message), Some(name)) => {
+                        DataFrameError::TypeError(format!("{}: {}", name, message))
+                    }
+                    (e, _) => e,
+                })?;
+        }
+        Ok(result)
+    }
+}
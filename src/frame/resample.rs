@@ -0,0 +1,24 @@
+use super::{DataFrame, DataFrameError};
+use crate::lazy::{col, LazyGroupBy};
+use crate::series::Period;
+
+impl DataFrame {
+    /// Groups the rows into `every`-long time buckets of the date or datetime
+    /// column `on`; finish it with [`LazyGroupBy::agg`].
+    ///
+    /// The key column keeps the name `on` and holds the start of each bucket.
+    /// Buckets come out in time order, followed by the rows with a null date;
+    /// empty buckets are not filled in.
+    pub fn resample(&self, on: &str, every: Period) -> Result<LazyGroupBy, DataFrameError> {
+        let column = self
+            .column(on)
+            .ok_or_else(|| DataFrameError::ColumnNotFound(on.to_string()))?;
+        let buckets = column.dt()?.truncate(every);
+        Ok(self
+            .clone()
+            .with_column(on, buckets)?
+            .lazy()
+            .sort(on, false)
+            .group_by([col(on)]))
+    }
+}
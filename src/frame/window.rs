@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use super::{DataFrame, DataFrameError};
+use crate::scalar::ValueKey;
+use crate::series::ColumnArray;
+use crate::CellValue;
+
+impl DataFrame {
+    /// Runs the window computation `f` on `column` separately within each
+    /// partition of rows sharing their `partition_by` values (the whole
+    /// column when empty), e.g. a running total per store.
+    ///
+    /// Rows keep their order within a partition and each result lands back
+    /// in its row. Null keys form a partition of their own. `f` must return
+    /// one value per row it is given.
+    pub fn over<S: AsRef<str>>(
+        &self,
+        column: &str,
+        partition_by: &[S],
+        f: impl Fn(&dyn ColumnArray) -> Result<Box<dyn ColumnArray>, DataFrameError>,
+    ) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        let values = self
+            .column(column)
+            .ok_or_else(|| DataFrameError::ColumnNotFound(column.to_string()))?;
+        if partition_by.is_empty() {
+            return f(values);
+        }
+        let keys = self.subset_indices(partition_by)?;
+
+        let mut positions: HashMap<Vec<ValueKey>, usize> = HashMap::new();
+        let mut partitions: Vec<Vec<usize>> = Vec::new();
+        for row in 0..self.rows_count() {
+            let key: Vec<ValueKey> = keys
+                .iter()
+                .map(|&index| ValueKey::from(self.columns[index].get(row)))
+                .collect();
+            let position = *positions.entry(key).or_insert_with(|| {
+                partitions.push(Vec::new());
+                partitions.len() - 1
+            });
+            partitions[position].push(row);
+        }
+
+        let mut cells: Vec<Option<CellValue>> = vec![None; self.rows_count()];
+        let mut dtypes = Vec::new();
+        let mut first: Option<Box<dyn ColumnArray>> = None;
+        for rows in &partitions {
+            let result = f(values.take(rows).as_ref())?;
+            if result.len() != rows.len() {
+                return Err(DataFrameError::ColumnsLengthMismatch {
+                    column: format!("window over '{}'", column),
+                    expected: rows.len(),
+                    actual: result.len(),
+                });
+            }
+            for (offset, &row) in rows.iter().enumerate() {
+                cells[row] = result.get(offset);
+            }
+            dtypes.push(result.dtype());
+            first.get_or_insert(result);
+        }
+
+        // cell values lose the column type, e.g. integers in an all-null
+        // result or categories, so a type all partitions agree on is restored
+        let combined: Box<dyn ColumnArray> = cells.into();
+        match first {
+            Some(first)
+                if dtypes.iter().all(|d| *d == first.dtype())
+                    && combined.dtype() != first.dtype() =>
+            {
+                if combined.non_null_count() == 0 {
+                    Ok(first.take(&vec![usize::MAX; combined.len()]))
+                } else {
+                    combined.cast(first.dtype(), false)
+                }
+            }
+            _ => Ok(combined),
+        }
+    }
+}
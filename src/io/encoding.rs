@@ -0,0 +1,405 @@
+use std::fmt;
+use std::str::FromStr;
+
+use encoding_rs::DecoderResult;
+
+use crate::frame::DataFrameError;
+
+/// Number of leading bytes inspected when guessing the encoding of a file without a BOM.
+const DETECTION_SAMPLE: usize = 8 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+    Windows1250,
+    Windows1251,
+    Windows1252,
+    Windows1253,
+    Windows1254,
+    Windows1255,
+    Windows1256,
+    Windows1257,
+    Windows1258,
+}
+
+impl Encoding {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "utf-8",
+            Encoding::Utf16Le => "utf-16le",
+            Encoding::Utf16Be => "utf-16be",
+            Encoding::Latin1 => "latin-1",
+            Encoding::Windows1250 => "windows-1250",
+            Encoding::Windows1251 => "windows-1251",
+            Encoding::Windows1252 => "windows-1252",
+            Encoding::Windows1253 => "windows-1253",
+            Encoding::Windows1254 => "windows-1254",
+            Encoding::Windows1255 => "windows-1255",
+            Encoding::Windows1256 => "windows-1256",
+            Encoding::Windows1257 => "windows-1257",
+            Encoding::Windows1258 => "windows-1258",
+        }
+    }
+
+    pub(crate) fn bom(&self) -> &'static [u8] {
+        match self {
+            Encoding::Utf8 => &[0xEF, 0xBB, 0xBF],
+            Encoding::Utf16Le => &[0xFF, 0xFE],
+            Encoding::Utf16Be => &[0xFE, 0xFF],
+            _ => &[],
+        }
+    }
+
+    /// Latin-1 is decoded by hand: `encoding_rs` follows WHATWG and treats
+    /// the `latin1` label as windows-1252.
+    fn codec(&self) -> Option<&'static encoding_rs::Encoding> {
+        match self {
+            Encoding::Utf8 => Some(encoding_rs::UTF_8),
+            Encoding::Utf16Le => Some(encoding_rs::UTF_16LE),
+            Encoding::Utf16Be => Some(encoding_rs::UTF_16BE),
+            Encoding::Latin1 => None,
+            Encoding::Windows1250 => Some(encoding_rs::WINDOWS_1250),
+            Encoding::Windows1251 => Some(encoding_rs::WINDOWS_1251),
+            Encoding::Windows1252 => Some(encoding_rs::WINDOWS_1252),
+            Encoding::Windows1253 => Some(encoding_rs::WINDOWS_1253),
+            Encoding::Windows1254 => Some(encoding_rs::WINDOWS_1254),
+            Encoding::Windows1255 => Some(encoding_rs::WINDOWS_1255),
+            Encoding::Windows1256 => Some(encoding_rs::WINDOWS_1256),
+            Encoding::Windows1257 => Some(encoding_rs::WINDOWS_1257),
+            Encoding::Windows1258 => Some(encoding_rs::WINDOWS_1258),
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = String;
+
+    fn from_str(label: &str) -> Result<Self, Self::Err> {
+        let normalized: String = label
+            .to_lowercase()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect();
+
+        match normalized.as_str() {
+            "utf8" => Ok(Encoding::Utf8),
+            "utf16" | "utf16le" => Ok(Encoding::Utf16Le),
+            "utf16be" => Ok(Encoding::Utf16Be),
+            "latin1" | "iso88591" => Ok(Encoding::Latin1),
+            "windows1250" | "cp1250" => Ok(Encoding::Windows1250),
+            "windows1251" | "cp1251" => Ok(Encoding::Windows1251),
+            "windows1252" | "cp1252" => Ok(Encoding::Windows1252),
+            "windows1253" | "cp1253" => Ok(Encoding::Windows1253),
+            "windows1254" | "cp1254" => Ok(Encoding::Windows1254),
+            "windows1255" | "cp1255" => Ok(Encoding::Windows1255),
+            "windows1256" | "cp1256" => Ok(Encoding::Windows1256),
+            "windows1257" | "cp1257" => Ok(Encoding::Windows1257),
+            "windows1258" | "cp1258" => Ok(Encoding::Windows1258),
+            _ => Err(format!("Unsupported encoding '{}'", label)),
+        }
+    }
+}
+
+/// Text produced by [`decode`], together with what was learned while decoding it.
+#[derive(Debug)]
+pub struct DecodedText {
+    pub text: String,
+    pub encoding: Encoding,
+    pub had_bom: bool,
+    pub invalid_bytes: usize,
+}
+
+/// Guesses the encoding of `bytes`.
+///
+/// A byte order mark always wins. Otherwise valid UTF-8 is assumed to be UTF-8,
+/// text with NUL bytes concentrated on one side of each byte pair is taken as
+/// UTF-16, and anything else falls back to windows-1252, which is what Excel
+/// writes on western-locale Windows machines.
+pub fn detect_encoding(bytes: &[u8]) -> Encoding {
+    for encoding in [Encoding::Utf8, Encoding::Utf16Le, Encoding::Utf16Be] {
+        if bytes.starts_with(encoding.bom()) {
+            return encoding;
+        }
+    }
+
+    let sample = &bytes[..bytes.len().min(DETECTION_SAMPLE)];
+
+    let pairs = sample.len() / 2;
+    if pairs > 0 {
+        let even_zeros = sample.iter().step_by(2).filter(|&&b| b == 0).count();
+        let odd_zeros = sample
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .filter(|&&b| b == 0)
+            .count();
+        if odd_zeros * 10 > pairs * 4 && even_zeros * 10 < pairs {
+            return Encoding::Utf16Le;
+        }
+        if even_zeros * 10 > pairs * 4 && odd_zeros * 10 < pairs {
+            return Encoding::Utf16Be;
+        }
+    }
+
+    if std::str::from_utf8(bytes).is_ok() {
+        Encoding::Utf8
+    } else {
+        Encoding::Windows1252
+    }
+}
+
+/// Decodes `bytes` into UTF-8, stripping a byte order mark that matches the encoding.
+///
+/// When `encoding` is `None` it is detected with [`detect_encoding`]. In lossy mode
+/// malformed sequences are replaced with U+FFFD and counted in `invalid_bytes`;
+/// otherwise the first malformed sequence is reported as an error.
+pub fn decode(
+    bytes: &[u8],
+    encoding: Option<Encoding>,
+    lossy: bool,
+) -> Result<DecodedText, DataFrameError> {
+    let encoding = encoding.unwrap_or_else(|| detect_encoding(bytes));
+    let had_bom = !encoding.bom().is_empty() && bytes.starts_with(encoding.bom());
+    let body = if had_bom {
+        &bytes[encoding.bom().len()..]
+    } else {
+        bytes
+    };
+
+    let (text, invalid_bytes) = match encoding.codec() {
+        None => (body.iter().map(|&b| b as char).collect(), 0),
+        Some(codec) if codec == encoding_rs::UTF_8 && !lossy => {
+            let text = String::from_utf8(body.to_vec())
+                .map_err(|e| invalid_sequence(encoding, had_bom, e.utf8_error().valid_up_to()))?;
+            (text, 0)
+        }
+        Some(codec) => decode_with(codec, encoding, body, had_bom, lossy)?,
+    };
+
+    Ok(DecodedText {
+        text,
+        encoding,
+        had_bom,
+        invalid_bytes,
+    })
+}
+
+/// Like [`decode`], but reuses the buffer instead of copying it when the input is
+/// already UTF-8 and no replacement is requested.
+pub(crate) fn decode_owned(
+    mut bytes: Vec<u8>,
+    encoding: Option<Encoding>,
+    lossy: bool,
+) -> Result<DecodedText, DataFrameError> {
+    let encoding = encoding.unwrap_or_else(|| detect_encoding(&bytes));
+    if encoding != Encoding::Utf8 || lossy {
+        return decode(&bytes, Some(encoding), lossy);
+    }
+
+    let had_bom = bytes.starts_with(encoding.bom());
+    if had_bom {
+        bytes.drain(..encoding.bom().len());
+    }
+    let text = String::from_utf8(bytes)
+        .map_err(|e| invalid_sequence(encoding, had_bom, e.utf8_error().valid_up_to()))?;
+
+    Ok(DecodedText {
+        text,
+        encoding,
+        had_bom,
+        invalid_bytes: 0,
+    })
+}
+
+fn decode_with(
+    codec: &'static encoding_rs::Encoding,
+    encoding: Encoding,
+    body: &[u8],
+    had_bom: bool,
+    lossy: bool,
+) -> Result<(String, usize), DataFrameError> {
+    let mut decoder = codec.new_decoder_without_bom_handling();
+    let mut text = String::with_capacity(
+        decoder
+            .max_utf8_buffer_length_without_replacement(body.len())
+            .unwrap_or(body.len()),
+    );
+    let mut invalid_bytes = 0;
+    let mut consumed = 0;
+
+    loop {
+        let (result, read) =
+            decoder.decode_to_string_without_replacement(&body[consumed..], &mut text, true);
+        consumed += read;
+        match result {
+            DecoderResult::InputEmpty => break,
+            DecoderResult::OutputFull => text.reserve(body.len() - consumed + 16),
+            DecoderResult::Malformed(bad, pending) => {
+                if !lossy {
+                    let offset = consumed - bad as usize - pending as usize;
+                    return Err(invalid_sequence(encoding, had_bom, offset));
+                }
+                invalid_bytes += bad as usize;
+                text.push(char::REPLACEMENT_CHARACTER);
+            }
+        }
+    }
+
+    Ok((text, invalid_bytes))
+}
+
+fn invalid_sequence(encoding: Encoding, had_bom: bool, offset: usize) -> DataFrameError {
+    let offset = if had_bom {
+        offset + encoding.bom().len()
+    } else {
+        offset
+    };
+    DataFrameError::EncodingError(format!(
+        "invalid {} sequence at byte {} (use lossy decoding to replace it)",
+        encoding, offset
+    ))
+}
+
+/// Like [`detect_encoding`] for the first bytes of a longer file, where a
+/// multi-byte character may be cut off at the end of the sample.
+pub(crate) fn detect_prefix_encoding(prefix: &[u8]) -> Encoding {
+    match std::str::from_utf8(prefix) {
+        Err(e) if e.error_len().is_none() => detect_encoding(&prefix[..e.valid_up_to()]),
+        _ => detect_encoding(prefix),
+    }
+}
+
+/// Size of the blocks [`DecodingReader`] reads from its source.
+const DECODE_BLOCK: usize = 64 * 1024;
+
+/// Streams a source in any supported encoding as UTF-8, block by block, so
+/// files larger than memory can be parsed.
+///
+/// A byte order mark matching `encoding` must already be stripped. In strict
+/// mode the first malformed sequence ends the stream with an `InvalidData`
+/// error and is kept in `error` with its byte offset.
+pub(crate) struct DecodingReader<R> {
+    inner: R,
+    encoding: Encoding,
+    had_bom: bool,
+    decoder: Option<encoding_rs::Decoder>,
+    lossy: bool,
+    input: Vec<u8>,
+    input_pos: usize,
+    output: Vec<u8>,
+    output_pos: usize,
+    consumed: usize,
+    eof: bool,
+    finished: bool,
+    pub(crate) invalid_bytes: usize,
+    pub(crate) error: Option<DataFrameError>,
+}
+
+impl<R: std::io::Read> DecodingReader<R> {
+    pub(crate) fn new(inner: R, encoding: Encoding, had_bom: bool, lossy: bool) -> Self {
+        DecodingReader {
+            inner,
+            encoding,
+            had_bom,
+            decoder: encoding
+                .codec()
+                .map(|codec| codec.new_decoder_without_bom_handling()),
+            lossy,
+            input: Vec::new(),
+            input_pos: 0,
+            output: Vec::new(),
+            output_pos: 0,
+            consumed: 0,
+            eof: false,
+            finished: false,
+            invalid_bytes: 0,
+            error: None,
+        }
+    }
+
+    /// Decodes until some output is ready or the source is exhausted.
+    fn fill(&mut self) -> std::io::Result<()> {
+        self.output.clear();
+        self.output_pos = 0;
+        while self.output.is_empty() && !self.finished {
+            if self.input_pos == self.input.len() && !self.eof {
+                self.input.resize(DECODE_BLOCK, 0);
+                let read = self.inner.read(&mut self.input)?;
+                self.input.truncate(read);
+                self.input_pos = 0;
+                self.eof = read == 0;
+            }
+            let pending = &self.input[self.input_pos..];
+            let Some(decoder) = self.decoder.as_mut() else {
+                // Latin-1: every byte is the code point of the same value
+                self.output.extend(
+                    pending
+                        .iter()
+                        .map(|&b| b as char)
+                        .collect::<String>()
+                        .bytes(),
+                );
+                self.input_pos = self.input.len();
+                self.finished = self.eof;
+                continue;
+            };
+
+            let start = self.output.len();
+            let capacity = decoder
+                .max_utf8_buffer_length_without_replacement(pending.len())
+                .unwrap_or(DECODE_BLOCK * 3);
+            self.output.resize(start + capacity.max(16), 0);
+            let (result, read, written) = decoder.decode_to_utf8_without_replacement(
+                pending,
+                &mut self.output[start..],
+                self.eof,
+            );
+            self.output.truncate(start + written);
+            self.input_pos += read;
+            self.consumed += read;
+            match result {
+                DecoderResult::InputEmpty => self.finished = self.eof,
+                DecoderResult::OutputFull => {}
+                DecoderResult::Malformed(bad, pending) => {
+                    if !self.lossy {
+                        let offset = self.consumed - bad as usize - pending as usize;
+                        let error = invalid_sequence(self.encoding, self.had_bom, offset);
+                        let message = error.to_string();
+                        self.error = Some(error);
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            message,
+                        ));
+                    }
+                    self.invalid_bytes += bad as usize;
+                    let mut buffer = [0; 4];
+                    let replacement = char::REPLACEMENT_CHARACTER.encode_utf8(&mut buffer);
+                    self.output.extend_from_slice(replacement.as_bytes());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: std::io::Read> std::io::Read for DecodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.output_pos == self.output.len() {
+            self.fill()?;
+        }
+        let available = &self.output[self.output_pos..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.output_pos += n;
+        Ok(n)
+    }
+}
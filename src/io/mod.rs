@@ -0,0 +1,333 @@
+//! CSV input and output: decoding raw bytes and turning records into a typed
+//! [`DataFrame`], and writing frames back out.
+
+mod encoding;
+mod parallel;
+mod sniff;
+mod stream;
+
+use std::fmt;
+
+use csv::{Reader, ReaderBuilder, StringRecord, Terminator, WriterBuilder};
+
+use crate::frame::{DataFrame, DataFrameError};
+use crate::series::{parse_column, ColumnArray};
+use parallel::{ChunkError, RawChunk};
+
+pub use encoding::{decode, detect_encoding, DecodedText, Encoding};
+pub use sniff::{sniff, sniff_file, Dialect, LineTerminator, DEFAULT_SNIFF_SIZE};
+pub use stream::{read_csv_rows, RowSelection};
+
+/// Options controlling how a CSV file is decoded and parsed.
+#[derive(Debug, Clone, Default)]
+pub struct CsvReadOptions {
+    /// Source encoding; detected from the file contents when `None`.
+    pub encoding: Option<Encoding>,
+    /// Replace malformed byte sequences with U+FFFD instead of failing.
+    pub lossy: bool,
+    /// Field separator; when `None` the whole dialect is sniffed from the file.
+    pub delimiter: Option<u8>,
+    pub quote: Option<u8>,
+    pub has_header: Option<bool>,
+    /// Worker threads for parsing and type inference; all cores when `None`.
+    pub threads: Option<usize>,
+    /// Only these columns are kept and type-inferred, in this order; all when `None`.
+    pub columns: Option<Vec<String>>,
+}
+
+impl CsvReadOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = Some(encoding);
+        self
+    }
+
+    pub fn with_lossy(mut self, lossy: bool) -> Self {
+        self.lossy = lossy;
+        self
+    }
+
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.quote = Some(quote);
+        self
+    }
+
+    pub fn with_has_header(mut self, has_header: bool) -> Self {
+        self.has_header = Some(has_header);
+        self
+    }
+
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    pub fn with_columns(mut self, columns: Vec<String>) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Fixes every dialect setting so that nothing is sniffed on read.
+    pub fn with_dialect(self, dialect: &Dialect) -> Self {
+        self.with_delimiter(dialect.delimiter)
+            .with_quote(dialect.quote)
+            .with_has_header(dialect.has_header)
+    }
+
+    /// Fills in the settings left open with the sniffed dialect of `text`.
+    ///
+    /// Sniffing only happens when no delimiter was given, otherwise the
+    /// remaining settings fall back to the standard CSV conventions.
+    fn resolve_dialect(&self, text: &str) -> Dialect {
+        let base = match self.delimiter {
+            Some(_) => Dialect {
+                terminator: sniff::detect_terminator(text),
+                ..Dialect::default()
+            },
+            None => sniff(sample_of(text)),
+        };
+
+        Dialect {
+            delimiter: self.delimiter.unwrap_or(base.delimiter),
+            quote: self.quote.unwrap_or(base.quote),
+            has_header: self.has_header.unwrap_or(base.has_header),
+            terminator: base.terminator,
+        }
+    }
+}
+
+fn sample_of(text: &str) -> &str {
+    let mut end = text.len().min(DEFAULT_SNIFF_SIZE);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Facts about a completed read that are not part of the data itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadReport {
+    pub encoding: Encoding,
+    pub had_bom: bool,
+    pub invalid_bytes: usize,
+    pub dialect: Dialect,
+}
+
+impl fmt::Display for ReadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Encoding: {}", self.encoding)?;
+        if self.had_bom {
+            write!(f, " (BOM)")?;
+        }
+        if self.invalid_bytes > 0 {
+            write!(f, ", {} invalid bytes replaced", self.invalid_bytes)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn read_csv(
+    filename: &str,
+    options: &CsvReadOptions,
+) -> Result<(DataFrame, ReadReport), DataFrameError> {
+    let bytes = std::fs::read(filename).map_err(|e| DataFrameError::IoError(e.to_string()))?;
+    let decoded = encoding::decode_owned(bytes, options.encoding, options.lossy)?;
+    read_decoded(decoded, options)
+}
+
+pub fn read_csv_from_bytes(
+    bytes: &[u8],
+    options: &CsvReadOptions,
+) -> Result<(DataFrame, ReadReport), DataFrameError> {
+    let decoded = decode(bytes, options.encoding, options.lossy)?;
+    read_decoded(decoded, options)
+}
+
+fn read_decoded(
+    decoded: DecodedText,
+    options: &CsvReadOptions,
+) -> Result<(DataFrame, ReadReport), DataFrameError> {
+    let dialect = options.resolve_dialect(&decoded.text);
+    let report = ReadReport {
+        encoding: decoded.encoding,
+        had_bom: decoded.had_bom,
+        invalid_bytes: decoded.invalid_bytes,
+        dialect,
+    };
+
+    let threads = parallel::resolve_threads(options.threads);
+    let df = parse_records(decoded.text, &dialect, threads, options.columns.as_deref())?;
+    Ok((df, report))
+}
+
+/// Column names of `filename` without reading past the first record.
+///
+/// With `options.columns` set, those are the names the read would produce.
+pub fn read_headers(
+    filename: &str,
+    options: &CsvReadOptions,
+) -> Result<Vec<String>, DataFrameError> {
+    if let Some(columns) = &options.columns {
+        return Ok(columns.clone());
+    }
+    let decoded = read_sample(filename, options)?;
+    let dialect = options.resolve_dialect(&decoded.text);
+    let mut first = StringRecord::new();
+    csv_reader(&dialect, decoded.text.as_bytes())
+        .read_record(&mut first)
+        .map_err(|e| DataFrameError::CsvError(e.to_string()))?;
+    Ok(header_names(&first, dialect.has_header))
+}
+
+/// The dialect a read of `filename` with `options` would use: the settings
+/// given in `options`, with the rest sniffed from the start of the file.
+pub fn read_dialect(filename: &str, options: &CsvReadOptions) -> Result<Dialect, DataFrameError> {
+    let decoded = read_sample(filename, options)?;
+    Ok(options.resolve_dialect(&decoded.text))
+}
+
+/// The first [`DEFAULT_SNIFF_SIZE`] bytes of `filename`, decoded.
+fn read_sample(filename: &str, options: &CsvReadOptions) -> Result<DecodedText, DataFrameError> {
+    use std::io::Read;
+
+    let file = std::fs::File::open(filename).map_err(|e| DataFrameError::IoError(e.to_string()))?;
+    let mut bytes = Vec::with_capacity(DEFAULT_SNIFF_SIZE);
+    file.take(DEFAULT_SNIFF_SIZE as u64)
+        .read_to_end(&mut bytes)
+        .map_err(|e| DataFrameError::IoError(e.to_string()))?;
+
+    // The sample may cut a multi-byte character in half, so decode leniently.
+    decode(&bytes, options.encoding, true)
+}
+
+fn header_names(first: &StringRecord, has_header: bool) -> Vec<String> {
+    if has_header {
+        first.iter().map(|h| h.to_string()).collect()
+    } else {
+        (1..=first.len()).map(|i| format!("column_{}", i)).collect()
+    }
+}
+
+pub(crate) fn csv_reader<R: std::io::Read>(dialect: &Dialect, source: R) -> Reader<R> {
+    let terminator = match dialect.terminator {
+        LineTerminator::Lf => Terminator::Any(b'\n'),
+        LineTerminator::CrLf | LineTerminator::Cr => Terminator::CRLF,
+    };
+    ReaderBuilder::new()
+        .has_headers(false)
+        .delimiter(dialect.delimiter)
+        .quote(dialect.quote)
+        .terminator(terminator)
+        .flexible(true)
+        .from_reader(source)
+}
+
+/// Takes ownership of the decoded text so it can be freed before type inference.
+///
+/// With `columns`, the other fields of each record are skipped without being copied.
+fn parse_records(
+    text: String,
+    dialect: &Dialect,
+    threads: usize,
+    columns: Option<&[String]>,
+) -> Result<DataFrame, DataFrameError> {
+    let (headers, raw) = {
+        let mut reader = csv_reader(dialect, text.as_bytes());
+        let mut first = StringRecord::new();
+        let has_records = reader
+            .read_record(&mut first)
+            .map_err(|e| DataFrameError::CsvError(e.to_string()))?;
+        let body = &text[reader.position().byte() as usize..];
+
+        let cols_count = first.len();
+
+        let all_headers = header_names(&first, dialect.has_header);
+        let selected: Vec<usize> = match columns {
+            None => (0..cols_count).collect(),
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    all_headers
+                        .iter()
+                        .position(|header| header == name)
+                        .ok_or_else(|| DataFrameError::ColumnNotFound(name.clone()))
+                })
+                .collect::<Result<_, _>>()?,
+        };
+        let headers: Vec<String> = selected.iter().map(|&i| all_headers[i].clone()).collect();
+
+        let mut raw = RawChunk::new(selected.len());
+        if !dialect.has_header && has_records {
+            for (column, &index) in raw.columns.iter_mut().zip(&selected) {
+                column.push(&first[index]);
+            }
+            raw.rows = 1;
+        }
+
+        let chunks = parallel::split_chunks(body, dialect, threads);
+        let parsed = parallel::map_ordered(&chunks, threads, |chunk| {
+            parallel::parse_chunk(chunk, dialect, cols_count, &selected)
+        });
+
+        for chunk in parsed {
+            match chunk {
+                Ok(chunk) => raw.append(chunk),
+                Err(ChunkError::Csv(msg)) => return Err(DataFrameError::CsvError(msg)),
+                Err(ChunkError::RowLength { row, actual }) => {
+                    return Err(DataFrameError::RowLengthMismatch {
+                        index: raw.rows + row + 1,
+                        expected: cols_count,
+                        actual,
+                    })
+                }
+            }
+        }
+
+        (headers, raw)
+    };
+    drop(text);
+
+    // Convert raw text columns to typed columns
+    let columns: Vec<Box<dyn ColumnArray>> = if raw.rows > 0 {
+        parallel::map_ordered(&raw.columns, threads, |column| {
+            parse_column(column.values())
+        })
+    } else {
+        Vec::new()
+    };
+
+    DataFrame::new(Some(headers), columns)
+}
+
+/// Writes `df` as CSV with a header row; nulls become empty fields.
+pub fn write_csv<W: std::io::Write>(
+    df: &DataFrame,
+    writer: W,
+    delimiter: u8,
+) -> Result<(), DataFrameError> {
+    let mut writer = WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(writer);
+    let csv_error = |e: csv::Error| DataFrameError::CsvError(e.to_string());
+
+    writer.write_record(df.headers()).map_err(csv_error)?;
+    let (rows, _) = df.shape();
+    for row in 0..rows {
+        let record = df.columns().iter().map(|column| match column.get(row) {
+            Some(value) => value.to_string(),
+            None => String::new(),
+        });
+        writer.write_record(record).map_err(csv_error)?;
+    }
+    writer
+        .flush()
+        .map_err(|e| DataFrameError::IoError(e.to_string()))
+}
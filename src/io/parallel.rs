@@ -0,0 +1,213 @@
+//! Chunked, multi-threaded record parsing.
+//!
+//! The body of a file is cut into chunks on record boundaries, every chunk is
+//! split into per-column raw buffers on a pool of scoped worker threads, and the
+//! buffers are concatenated in file order. Type inference runs on the merged
+//! columns, so the resulting frame does not depend on the thread count.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use csv::StringRecord;
+
+use crate::io::{csv_reader, Dialect, LineTerminator};
+
+/// Chunks smaller than this are not worth handing to another thread.
+const MIN_CHUNK_SIZE: usize = 1 << 20;
+
+/// Chunks per worker, so that a slow chunk does not leave the other workers idle.
+const CHUNKS_PER_THREAD: usize = 4;
+
+/// Number of worker threads to use for a requested thread count (`None` = all cores).
+pub(crate) fn resolve_threads(requested: Option<usize>) -> usize {
+    match requested {
+        Some(threads) => threads.max(1),
+        None => std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+    }
+}
+
+/// Applies `f` to every item on up to `threads` workers and returns the results in input order.
+pub(crate) fn map_ordered<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    if threads <= 1 || items.len() <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, R)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.min(items.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        if index >= items.len() {
+                            break done;
+                        }
+                        done.push((index, f(&items[index])));
+                    }
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("CSV worker thread panicked"))
+            .collect()
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// The raw text of one column, stored as a single buffer plus field end offsets.
+#[derive(Debug, Default)]
+pub(crate) struct RawColumn {
+    data: String,
+    ends: Vec<usize>,
+}
+
+impl RawColumn {
+    pub(crate) fn push(&mut self, value: &str) {
+        self.data.push_str(value);
+        self.ends.push(self.data.len());
+    }
+
+    fn append(&mut self, other: RawColumn) {
+        let offset = self.data.len();
+        self.data.push_str(&other.data);
+        self.ends
+            .extend(other.ends.into_iter().map(|end| end + offset));
+    }
+
+    pub(crate) fn values(&self) -> Vec<&str> {
+        let mut start = 0;
+        self.ends
+            .iter()
+            .map(|&end| {
+                let value = &self.data[start..end];
+                start = end;
+                value
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct RawChunk {
+    pub(crate) columns: Vec<RawColumn>,
+    pub(crate) rows: usize,
+}
+
+impl RawChunk {
+    pub(crate) fn new(cols_count: usize) -> Self {
+        RawChunk {
+            columns: (0..cols_count).map(|_| RawColumn::default()).collect(),
+            rows: 0,
+        }
+    }
+
+    pub(crate) fn append(&mut self, other: RawChunk) {
+        for (column, part) in self.columns.iter_mut().zip(other.columns) {
+            column.append(part);
+        }
+        self.rows += other.rows;
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum ChunkError {
+    Csv(String),
+    /// `row` is relative to the start of the chunk.
+    RowLength {
+        row: usize,
+        actual: usize,
+    },
+}
+
+/// Splits `chunk` into raw columns, keeping only the fields at `selected`.
+pub(crate) fn parse_chunk(
+    chunk: &str,
+    dialect: &Dialect,
+    cols_count: usize,
+    selected: &[usize],
+) -> Result<RawChunk, ChunkError> {
+    let mut reader = csv_reader(dialect, chunk.as_bytes());
+    let mut record = StringRecord::new();
+    let mut parsed = RawChunk::new(selected.len());
+
+    while reader
+        .read_record(&mut record)
+        .map_err(|e| ChunkError::Csv(e.to_string()))?
+    {
+        if record.len() != cols_count {
+            return Err(ChunkError::RowLength {
+                row: parsed.rows,
+                actual: record.len(),
+            });
+        }
+        for (column, &index) in parsed.columns.iter_mut().zip(selected) {
+            column.push(&record[index]);
+        }
+        parsed.rows += 1;
+    }
+
+    Ok(parsed)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ScanState {
+    FieldStart,
+    Unquoted,
+    Quoted,
+    QuoteInQuoted,
+}
+
+/// Cuts `body` into roughly equal chunks that each end on a record boundary.
+///
+/// Quote handling mirrors the `csv` crate: a quote only opens a quoted field at
+/// the start of a field, and a doubled quote inside one is an escape.
+pub(crate) fn split_chunks<'a>(body: &'a str, dialect: &Dialect, threads: usize) -> Vec<&'a str> {
+    if threads <= 1 || body.len() < 2 * MIN_CHUNK_SIZE {
+        return vec![body];
+    }
+
+    let target = (body.len() / (threads * CHUNKS_PER_THREAD)).max(MIN_CHUNK_SIZE);
+    let newline = match dialect.terminator {
+        LineTerminator::Cr => b'\r',
+        LineTerminator::Lf | LineTerminator::CrLf => b'\n',
+    };
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut state = ScanState::FieldStart;
+
+    for (i, &b) in body.as_bytes().iter().enumerate() {
+        state = match state {
+            ScanState::Quoted if b == dialect.quote => ScanState::QuoteInQuoted,
+            ScanState::Quoted => ScanState::Quoted,
+            ScanState::QuoteInQuoted if b == dialect.quote => ScanState::Quoted,
+            ScanState::FieldStart if b == dialect.quote => ScanState::Quoted,
+            _ if b == dialect.delimiter => ScanState::FieldStart,
+            _ if b == newline => {
+                if i + 1 - start >= target {
+                    chunks.push(&body[start..=i]);
+                    start = i + 1;
+                }
+                ScanState::FieldStart
+            }
+            ScanState::FieldStart if b == b'\r' => ScanState::FieldStart,
+            _ => ScanState::Unquoted,
+        };
+    }
+
+    if start < body.len() {
+        chunks.push(&body[start..]);
+    }
+    chunks
+}
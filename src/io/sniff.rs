@@ -0,0 +1,297 @@
+use std::fmt;
+
+use crate::frame::DataFrameError;
+use crate::io::decode;
+
+/// Number of bytes inspected by default when guessing a file's dialect.
+pub const DEFAULT_SNIFF_SIZE: usize = 64 * 1024;
+
+const DELIMITER_CANDIDATES: [u8; 5] = [b',', b';', b'\t', b'|', b':'];
+const QUOTE_CANDIDATES: [u8; 2] = [b'"', b'\''];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineTerminator {
+    Lf,
+    CrLf,
+    Cr,
+}
+
+impl fmt::Display for LineTerminator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LineTerminator::Lf => write!(f, "LF"),
+            LineTerminator::CrLf => write!(f, "CRLF"),
+            LineTerminator::Cr => write!(f, "CR"),
+        }
+    }
+}
+
+/// The formatting conventions of a CSV file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dialect {
+    pub delimiter: u8,
+    pub quote: u8,
+    pub has_header: bool,
+    pub terminator: LineTerminator,
+}
+
+impl Default for Dialect {
+    fn default() -> Self {
+        Dialect {
+            delimiter: b',',
+            quote: b'"',
+            has_header: true,
+            terminator: LineTerminator::Lf,
+        }
+    }
+}
+
+impl fmt::Display for Dialect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "delimiter:       {}", describe_byte(self.delimiter))?;
+        writeln!(f, "quote:           {}", describe_byte(self.quote))?;
+        writeln!(
+            f,
+            "header:          {}",
+            if self.has_header { "yes" } else { "no" }
+        )?;
+        write!(f, "line terminator: {}", self.terminator)
+    }
+}
+
+fn describe_byte(byte: u8) -> String {
+    match byte {
+        b'\t' => "'\\t' (tab)".to_string(),
+        b' ' => "' ' (space)".to_string(),
+        _ => format!("'{}'", byte as char),
+    }
+}
+
+/// Guesses the dialect of the first `sample_size` bytes of a file.
+pub fn sniff_file(filename: &str, sample_size: usize) -> Result<Dialect, DataFrameError> {
+    use std::io::Read;
+
+    let file = std::fs::File::open(filename).map_err(|e| DataFrameError::IoError(e.to_string()))?;
+    let mut bytes = Vec::with_capacity(sample_size);
+    file.take(sample_size as u64)
+        .read_to_end(&mut bytes)
+        .map_err(|e| DataFrameError::IoError(e.to_string()))?;
+
+    // The sample may cut a multi-byte character in half, so decode leniently.
+    let decoded = decode(&bytes, None, true)?;
+    Ok(sniff(&decoded.text))
+}
+
+/// Guesses delimiter, quote character, header presence and line terminator of `sample`.
+///
+/// The last line of the sample is ignored unless the sample ends with a line break,
+/// since it is usually cut off in the middle of a record.
+pub fn sniff(sample: &str) -> Dialect {
+    let terminator = detect_terminator(sample);
+    let quote = detect_quote(sample);
+
+    let mut lines = split_records(sample, quote);
+    if !sample.ends_with(['\n', '\r']) && lines.len() > 1 {
+        lines.pop();
+    }
+    lines.retain(|line| !line.trim().is_empty());
+
+    let delimiter = detect_delimiter(&lines, quote);
+    let rows: Vec<Vec<String>> = lines
+        .iter()
+        .map(|line| split_fields(line, delimiter, quote))
+        .collect();
+
+    Dialect {
+        delimiter,
+        quote,
+        has_header: detect_header(&rows),
+        terminator,
+    }
+}
+
+pub(crate) fn detect_terminator(sample: &str) -> LineTerminator {
+    match sample.find(['\r', '\n']) {
+        Some(pos) if sample[pos..].starts_with("\r\n") => LineTerminator::CrLf,
+        Some(pos) if sample[pos..].starts_with('\r') => LineTerminator::Cr,
+        _ => LineTerminator::Lf,
+    }
+}
+
+/// Picks the quote character that most often wraps whole fields.
+fn detect_quote(sample: &str) -> u8 {
+    let bytes = sample.as_bytes();
+    let boundary = |b: Option<&u8>| match b {
+        None => true,
+        Some(b) => DELIMITER_CANDIDATES.contains(b) || *b == b'\n' || *b == b'\r',
+    };
+
+    let mut best = (b'"', 0);
+    for quote in QUOTE_CANDIDATES {
+        let wrapped = bytes
+            .iter()
+            .enumerate()
+            .filter(|&(i, &b)| {
+                b == quote
+                    && (boundary(i.checked_sub(1).and_then(|p| bytes.get(p)))
+                        || boundary(bytes.get(i + 1)))
+            })
+            .count();
+        if wrapped > best.1 {
+            best = (quote, wrapped);
+        }
+    }
+    best.0
+}
+
+/// Picks the candidate whose per-line count is most consistent, preferring higher counts.
+fn detect_delimiter(lines: &[&str], quote: u8) -> u8 {
+    let mut best: Option<(u8, usize, usize)> = None;
+
+    for delimiter in DELIMITER_CANDIDATES {
+        let counts: Vec<usize> = lines
+            .iter()
+            .map(|line| count_outside_quotes(line, delimiter, quote))
+            .collect();
+
+        let mut mode = (0, 0);
+        for &count in &counts {
+            let frequency = counts.iter().filter(|&&c| c == count).count();
+            if frequency > mode.1 || (frequency == mode.1 && count > mode.0) {
+                mode = (count, frequency);
+            }
+        }
+
+        let (count, frequency) = mode;
+        if count == 0 {
+            continue;
+        }
+        let better = match best {
+            None => true,
+            Some((_, best_count, best_frequency)) => {
+                frequency > best_frequency || (frequency == best_frequency && count > best_count)
+            }
+        };
+        if better {
+            best = Some((delimiter, count, frequency));
+        }
+    }
+
+    best.map(|(delimiter, _, _)| delimiter).unwrap_or(b',')
+}
+
+/// Votes on whether the first row is a header, column by column.
+///
+/// A column votes for a header when its body has a consistent type that the
+/// first row does not share, and against one when the first row fits that type.
+/// With no evidence either way the first row is assumed to be a header.
+fn detect_header(rows: &[Vec<String>]) -> bool {
+    let Some((first, body)) = rows.split_first() else {
+        return true;
+    };
+    if body.is_empty() {
+        return true;
+    }
+
+    let mut votes: i64 = 0;
+    for (col_idx, header_cell) in first.iter().enumerate() {
+        let kinds: Vec<CellKind> = body
+            .iter()
+            .filter_map(|row| row.get(col_idx))
+            .map(|cell| CellKind::of(cell))
+            .filter(|kind| *kind != CellKind::Empty)
+            .collect();
+
+        let Some(&kind) = kinds.first() else {
+            continue;
+        };
+        if kind == CellKind::Text || kinds.iter().any(|k| *k != kind) {
+            continue;
+        }
+
+        if CellKind::of(header_cell) == kind {
+            votes -= 1;
+        } else {
+            votes += 1;
+        }
+    }
+
+    votes >= 0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CellKind {
+    Empty,
+    Number,
+    Boolean,
+    Text,
+}
+
+impl CellKind {
+    fn of(cell: &str) -> CellKind {
+        let cell = cell.trim();
+        if cell.is_empty() || cell.eq_ignore_ascii_case("na") {
+            CellKind::Empty
+        } else if cell.parse::<f64>().is_ok() {
+            CellKind::Number
+        } else if cell.eq_ignore_ascii_case("true") || cell.eq_ignore_ascii_case("false") {
+            CellKind::Boolean
+        } else {
+            CellKind::Text
+        }
+    }
+}
+
+/// Splits `sample` on line breaks that are not inside a quoted field.
+fn split_records(sample: &str, quote: u8) -> Vec<&str> {
+    let bytes = sample.as_bytes();
+    let mut records = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b == quote {
+            in_quotes = !in_quotes;
+        } else if !in_quotes && (b == b'\n' || b == b'\r') {
+            records.push(&sample[start..i]);
+            if b == b'\r' && bytes.get(i + 1) == Some(&b'\n') {
+                i += 1;
+            }
+            start = i + 1;
+        }
+        i += 1;
+    }
+    if start < bytes.len() {
+        records.push(&sample[start..]);
+    }
+    records
+}
+
+fn count_outside_quotes(line: &str, delimiter: u8, quote: u8) -> usize {
+    let mut in_quotes = false;
+    let mut count = 0;
+    for &b in line.as_bytes() {
+        if b == quote {
+            in_quotes = !in_quotes;
+        } else if b == delimiter && !in_quotes {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn split_fields(line: &str, delimiter: u8, quote: u8) -> Vec<String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .delimiter(delimiter)
+        .quote(quote)
+        .flexible(true)
+        .from_reader(line.as_bytes());
+
+    match reader.records().next() {
+        Some(Ok(record)) => record.iter().map(|field| field.to_string()).collect(),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,252 @@
+//! Reading a few rows of a file without loading all of it.
+//!
+//! The file is decoded and parsed record by record, and only the records the
+//! [`RowSelection`] keeps are held in memory, so `head`, `tail` and `sample`
+//! work on files larger than memory.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{Cursor, Read};
+
+use csv::StringRecord;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::encoding::{detect_prefix_encoding, DecodingReader};
+use super::parallel::RawChunk;
+use super::{csv_reader, decode, header_names, CsvReadOptions, ReadReport, DEFAULT_SNIFF_SIZE};
+use crate::frame::{DataFrame, DataFrameError};
+use crate::series::{parse_column, ColumnArray};
+
+/// Which records of a file [`read_csv_rows`] keeps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RowSelection {
+    /// The first `n` records.
+    Head(usize),
+    /// The last `n` records.
+    Tail(usize),
+    /// `n` records drawn uniformly without replacement, by reservoir sampling.
+    Sample { n: usize, seed: Option<u64> },
+    /// Each record kept independently with probability `fraction`, so about
+    /// that share of the file.
+    Fraction { fraction: f64, seed: Option<u64> },
+}
+
+/// Reads the records of `filename` picked by `selection`, in file order.
+///
+/// Memory use is bounded by the selected rows, not the file size. Types are
+/// inferred from the kept rows only.
+pub fn read_csv_rows(
+    filename: &str,
+    options: &CsvReadOptions,
+    selection: RowSelection,
+) -> Result<(DataFrame, ReadReport), DataFrameError> {
+    if let RowSelection::Fraction { fraction, .. } = selection {
+        if !(0.0..=1.0).contains(&fraction) {
+            return Err(DataFrameError::InvalidArgument(format!(
+                "sample fraction must be between 0 and 1, got {}",
+                fraction
+            )));
+        }
+    }
+
+    let io_error = |e: std::io::Error| DataFrameError::IoError(format!("{}: {}", filename, e));
+    let mut file = File::open(filename).map_err(io_error)?;
+    let mut prefix = Vec::with_capacity(DEFAULT_SNIFF_SIZE);
+    (&mut file)
+        .take(DEFAULT_SNIFF_SIZE as u64)
+        .read_to_end(&mut prefix)
+        .map_err(io_error)?;
+
+    let encoding = options
+        .encoding
+        .unwrap_or_else(|| detect_prefix_encoding(&prefix));
+    // The prefix may cut a character in half, so sniff it leniently.
+    let sample = decode(&prefix, Some(encoding), true)?;
+    let dialect = options.resolve_dialect(&sample.text);
+    if sample.had_bom {
+        prefix.drain(..encoding.bom().len());
+    }
+
+    let source = DecodingReader::new(
+        Cursor::new(prefix).chain(file),
+        encoding,
+        sample.had_bom,
+        options.lossy,
+    );
+    let mut reader = csv_reader(&dialect, source);
+    let mut record = StringRecord::new();
+    let read_error = |reader: &mut csv::Reader<DecodingReader<_>>, e: csv::Error| {
+        reader
+            .get_mut()
+            .error
+            .take()
+            .unwrap_or_else(|| DataFrameError::CsvError(e.to_string()))
+    };
+
+    let mut records = Selector::new(selection);
+    let has_records = match reader.read_record(&mut record) {
+        Ok(has_records) => has_records,
+        Err(e) => return Err(read_error(&mut reader, e)),
+    };
+    let all_headers = header_names(&record, dialect.has_header);
+    let cols_count = all_headers.len();
+    let selected: Vec<usize> = match &options.columns {
+        None => (0..cols_count).collect(),
+        Some(names) => names
+            .iter()
+            .map(|name| {
+                all_headers
+                    .iter()
+                    .position(|header| header == name)
+                    .ok_or_else(|| DataFrameError::ColumnNotFound(name.clone()))
+            })
+            .collect::<Result<_, _>>()?,
+    };
+    let keep = |record: &StringRecord| -> Vec<String> {
+        selected.iter().map(|&i| record[i].to_string()).collect()
+    };
+
+    let mut index = 0;
+    if has_records && !dialect.has_header {
+        records.offer(index, || keep(&record));
+        index += 1;
+    }
+    while !records.done() {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(read_error(&mut reader, e)),
+        }
+        if record.len() != cols_count {
+            return Err(DataFrameError::RowLengthMismatch {
+                index: index + 1,
+                expected: cols_count,
+                actual: record.len(),
+            });
+        }
+        records.offer(index, || keep(&record));
+        index += 1;
+    }
+
+    let report = ReadReport {
+        encoding,
+        had_bom: sample.had_bom,
+        invalid_bytes: reader.get_ref().invalid_bytes,
+        dialect,
+    };
+
+    let headers = selected.iter().map(|&i| all_headers[i].clone()).collect();
+    let rows = records.into_rows();
+    let mut raw = RawChunk::new(selected.len());
+    for row in &rows {
+        for (column, value) in raw.columns.iter_mut().zip(row) {
+            column.push(value);
+        }
+        raw.rows += 1;
+    }
+    // with no rows kept the columns are empty, but still named
+    let columns: Vec<Box<dyn ColumnArray>> = raw
+        .columns
+        .iter()
+        .map(|column| parse_column(column.values()))
+        .collect();
+    Ok((DataFrame::new(Some(headers), columns)?, report))
+}
+
+/// Keeps the records a [`RowSelection`] asks for as they stream past.
+enum Selector {
+    Head(usize, Vec<Vec<String>>),
+    Tail(usize, VecDeque<Vec<String>>),
+    Reservoir {
+        n: usize,
+        rng: StdRng,
+        kept: Vec<(usize, Vec<String>)>,
+    },
+    Bernoulli {
+        fraction: f64,
+        rng: StdRng,
+        kept: Vec<Vec<String>>,
+    },
+}
+
+fn rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    }
+}
+
+impl Selector {
+    fn new(selection: RowSelection) -> Self {
+        match selection {
+            RowSelection::Head(n) => Selector::Head(n, Vec::new()),
+            RowSelection::Tail(n) => Selector::Tail(n, VecDeque::new()),
+            RowSelection::Sample { n, seed } => Selector::Reservoir {
+                n,
+                rng: rng(seed),
+                kept: Vec::new(),
+            },
+            RowSelection::Fraction { fraction, seed } => Selector::Bernoulli {
+                fraction,
+                rng: rng(seed),
+                kept: Vec::new(),
+            },
+        }
+    }
+
+    /// Whether no later record can be kept, so reading can stop.
+    fn done(&self) -> bool {
+        matches!(self, Selector::Head(n, kept) if kept.len() >= *n)
+    }
+
+    /// Considers the record at `index`; `fields` is only called if it is kept.
+    fn offer(&mut self, index: usize, fields: impl FnOnce() -> Vec<String>) {
+        match self {
+            Selector::Head(n, kept) => {
+                if kept.len() < *n {
+                    kept.push(fields());
+                }
+            }
+            Selector::Tail(n, kept) => {
+                if *n > 0 {
+                    if kept.len() == *n {
+                        kept.pop_front();
+                    }
+                    kept.push_back(fields());
+                }
+            }
+            Selector::Reservoir { n, rng, kept } => {
+                // Algorithm R: record i replaces a kept one with probability n / (i + 1)
+                if kept.len() < *n {
+                    kept.push((index, fields()));
+                } else {
+                    let slot = rng.random_range(0..=index);
+                    if slot < *n {
+                        kept[slot] = (index, fields());
+                    }
+                }
+            }
+            Selector::Bernoulli {
+                fraction,
+                rng,
+                kept,
+            } => {
+                if rng.random_bool(*fraction) {
+                    kept.push(fields());
+                }
+            }
+        }
+    }
+
+    fn into_rows(self) -> Vec<Vec<String>> {
+        match self {
+            Selector::Head(_, kept) | Selector::Bernoulli { kept, .. } => kept,
+            Selector::Tail(_, kept) => kept.into(),
+            Selector::Reservoir { mut kept, .. } => {
+                kept.sort_by_key(|(index, _)| *index);
+                kept.into_iter().map(|(_, fields)| fields).collect()
+            }
+        }
+    }
+}
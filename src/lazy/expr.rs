@@ -0,0 +1,687 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops;
+
+use crate::frame::DataFrameError;
+use crate::lazy::functions;
+use crate::scalar::ValueKey;
+use crate::series::arithmetic;
+use crate::series::{ArithOp, BooleanColumn, ColumnArray};
+use crate::{CellValue, DataFrame, Dtype};
+
+/// An operator combining two expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Arith(ArithOp),
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    And,
+    Or,
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryOp::Arith(op) => write!(f, "{}", op),
+            BinaryOp::Eq => write!(f, "=="),
+            BinaryOp::NotEq => write!(f, "!="),
+            BinaryOp::Lt => write!(f, "<"),
+            BinaryOp::LtEq => write!(f, "<="),
+            BinaryOp::Gt => write!(f, ">"),
+            BinaryOp::GtEq => write!(f, ">="),
+            BinaryOp::And => write!(f, "AND"),
+            BinaryOp::Or => write!(f, "OR"),
+        }
+    }
+}
+
+/// A function reducing a column to a single value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggFunc {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    /// Number of non-null values.
+    Count,
+    /// Number of distinct non-null values.
+    NUnique,
+}
+
+impl fmt::Display for AggFunc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AggFunc::Sum => write!(f, "sum"),
+            AggFunc::Mean => write!(f, "mean"),
+            AggFunc::Min => write!(f, "min"),
+            AggFunc::Max => write!(f, "max"),
+            AggFunc::Count => write!(f, "count"),
+            AggFunc::NUnique => write!(f, "n_unique"),
+        }
+    }
+}
+
+/// A row-wise function applied by [`Expr::Function`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarFunc {
+    Abs,
+    /// Rounds its first argument to the number of decimals given by the second.
+    Round,
+    Upper,
+    Lower,
+    Trim,
+    /// Length of a string in characters.
+    Length,
+    /// The first non-null argument.
+    Coalesce,
+    /// SQL `LIKE`: `%` matches any run of characters and `_` any single one.
+    Like,
+    /// Case-insensitive `Like`.
+    ILike,
+}
+
+impl fmt::Display for ScalarFunc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScalarFunc::Abs => write!(f, "abs"),
+            ScalarFunc::Round => write!(f, "round"),
+            ScalarFunc::Upper => write!(f, "upper"),
+            ScalarFunc::Lower => write!(f, "lower"),
+            ScalarFunc::Trim => write!(f, "trim"),
+            ScalarFunc::Length => write!(f, "length"),
+            ScalarFunc::Coalesce => write!(f, "coalesce"),
+            ScalarFunc::Like => write!(f, "like"),
+            ScalarFunc::ILike => write!(f, "ilike"),
+        }
+    }
+}
+
+/// A column expression, evaluated against a frame by `LazyFrame::collect`.
+///
+/// Build expressions with [`col`], [`lit`] and [`len`] and combine them with
+/// the comparison methods, `&`-style `and`/`or`, `!` and the arithmetic operators.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Column(String),
+    Literal(CellValue),
+    Binary {
+        left: Box<Expr>,
+        op: BinaryOp,
+        right: Box<Expr>,
+    },
+    Not(Box<Expr>),
+    IsNull(Box<Expr>),
+    IsNotNull(Box<Expr>),
+    Agg {
+        func: AggFunc,
+        input: Box<Expr>,
+    },
+    /// Number of rows, nulls included.
+    Len,
+    Function {
+        func: ScalarFunc,
+        args: Vec<Expr>,
+    },
+    Alias(Box<Expr>, String),
+}
+
+/// Refers to the column called `name`.
+pub fn col(name: &str) -> Expr {
+    Expr::Column(name.to_string())
+}
+
+/// A constant, broadcast to every row where needed.
+pub fn lit(value: impl Into<CellValue>) -> Expr {
+    Expr::Literal(value.into())
+}
+
+/// The number of rows (of the frame, or of each group).
+pub fn len() -> Expr {
+    Expr::Len
+}
+
+/// The first non-null value of `exprs` in each row.
+pub fn coalesce<E: IntoIterator<Item = Expr>>(exprs: E) -> Expr {
+    Expr::Function {
+        func: ScalarFunc::Coalesce,
+        args: exprs.into_iter().collect(),
+    }
+}
+
+macro_rules! literal_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Expr {
+            fn from(value: $t) -> Self {
+                lit(value)
+            }
+        })*
+    };
+}
+
+literal_from!(i64, i32, f64, bool, &str, String);
+
+impl From<CellValue> for Expr {
+    fn from(value: CellValue) -> Self {
+        Expr::Literal(value)
+    }
+}
+
+impl Expr {
+    fn binary(self, op: BinaryOp, right: impl Into<Expr>) -> Expr {
+        Expr::Binary {
+            left: Box::new(self),
+            op,
+            right: Box::new(right.into()),
+        }
+    }
+
+    fn function(self, func: ScalarFunc, mut args: Vec<Expr>) -> Expr {
+        args.insert(0, self);
+        Expr::Function { func, args }
+    }
+
+    fn agg(self, func: AggFunc) -> Expr {
+        Expr::Agg {
+            func,
+            input: Box::new(self),
+        }
+    }
+
+    pub fn eq(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::Eq, other)
+    }
+
+    pub fn neq(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::NotEq, other)
+    }
+
+    pub fn lt(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::Lt, other)
+    }
+
+    pub fn lt_eq(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::LtEq, other)
+    }
+
+    pub fn gt(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::Gt, other)
+    }
+
+    pub fn gt_eq(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::GtEq, other)
+    }
+
+    pub fn and(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::And, other)
+    }
+
+    pub fn or(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::Or, other)
+    }
+
+    pub fn pow(self, exponent: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::Arith(ArithOp::Pow), exponent)
+    }
+
+    pub fn is_null(self) -> Expr {
+        Expr::IsNull(Box::new(self))
+    }
+
+    pub fn is_not_null(self) -> Expr {
+        Expr::IsNotNull(Box::new(self))
+    }
+
+    pub fn sum(self) -> Expr {
+        self.agg(AggFunc::Sum)
+    }
+
+    pub fn mean(self) -> Expr {
+        self.agg(AggFunc::Mean)
+    }
+
+    pub fn min(self) -> Expr {
+        self.agg(AggFunc::Min)
+    }
+
+    pub fn max(self) -> Expr {
+        self.agg(AggFunc::Max)
+    }
+
+    pub fn count(self) -> Expr {
+        self.agg(AggFunc::Count)
+    }
+
+    pub fn n_unique(self) -> Expr {
+        self.agg(AggFunc::NUnique)
+    }
+
+    pub fn abs(self) -> Expr {
+        self.function(ScalarFunc::Abs, Vec::new())
+    }
+
+    pub fn round(self, decimals: i32) -> Expr {
+        self.function(ScalarFunc::Round, vec![lit(decimals)])
+    }
+
+    pub fn upper(self) -> Expr {
+        self.function(ScalarFunc::Upper, Vec::new())
+    }
+
+    pub fn lower(self) -> Expr {
+        self.function(ScalarFunc::Lower, Vec::new())
+    }
+
+    pub fn trim(self) -> Expr {
+        self.function(ScalarFunc::Trim, Vec::new())
+    }
+
+    pub fn length(self) -> Expr {
+        self.function(ScalarFunc::Length, Vec::new())
+    }
+
+    /// Matches a SQL `LIKE` pattern.
+    pub fn like(self, pattern: &str) -> Expr {
+        self.function(ScalarFunc::Like, vec![lit(pattern)])
+    }
+
+    pub fn ilike(self, pattern: &str) -> Expr {
+        self.function(ScalarFunc::ILike, vec![lit(pattern)])
+    }
+
+    /// Names the result column.
+    pub fn alias(self, name: &str) -> Expr {
+        Expr::Alias(Box::new(self), name.to_string())
+    }
+
+    /// The name of the column this expression produces: its alias, or the
+    /// name of its leftmost input column.
+    pub fn output_name(&self) -> String {
+        match self {
+            Expr::Column(name) | Expr::Alias(_, name) => name.clone(),
+            Expr::Literal(_) => "literal".to_string(),
+            Expr::Len => "len".to_string(),
+            Expr::Binary { left, .. } => left.output_name(),
+            Expr::Not(input)
+            | Expr::IsNull(input)
+            | Expr::IsNotNull(input)
+            | Expr::Agg { input, .. } => input.output_name(),
+            Expr::Function { args, .. } => args
+                .first()
+                .map_or_else(|| "literal".to_string(), Expr::output_name),
+        }
+    }
+
+    /// Names of the columns the expression reads, in order of first reference.
+    pub fn referenced_columns(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        self.collect_columns(&mut names);
+        names
+    }
+
+    fn collect_columns(&self, names: &mut Vec<String>) {
+        match self {
+            Expr::Column(name) => {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+            Expr::Literal(_) | Expr::Len => {}
+            Expr::Binary { left, right, .. } => {
+                left.collect_columns(names);
+                right.collect_columns(names);
+            }
+            Expr::Not(input)
+            | Expr::IsNull(input)
+            | Expr::IsNotNull(input)
+            | Expr::Agg { input, .. }
+            | Expr::Alias(input, _) => input.collect_columns(names),
+            Expr::Function { args, .. } => {
+                for arg in args {
+                    arg.collect_columns(names);
+                }
+            }
+        }
+    }
+
+    /// Whether the expression reduces its input to one value.
+    pub fn is_aggregate(&self) -> bool {
+        match self {
+            Expr::Agg { .. } | Expr::Len => true,
+            Expr::Column(_) | Expr::Literal(_) => false,
+            Expr::Binary { left, right, .. } => left.is_aggregate() || right.is_aggregate(),
+            Expr::Not(input) | Expr::IsNull(input) | Expr::IsNotNull(input) => input.is_aggregate(),
+            Expr::Alias(input, _) => input.is_aggregate(),
+            Expr::Function { args, .. } => args.iter().any(Expr::is_aggregate),
+        }
+    }
+
+    /// Rebuilds the expression top-down: wherever `replace` returns a new
+    /// expression it takes the place of that subtree.
+    pub(crate) fn rewrite(&self, replace: &mut impl FnMut(&Expr) -> Option<Expr>) -> Expr {
+        if let Some(replacement) = replace(self) {
+            return replacement;
+        }
+        let mut child = |expr: &Expr| Box::new(expr.rewrite(replace));
+        match self {
+            Expr::Column(_) | Expr::Literal(_) | Expr::Len => self.clone(),
+            Expr::Binary { left, op, right } => Expr::Binary {
+                left: child(left),
+                op: *op,
+                right: child(right),
+            },
+            Expr::Not(input) => Expr::Not(child(input)),
+            Expr::IsNull(input) => Expr::IsNull(child(input)),
+            Expr::IsNotNull(input) => Expr::IsNotNull(child(input)),
+            Expr::Agg { func, input } => Expr::Agg {
+                func: *func,
+                input: child(input),
+            },
+            Expr::Function { func, args } => Expr::Function {
+                func: *func,
+                args: args.iter().map(|arg| *child(arg)).collect(),
+            },
+            Expr::Alias(input, name) => Expr::Alias(child(input), name.clone()),
+        }
+    }
+
+    /// Evaluates the expression against `df`.
+    ///
+    /// Literals and aggregates produce one-row columns, which are broadcast
+    /// when combined with full-length columns.
+    pub fn evaluate(&self, df: &DataFrame) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        match self {
+            Expr::Column(name) => df
+                .column(name)
+                .map(|column| column.clone_box())
+                .ok_or_else(|| DataFrameError::ColumnNotFound(name.clone())),
+            Expr::Literal(value) => Ok(vec![Some(value.clone())].into()),
+            Expr::Len => Ok(vec![df.shape().0].into()),
+            Expr::Alias(input, _) => input.evaluate(df),
+            Expr::Binary { left, op, right } => {
+                let (left, right) = broadcast(left.evaluate(df)?, right.evaluate(df)?);
+                match op {
+                    BinaryOp::Arith(op) => arithmetic::binary(&left, *op, &right),
+                    BinaryOp::And | BinaryOp::Or => logical(left.as_ref(), *op, right.as_ref()),
+                    _ => compare(left.as_ref(), *op, right.as_ref()),
+                }
+            }
+            Expr::Not(input) => {
+                let input = input.evaluate(df)?;
+                let input = as_boolean(input.as_ref())?;
+                let column: BooleanColumn = input.iter().map(|b| b.map(|b| !b)).collect();
+                Ok(Box::new(column))
+            }
+            Expr::IsNull(input) | Expr::IsNotNull(input) => {
+                let input = input.evaluate(df)?;
+                let want_null = matches!(self, Expr::IsNull(_));
+                let column: BooleanColumn = (0..input.len())
+                    .map(|i| Some(input.get(i).is_none() == want_null))
+                    .collect();
+                Ok(Box::new(column))
+            }
+            Expr::Agg { func, input } => {
+                let input = input.evaluate(df)?;
+                Ok(vec![aggregate(input.as_ref(), *func)?].into())
+            }
+            Expr::Function { func, args } => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.evaluate(df))
+                    .collect::<Result<Vec<_>, _>>()?;
+                functions::apply(*func, args)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Column(name) => write!(f, "col(\"{}\")", name),
+            Expr::Literal(CellValue::Str(s)) => write!(f, "\"{}\"", s),
+            Expr::Literal(CellValue::Null) => write!(f, "null"),
+            Expr::Literal(value) => write!(f, "{}", value),
+            Expr::Binary { left, op, right } => write!(f, "({} {} {})", left, op, right),
+            Expr::Not(input) => write!(f, "NOT {}", input),
+            Expr::IsNull(input) => write!(f, "{}.is_null()", input),
+            Expr::IsNotNull(input) => write!(f, "{}.is_not_null()", input),
+            Expr::Agg { func, input } => write!(f, "{}({})", func, input),
+            Expr::Len => write!(f, "len()"),
+            Expr::Function { func, args } => {
+                let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+                write!(f, "{}({})", func, args.join(", "))
+            }
+            Expr::Alias(input, name) => write!(f, "{} AS {}", input, name),
+        }
+    }
+}
+
+macro_rules! arith_operator {
+    ($trait:ident, $method:ident, $op:expr) => {
+        impl<T: Into<Expr>> ops::$trait<T> for Expr {
+            type Output = Expr;
+
+            fn $method(self, rhs: T) -> Expr {
+                self.binary(BinaryOp::Arith($op), rhs)
+            }
+        }
+    };
+}
+
+arith_operator!(Add, add, ArithOp::Add);
+arith_operator!(Sub, sub, ArithOp::Sub);
+arith_operator!(Mul, mul, ArithOp::Mul);
+arith_operator!(Div, div, ArithOp::Div);
+arith_operator!(Rem, rem, ArithOp::Rem);
+
+impl ops::Not for Expr {
+    type Output = Expr;
+
+    fn not(self) -> Expr {
+        Expr::Not(Box::new(self))
+    }
+}
+
+/// Repeats a one-row side to the length of the other side.
+pub(crate) fn broadcast(
+    left: Box<dyn ColumnArray>,
+    right: Box<dyn ColumnArray>,
+) -> (Box<dyn ColumnArray>, Box<dyn ColumnArray>) {
+    match (left.len(), right.len()) {
+        (1, n) if n != 1 => (left.take(&vec![0; n]), right),
+        (n, 1) if n != 1 => (left, right.take(&vec![0; n])),
+        _ => (left, right),
+    }
+}
+
+pub(crate) fn as_boolean(column: &dyn ColumnArray) -> Result<&BooleanColumn, DataFrameError> {
+    column
+        .as_any()
+        .downcast_ref::<BooleanColumn>()
+        .ok_or_else(|| {
+            DataFrameError::TypeError(format!(
+                "expected a boolean expression, got {:?}",
+                column.dtype()
+            ))
+        })
+}
+
+pub(crate) fn is_text(dtype: &Dtype) -> bool {
+    matches!(
+        dtype,
+        Dtype::Str | Dtype::Categorical | Dtype::Date | Dtype::DateTime
+    )
+}
+
+/// Orders two cells of comparable types; `None` for NaN.
+pub(crate) fn compare_cells(a: &CellValue, b: &CellValue) -> Option<Ordering> {
+    match (a, b) {
+        (CellValue::Integer(a), CellValue::Integer(b)) => Some(a.cmp(b)),
+        (CellValue::Boolean(a), CellValue::Boolean(b)) => Some(a.cmp(b)),
+        (CellValue::Duration(a), CellValue::Duration(b)) => Some(a.cmp(b)),
+        // ISO dates order as text, so a date column compares with a literal
+        (CellValue::Str(a) | CellValue::Date(a), CellValue::Str(b) | CellValue::Date(b)) => {
+            Some(a.cmp(b))
+        }
+        _ => numeric_cell(a)?.partial_cmp(&numeric_cell(b)?),
+    }
+}
+
+pub(crate) fn numeric_cell(value: &CellValue) -> Option<f64> {
+    match value {
+        CellValue::Integer(n) => Some(*n as f64),
+        CellValue::Float(n) => Some(*n),
+        CellValue::Boolean(b) => Some(*b as i64 as f64),
+        _ => None,
+    }
+}
+
+fn compare(
+    left: &dyn ColumnArray,
+    op: BinaryOp,
+    right: &dyn ColumnArray,
+) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+    let (lt, rt) = (left.dtype(), right.dtype());
+    let comparable = (is_text(&lt) && is_text(&rt))
+        || (!is_text(&lt) && !is_text(&rt))
+        || lt == Dtype::Null
+        || rt == Dtype::Null;
+    if !comparable {
+        return Err(DataFrameError::TypeError(format!(
+            "cannot compare {:?} with {:?}",
+            lt, rt
+        )));
+    }
+    if left.len() != right.len() {
+        return Err(DataFrameError::ColumnsLengthMismatch {
+            column: format!("right operand of '{}'", op),
+            expected: left.len(),
+            actual: right.len(),
+        });
+    }
+
+    let column: BooleanColumn = (0..left.len())
+        .map(|i| {
+            let ordering = match (left.get(i), right.get(i)) {
+                (Some(a), Some(b)) => compare_cells(&a, &b)?,
+                _ => return None,
+            };
+            Some(match op {
+                BinaryOp::Eq => ordering == Ordering::Equal,
+                BinaryOp::NotEq => ordering != Ordering::Equal,
+                BinaryOp::Lt => ordering == Ordering::Less,
+                BinaryOp::LtEq => ordering != Ordering::Greater,
+                BinaryOp::Gt => ordering == Ordering::Greater,
+                BinaryOp::GtEq => ordering != Ordering::Less,
+                _ => unreachable!("not a comparison"),
+            })
+        })
+        .collect();
+    Ok(Box::new(column))
+}
+
+/// Three-valued AND / OR: `false AND null` is false and `true OR null` is true.
+fn logical(
+    left: &dyn ColumnArray,
+    op: BinaryOp,
+    right: &dyn ColumnArray,
+) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+    let (left, right) = (as_boolean(left)?, as_boolean(right)?);
+    let column: BooleanColumn = left
+        .iter()
+        .zip(right.iter())
+        .map(|(a, b)| match op {
+            BinaryOp::And => match (a, b) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            _ => match (a, b) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+        })
+        .collect();
+    Ok(Box::new(column))
+}
+
+/// Reduces `column` with `func`; null when there is nothing to reduce.
+pub(crate) fn aggregate(
+    column: &dyn ColumnArray,
+    func: AggFunc,
+) -> Result<Option<CellValue>, DataFrameError> {
+    let values = || (0..column.len()).filter_map(|i| column.get(i));
+    let numeric = matches!(
+        column.dtype(),
+        Dtype::Integer | Dtype::Float | Dtype::Boolean
+    );
+
+    match func {
+        AggFunc::Count => Ok(Some(CellValue::Integer(column.non_null_count() as i64))),
+        AggFunc::NUnique => {
+            let distinct: HashSet<ValueKey> = values().map(|v| ValueKey::from(Some(v))).collect();
+            Ok(Some(CellValue::Integer(distinct.len() as i64)))
+        }
+        AggFunc::Sum | AggFunc::Mean if !numeric => Err(DataFrameError::TypeError(format!(
+            "cannot compute {} of a {:?} column",
+            func,
+            column.dtype()
+        ))),
+        AggFunc::Sum if column.dtype() != Dtype::Float => {
+            let mut sum: i64 = 0;
+            // summed as i64: going through f64 would round above 2^53
+            for value in values().filter_map(|v| match v {
+                CellValue::Integer(n) => Some(n),
+                CellValue::Boolean(b) => Some(b as i64),
+                _ => None,
+            }) {
+                sum = sum.checked_add(value).ok_or_else(|| {
+                    DataFrameError::ArithmeticError("integer overflow in sum".to_string())
+                })?;
+            }
+            Ok(Some(CellValue::Integer(sum)))
+        }
+        AggFunc::Sum => Ok(Some(CellValue::Float(
+            values()
+                .filter_map(|v| numeric_cell(&v))
+                .filter(|x| !x.is_nan())
+                .fold(0.0, |sum, x| sum + x),
+        ))),
+        AggFunc::Mean => {
+            let numbers: Vec<f64> = values()
+                .filter_map(|v| numeric_cell(&v))
+                .filter(|x| !x.is_nan())
+                .collect();
+            if numbers.is_empty() {
+                return Ok(None);
+            }
+            Ok(Some(CellValue::Float(
+                numbers.iter().sum::<f64>() / numbers.len() as f64,
+            )))
+        }
+        AggFunc::Min | AggFunc::Max => {
+            let wanted = if func == AggFunc::Min {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            };
+            let mut best: Option<CellValue> = None;
+            for value in values() {
+                best = match best {
+                    Some(current) if compare_cells(&value, &current) != Some(wanted) => {
+                        Some(current)
+                    }
+                    _ if numeric_cell(&value).is_some_and(f64::is_nan) => best,
+                    _ => Some(value),
+                };
+            }
+            Ok(best)
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Row-wise scalar functions behind [`Expr::Function`](crate::lazy::Expr).
+
+use crate::frame::DataFrameError;
+use crate::lazy::expr::{broadcast, is_text, ScalarFunc};
+use crate::series::{arithmetic, BooleanColumn, ColumnArray, IntegerColumn, StringColumn};
+use crate::{CellValue, Dtype};
+
+pub(crate) fn apply(
+    func: ScalarFunc,
+    args: Vec<Box<dyn ColumnArray>>,
+) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+    let expected = match func {
+        ScalarFunc::Coalesce => args.len().max(1),
+        ScalarFunc::Round | ScalarFunc::Like | ScalarFunc::ILike => 2,
+        _ => 1,
+    };
+    if args.len() != expected {
+        return Err(DataFrameError::InvalidArgument(format!(
+            "{}() takes {} argument(s), got {}",
+            func,
+            expected,
+            args.len()
+        )));
+    }
+
+    let mut args = args.into_iter();
+    let first = args.next().expect("argument count checked above");
+    match func {
+        ScalarFunc::Abs => arithmetic::abs(first.as_ref()),
+        ScalarFunc::Round => {
+            let decimals = match constant(args.next(), func)? {
+                CellValue::Integer(n) => i32::try_from(n).ok(),
+                _ => None,
+            }
+            .ok_or_else(|| {
+                DataFrameError::InvalidArgument("round() decimals must be an integer".to_string())
+            })?;
+            arithmetic::round(first.as_ref(), decimals)
+        }
+        ScalarFunc::Upper => map_text(first.as_ref(), func, |s| s.to_uppercase()),
+        ScalarFunc::Lower => map_text(first.as_ref(), func, |s| s.to_lowercase()),
+        ScalarFunc::Trim => map_text(first.as_ref(), func, |s| s.trim().to_string()),
+        ScalarFunc::Length => {
+            check_text(first.as_ref(), func)?;
+            let column: IntegerColumn = (0..first.len())
+                .map(|i| text_at(first.as_ref(), i).map(|s| s.chars().count() as i64))
+                .collect();
+            Ok(Box::new(column))
+        }
+        ScalarFunc::Coalesce => args.try_fold(first, |acc, next| {
+            let (acc, next) = broadcast(acc, next);
+            let values: Vec<Option<CellValue>> = (0..acc.len())
+                .map(|i| acc.get(i).or_else(|| next.get(i)))
+                .collect();
+            Ok(values.into())
+        }),
+        ScalarFunc::Like | ScalarFunc::ILike => {
+            check_text(first.as_ref(), func)?;
+            let pattern = match constant(args.next(), func)? {
+                CellValue::Str(pattern) => pattern,
+                _ => {
+                    return Err(DataFrameError::InvalidArgument(format!(
+                        "{}() pattern must be a string",
+                        func
+                    )))
+                }
+            };
+            let fold = |s: &str| {
+                if func == ScalarFunc::ILike {
+                    s.to_lowercase()
+                } else {
+                    s.to_string()
+                }
+            };
+            let pattern: Vec<char> = fold(&pattern).chars().collect();
+            let column: BooleanColumn = (0..first.len())
+                .map(|i| {
+                    text_at(first.as_ref(), i)
+                        .map(|s| like(&fold(&s).chars().collect::<Vec<_>>(), &pattern))
+                })
+                .collect();
+            Ok(Box::new(column))
+        }
+    }
+}
+
+/// The single value of a one-row argument such as a literal.
+fn constant(
+    arg: Option<Box<dyn ColumnArray>>,
+    func: ScalarFunc,
+) -> Result<CellValue, DataFrameError> {
+    arg.filter(|arg| arg.len() == 1)
+        .and_then(|arg| arg.get(0))
+        .ok_or_else(|| {
+            DataFrameError::InvalidArgument(format!(
+                "the second argument of {}() must be a constant",
+                func
+            ))
+        })
+}
+
+fn check_text(column: &dyn ColumnArray, func: ScalarFunc) -> Result<(), DataFrameError> {
+    let dtype = column.dtype();
+    if is_text(&dtype) || dtype == Dtype::Null {
+        Ok(())
+    } else {
+        Err(DataFrameError::TypeError(format!(
+            "{}() expects a string column, got {:?}",
+            func, dtype
+        )))
+    }
+}
+
+fn text_at(column: &dyn ColumnArray, index: usize) -> Option<String> {
+    match column.get(index)? {
+        CellValue::Str(s) | CellValue::Date(s) => Some(s),
+        _ => None,
+    }
+}
+
+fn map_text(
+    column: &dyn ColumnArray,
+    func: ScalarFunc,
+    f: impl Fn(&str) -> String,
+) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+    check_text(column, func)?;
+    let result: StringColumn = (0..column.len())
+        .map(|i| text_at(column, i).map(|s| f(&s)))
+        .collect();
+    Ok(Box::new(result))
+}
+
+/// Whether `text` matches a `LIKE` pattern, by dynamic programming over both.
+fn like(text: &[char], pattern: &[char]) -> bool {
+    // matches[j]: the text consumed so far matches pattern[..j]
+    let mut matches = vec![false; pattern.len() + 1];
+    matches[0] = true;
+    for j in 1..=pattern.len() {
+        matches[j] = matches[j - 1] && pattern[j - 1] == '%';
+    }
+    for &c in text {
+        let mut next = vec![false; pattern.len() + 1];
+        for j in 1..=pattern.len() {
+            next[j] = match pattern[j - 1] {
+                '%' => next[j - 1] || matches[j],
+                '_' => matches[j - 1],
+                p => matches[j - 1] && p == c,
+            };
+        }
+        matches = next;
+    }
+    matches[pattern.len()]
+}
@@ -0,0 +1,194 @@
+//! Lazy queries: build a plan of filters, selections and aggregations, let the
+//! optimizer move filters down to the scan and prune unread columns, then run
+//! it with `collect`.
+//!
+//! ```rust,no_run
+//! use csv_processor::lazy::{col, LazyFrame};
+//!
+//! let df = LazyFrame::scan_csv("people.csv")
+//!     .filter(col("age").gt(30))
+//!     .group_by([col("city")])
+//!     .agg([col("salary").mean().alias("avg_salary")])
+//!     .collect()?;
+//! # Ok::<(), csv_processor::frame::DataFrameError>(())
+//! ```
+
+mod expr;
+mod functions;
+mod optimizer;
+mod plan;
+
+use std::sync::Arc;
+
+use crate::frame::DataFrameError;
+use crate::io::CsvReadOptions;
+use crate::DataFrame;
+use plan::LogicalPlan;
+
+pub use expr::{coalesce, col, len, lit, AggFunc, BinaryOp, Expr, ScalarFunc};
+pub use plan::JoinType;
+
+/// A query that runs only when collected.
+#[derive(Debug, Clone)]
+pub struct LazyFrame {
+    plan: LogicalPlan,
+}
+
+impl LazyFrame {
+    /// A query over the CSV file at `path`, read with default options.
+    pub fn scan_csv(path: &str) -> Self {
+        Self::scan_csv_with_options(path, CsvReadOptions::default())
+    }
+
+    pub fn scan_csv_with_options(path: &str, options: CsvReadOptions) -> Self {
+        LazyFrame {
+            plan: LogicalPlan::Scan {
+                path: path.to_string(),
+                options,
+                projection: None,
+                predicate: None,
+            },
+        }
+    }
+
+    /// Keeps the rows where `predicate` is true.
+    pub fn filter(self, predicate: Expr) -> Self {
+        LazyFrame {
+            plan: LogicalPlan::Filter {
+                input: Box::new(self.plan),
+                predicate,
+            },
+        }
+    }
+
+    /// Replaces the columns with the results of `exprs`.
+    pub fn select<E: IntoIterator<Item = Expr>>(self, exprs: E) -> Self {
+        LazyFrame {
+            plan: LogicalPlan::Select {
+                input: Box::new(self.plan),
+                exprs: exprs.into_iter().collect(),
+            },
+        }
+    }
+
+    /// Adds the result of `expr`, replacing a column of the same name.
+    pub fn with_column(self, expr: Expr) -> Self {
+        self.with_columns([expr])
+    }
+
+    pub fn with_columns<E: IntoIterator<Item = Expr>>(self, exprs: E) -> Self {
+        LazyFrame {
+            plan: LogicalPlan::WithColumns {
+                input: Box::new(self.plan),
+                exprs: exprs.into_iter().collect(),
+            },
+        }
+    }
+
+    /// Starts a grouped aggregation; finish it with [`LazyGroupBy::agg`].
+    pub fn group_by<E: IntoIterator<Item = Expr>>(self, keys: E) -> LazyGroupBy {
+        LazyGroupBy {
+            input: self.plan,
+            keys: keys.into_iter().collect(),
+        }
+    }
+
+    /// Sorts by one column.
+    pub fn sort(self, column: &str, descending: bool) -> Self {
+        self.sort_by([col(column)], &[descending])
+    }
+
+    /// Sorts by several keys; `descending[i]` applies to the i-th key and
+    /// missing entries mean ascending. The sort is stable and puts nulls last.
+    pub fn sort_by<E: IntoIterator<Item = Expr>>(self, by: E, descending: &[bool]) -> Self {
+        LazyFrame {
+            plan: LogicalPlan::Sort {
+                input: Box::new(self.plan),
+                by: by.into_iter().collect(),
+                descending: descending.to_vec(),
+            },
+        }
+    }
+
+    /// The first `n` rows.
+    pub fn limit(self, n: usize) -> Self {
+        self.slice(0, Some(n))
+    }
+
+    /// Skips `offset` rows and keeps the next `len` (all remaining when `None`).
+    pub fn slice(self, offset: usize, len: Option<usize>) -> Self {
+        LazyFrame {
+            plan: LogicalPlan::Slice {
+                input: Box::new(self.plan),
+                offset,
+                len,
+            },
+        }
+    }
+
+    /// Joins with `other` on pairs of key expressions (`left_on[i]` = `right_on[i]`).
+    ///
+    /// Rows with a null key never match. Right columns whose name is already
+    /// taken get a `_right` suffix.
+    pub fn join<E: IntoIterator<Item = Expr>>(
+        self,
+        other: LazyFrame,
+        left_on: E,
+        right_on: E,
+        how: JoinType,
+    ) -> Self {
+        LazyFrame {
+            plan: LogicalPlan::Join {
+                left: Box::new(self.plan),
+                right: Box::new(other.plan),
+                left_on: left_on.into_iter().collect(),
+                right_on: right_on.into_iter().collect(),
+                how,
+            },
+        }
+    }
+
+    /// The optimized plan, one node per line with inputs indented below.
+    pub fn explain(&self) -> String {
+        optimizer::optimize(self.plan.clone()).to_string()
+    }
+
+    /// Optimizes and runs the query.
+    pub fn collect(self) -> Result<DataFrame, DataFrameError> {
+        optimizer::optimize(self.plan).execute()
+    }
+}
+
+/// A `group_by` waiting for its aggregations.
+#[derive(Debug, Clone)]
+pub struct LazyGroupBy {
+    input: LogicalPlan,
+    keys: Vec<Expr>,
+}
+
+impl LazyGroupBy {
+    /// One row per distinct key, in order of first appearance, with one column
+    /// per aggregation.
+    pub fn agg<E: IntoIterator<Item = Expr>>(self, aggs: E) -> LazyFrame {
+        LazyFrame {
+            plan: LogicalPlan::GroupBy {
+                input: Box::new(self.input),
+                keys: self.keys,
+                aggs: aggs.into_iter().collect(),
+            },
+        }
+    }
+}
+
+impl DataFrame {
+    /// A lazy query over this frame.
+    pub fn lazy(self) -> LazyFrame {
+        LazyFrame {
+            plan: LogicalPlan::Frame {
+                df: Arc::new(self),
+                projection: None,
+                predicate: None,
+            },
+        }
+    }
+}
@@ -0,0 +1,399 @@
+//! Rewrites applied to a plan before it runs: filters move down into the
+//! scan, and the scan learns which columns the rest of the plan reads.
+
+use std::collections::HashMap;
+
+use crate::lazy::plan::{join_names, JoinType, LogicalPlan};
+use crate::lazy::{BinaryOp, Expr};
+
+pub(crate) fn optimize(plan: LogicalPlan) -> LogicalPlan {
+    push_projection(push_predicates(plan), None)
+}
+
+fn push_predicates(plan: LogicalPlan) -> LogicalPlan {
+    match plan {
+        LogicalPlan::Filter { input, predicate } => push_filter(push_predicates(*input), predicate),
+        LogicalPlan::Select { input, exprs } => LogicalPlan::Select {
+            input: Box::new(push_predicates(*input)),
+            exprs,
+        },
+        LogicalPlan::WithColumns { input, exprs } => LogicalPlan::WithColumns {
+            input: Box::new(push_predicates(*input)),
+            exprs,
+        },
+        LogicalPlan::GroupBy { input, keys, aggs } => LogicalPlan::GroupBy {
+            input: Box::new(push_predicates(*input)),
+            keys,
+            aggs,
+        },
+        LogicalPlan::Sort {
+            input,
+            by,
+            descending,
+        } => LogicalPlan::Sort {
+            input: Box::new(push_predicates(*input)),
+            by,
+            descending,
+        },
+        LogicalPlan::Slice { input, offset, len } => LogicalPlan::Slice {
+            input: Box::new(push_predicates(*input)),
+            offset,
+            len,
+        },
+        LogicalPlan::Join {
+            left,
+            right,
+            left_on,
+            right_on,
+            how,
+        } => LogicalPlan::Join {
+            left: Box::new(push_predicates(*left)),
+            right: Box::new(push_predicates(*right)),
+            left_on,
+            right_on,
+            how,
+        },
+        source => source,
+    }
+}
+
+/// Moves `predicate` as far down as it keeps its meaning: below nodes that pass
+/// the referenced columns through (possibly renamed) and don't aggregate over
+/// rows. The parts of an `AND` move independently.
+///
+/// A predicate with an aggregate, like `a > mean(a)`, stays where it is: the
+/// aggregate must see exactly the rows of the filter's input.
+fn push_filter(plan: LogicalPlan, predicate: Expr) -> LogicalPlan {
+    if predicate.is_aggregate() {
+        return filter(plan, predicate);
+    }
+    if let Expr::Binary {
+        left,
+        op: BinaryOp::And,
+        right,
+    } = predicate
+    {
+        return push_filter(push_filter(plan, *left), *right);
+    }
+    let columns = predicate.referenced_columns();
+
+    match plan {
+        LogicalPlan::Scan {
+            path,
+            options,
+            projection,
+            predicate: existing,
+        } => LogicalPlan::Scan {
+            path,
+            options,
+            projection,
+            predicate: Some(combine(existing, predicate)),
+        },
+        LogicalPlan::Frame {
+            df,
+            projection,
+            predicate: existing,
+        } => LogicalPlan::Frame {
+            df,
+            projection,
+            predicate: Some(combine(existing, predicate)),
+        },
+        LogicalPlan::Filter {
+            input,
+            predicate: inner,
+        } if !inner.is_aggregate() => LogicalPlan::Filter {
+            input: Box::new(push_filter(*input, predicate)),
+            predicate: inner,
+        },
+        LogicalPlan::Sort {
+            input,
+            by,
+            descending,
+        } => LogicalPlan::Sort {
+            input: Box::new(push_filter(*input, predicate)),
+            by,
+            descending,
+        },
+        LogicalPlan::Select { input, exprs } if !exprs.iter().any(Expr::is_aggregate) => {
+            match sources(&exprs, &columns) {
+                Some(renames) => LogicalPlan::Select {
+                    input: Box::new(push_filter(*input, rename(&predicate, &renames))),
+                    exprs,
+                },
+                None => filter(LogicalPlan::Select { input, exprs }, predicate),
+            }
+        }
+        LogicalPlan::WithColumns { input, exprs }
+            if !exprs
+                .iter()
+                .any(|expr| expr.is_aggregate() || columns.contains(&expr.output_name())) =>
+        {
+            LogicalPlan::WithColumns {
+                input: Box::new(push_filter(*input, predicate)),
+                exprs,
+            }
+        }
+        LogicalPlan::GroupBy { input, keys, aggs } => match sources(&keys, &columns) {
+            Some(renames) => LogicalPlan::GroupBy {
+                input: Box::new(push_filter(*input, rename(&predicate, &renames))),
+                keys,
+                aggs,
+            },
+            None => filter(LogicalPlan::GroupBy { input, keys, aggs }, predicate),
+        },
+        LogicalPlan::Join {
+            left,
+            right,
+            left_on,
+            right_on,
+            how,
+        } => {
+            let (mut left, mut right) = (left, right);
+            let mut residual = None;
+            match left.schema().zip(right.schema()) {
+                Some((left_schema, _)) if covers(&left_schema, &columns) && keeps_left(how) => {
+                    left = Box::new(push_filter(*left, predicate));
+                }
+                Some((left_schema, right_schema)) => {
+                    let right_output = join_names(&left_schema, &right_schema);
+                    if covers(&right_output, &columns) && keeps_right(how) {
+                        let renames: HashMap<String, String> =
+                            right_output.into_iter().zip(right_schema).collect();
+                        right = Box::new(push_filter(*right, rename(&predicate, &renames)));
+                    } else {
+                        residual = Some(predicate);
+                    }
+                }
+                None => residual = Some(predicate),
+            }
+            let join = LogicalPlan::Join {
+                left,
+                right,
+                left_on,
+                right_on,
+                how,
+            };
+            match residual {
+                Some(predicate) => filter(join, predicate),
+                None => join,
+            }
+        }
+        plan => filter(plan, predicate),
+    }
+}
+
+/// Filtering a side before the join is only equivalent when that side's
+/// unmatched rows are not padded back in with nulls.
+fn keeps_left(how: JoinType) -> bool {
+    matches!(how, JoinType::Inner | JoinType::Left | JoinType::Cross)
+}
+
+fn keeps_right(how: JoinType) -> bool {
+    matches!(how, JoinType::Inner | JoinType::Right | JoinType::Cross)
+}
+
+fn covers(schema: &[String], columns: &[String]) -> bool {
+    columns.iter().all(|name| schema.contains(name))
+}
+
+fn filter(plan: LogicalPlan, predicate: Expr) -> LogicalPlan {
+    LogicalPlan::Filter {
+        input: Box::new(plan),
+        predicate,
+    }
+}
+
+/// For each of `columns`, the input column an output of `exprs` copies it
+/// from; `None` if any of them is computed.
+fn sources(exprs: &[Expr], columns: &[String]) -> Option<HashMap<String, String>> {
+    columns
+        .iter()
+        .map(|name| {
+            let source = exprs.iter().find_map(|expr| match expr {
+                Expr::Column(source) if source == name => Some(source.clone()),
+                Expr::Alias(inner, alias) if alias == name => match inner.as_ref() {
+                    Expr::Column(source) => Some(source.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })?;
+            Some((name.clone(), source))
+        })
+        .collect()
+}
+
+fn rename(predicate: &Expr, renames: &HashMap<String, String>) -> Expr {
+    predicate.rewrite(&mut |expr| match expr {
+        Expr::Column(name) => renames.get(name).map(|source| Expr::Column(source.clone())),
+        _ => None,
+    })
+}
+
+fn combine(existing: Option<Expr>, predicate: Expr) -> Expr {
+    match existing {
+        Some(existing) => existing.and(predicate),
+        None => predicate,
+    }
+}
+
+/// Tells each source which columns its consumers read; `None` means all of them.
+fn push_projection(plan: LogicalPlan, required: Option<Vec<String>>) -> LogicalPlan {
+    match plan {
+        LogicalPlan::Scan {
+            path,
+            options,
+            predicate,
+            ..
+        } => LogicalPlan::Scan {
+            path,
+            options,
+            projection: source_projection(required, &predicate),
+            predicate,
+        },
+        LogicalPlan::Frame { df, predicate, .. } => LogicalPlan::Frame {
+            df,
+            projection: source_projection(required, &predicate),
+            predicate,
+        },
+        LogicalPlan::Filter { input, predicate } => {
+            let required = required.map(|mut names| {
+                extend(&mut names, predicate.referenced_columns());
+                names
+            });
+            LogicalPlan::Filter {
+                input: Box::new(push_projection(*input, required)),
+                predicate,
+            }
+        }
+        LogicalPlan::Select { input, exprs } => {
+            // Outputs nobody reads are dropped, keeping one so the row count survives.
+            let exprs = match required {
+                Some(names) => {
+                    let kept: Vec<Expr> = exprs
+                        .iter()
+                        .filter(|expr| names.contains(&expr.output_name()))
+                        .cloned()
+                        .collect();
+                    if kept.is_empty() {
+                        exprs.into_iter().take(1).collect()
+                    } else {
+                        kept
+                    }
+                }
+                None => exprs,
+            };
+            LogicalPlan::Select {
+                input: Box::new(push_projection(*input, Some(referenced(&exprs)))),
+                exprs,
+            }
+        }
+        LogicalPlan::WithColumns { input, exprs } => {
+            let required = required.map(|names| {
+                let produced: Vec<String> = exprs.iter().map(Expr::output_name).collect();
+                let mut names: Vec<String> = names
+                    .into_iter()
+                    .filter(|name| !produced.contains(name))
+                    .collect();
+                extend(&mut names, referenced(&exprs));
+                names
+            });
+            LogicalPlan::WithColumns {
+                input: Box::new(push_projection(*input, required)),
+                exprs,
+            }
+        }
+        LogicalPlan::GroupBy { input, keys, aggs } => {
+            let mut names = referenced(&keys);
+            extend(&mut names, referenced(&aggs));
+            LogicalPlan::GroupBy {
+                input: Box::new(push_projection(*input, Some(names))),
+                keys,
+                aggs,
+            }
+        }
+        LogicalPlan::Sort {
+            input,
+            by,
+            descending,
+        } => {
+            let required = required.map(|mut names| {
+                extend(&mut names, referenced(&by));
+                names
+            });
+            LogicalPlan::Sort {
+                input: Box::new(push_projection(*input, required)),
+                by,
+                descending,
+            }
+        }
+        LogicalPlan::Slice { input, offset, len } => LogicalPlan::Slice {
+            input: Box::new(push_projection(*input, required)),
+            offset,
+            len,
+        },
+        LogicalPlan::Join {
+            left,
+            right,
+            left_on,
+            right_on,
+            how,
+        } => {
+            let (left_required, right_required) = match (required, left.schema(), right.schema()) {
+                (Some(names), Some(left_schema), Some(right_schema)) => {
+                    let right_output = join_names(&left_schema, &right_schema);
+                    let mut left_names: Vec<String> = names
+                        .iter()
+                        .filter(|name| left_schema.contains(name))
+                        .cloned()
+                        .collect();
+                    extend(&mut left_names, referenced(&left_on));
+                    let mut right_names: Vec<String> = right_output
+                        .iter()
+                        .zip(&right_schema)
+                        .filter(|(output, _)| names.contains(output))
+                        .map(|(_, source)| source.clone())
+                        .collect();
+                    extend(&mut right_names, referenced(&right_on));
+                    (Some(left_names), Some(right_names))
+                }
+                _ => (None, None),
+            };
+            LogicalPlan::Join {
+                left: Box::new(push_projection(*left, left_required)),
+                right: Box::new(push_projection(*right, right_required)),
+                left_on,
+                right_on,
+                how,
+            }
+        }
+    }
+}
+
+/// The source must also read the columns of its own predicate. An empty set
+/// (e.g. only `len()` is needed) still reads every column, to keep the row count.
+fn source_projection(
+    required: Option<Vec<String>>,
+    predicate: &Option<Expr>,
+) -> Option<Vec<String>> {
+    let mut names = required?;
+    if let Some(predicate) = predicate {
+        extend(&mut names, predicate.referenced_columns());
+    }
+    (!names.is_empty()).then_some(names)
+}
+
+fn referenced(exprs: &[Expr]) -> Vec<String> {
+    let mut names = Vec::new();
+    for expr in exprs {
+        extend(&mut names, expr.referenced_columns());
+    }
+    names
+}
+
+fn extend(names: &mut Vec<String>, more: Vec<String>) {
+    for name in more {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+}
@@ -0,0 +1,565 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::frame::DataFrameError;
+use crate::io::{read_csv, CsvReadOptions};
+use crate::lazy::expr::compare_cells;
+use crate::lazy::Expr;
+use crate::scalar::{CellValue, ValueKey};
+use crate::series::ColumnArray;
+use crate::DataFrame;
+
+/// How `LazyFrame::join` pairs rows of the two inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinType {
+    /// Only rows with a match on both sides.
+    Inner,
+    /// Every left row, with nulls where the right side has no match.
+    Left,
+    /// Every right row, with nulls where the left side has no match.
+    Right,
+    /// Every row of both sides.
+    Full,
+    /// Every combination of a left and a right row; takes no keys.
+    Cross,
+}
+
+impl fmt::Display for JoinType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JoinType::Inner => write!(f, "INNER"),
+            JoinType::Left => write!(f, "LEFT"),
+            JoinType::Right => write!(f, "RIGHT"),
+            JoinType::Full => write!(f, "FULL"),
+            JoinType::Cross => write!(f, "CROSS"),
+        }
+    }
+}
+
+/// A tree of operations, executed bottom-up by `LazyFrame::collect`.
+#[derive(Debug, Clone)]
+pub(crate) enum LogicalPlan {
+    /// Reads a CSV file, keeping only `projection`, then drops the rows not
+    /// matching `predicate`.
+    ///
+    /// The predicate runs on the parsed frame rather than inside the reader:
+    /// column types are inferred from every row, so rows cannot be rejected
+    /// before the whole file is parsed.
+    Scan {
+        path: String,
+        options: CsvReadOptions,
+        projection: Option<Vec<String>>,
+        predicate: Option<Expr>,
+    },
+    /// An in-memory frame, with the same pushed-down projection and predicate as a scan.
+    Frame {
+        df: Arc<DataFrame>,
+        projection: Option<Vec<String>>,
+        predicate: Option<Expr>,
+    },
+    Filter {
+        input: Box<LogicalPlan>,
+        predicate: Expr,
+    },
+    Select {
+        input: Box<LogicalPlan>,
+        exprs: Vec<Expr>,
+    },
+    WithColumns {
+        input: Box<LogicalPlan>,
+        exprs: Vec<Expr>,
+    },
+    GroupBy {
+        input: Box<LogicalPlan>,
+        keys: Vec<Expr>,
+        aggs: Vec<Expr>,
+    },
+    /// Stable sort; nulls go last in either direction.
+    Sort {
+        input: Box<LogicalPlan>,
+        by: Vec<Expr>,
+        descending: Vec<bool>,
+    },
+    Slice {
+        input: Box<LogicalPlan>,
+        offset: usize,
+        len: Option<usize>,
+    },
+    /// Right-side columns whose names clash with the left get a `_right` suffix.
+    Join {
+        left: Box<LogicalPlan>,
+        right: Box<LogicalPlan>,
+        left_on: Vec<Expr>,
+        right_on: Vec<Expr>,
+        how: JoinType,
+    },
+}
+
+impl LogicalPlan {
+    pub(crate) fn execute(self) -> Result<DataFrame, DataFrameError> {
+        match self {
+            LogicalPlan::Scan {
+                path,
+                mut options,
+                projection,
+                predicate,
+            } => {
+                if projection.is_some() {
+                    options.columns = projection;
+                }
+                let (df, _) = read_csv(&path, &options)?;
+                apply_predicate(df, predicate.as_ref())
+            }
+            LogicalPlan::Frame {
+                df,
+                projection,
+                predicate,
+            } => {
+                let df = match projection {
+                    Some(names) => df.select(&names)?,
+                    None => Arc::unwrap_or_clone(df),
+                };
+                apply_predicate(df, predicate.as_ref())
+            }
+            LogicalPlan::Filter { input, predicate } => {
+                apply_predicate(input.execute()?, Some(&predicate))
+            }
+            LogicalPlan::Select { input, exprs } => select(&input.execute()?, &exprs),
+            LogicalPlan::WithColumns { input, exprs } => with_columns(input.execute()?, &exprs),
+            LogicalPlan::GroupBy { input, keys, aggs } => group_by(&input.execute()?, &keys, &aggs),
+            LogicalPlan::Sort {
+                input,
+                by,
+                descending,
+            } => sort(&input.execute()?, &by, &descending),
+            LogicalPlan::Slice { input, offset, len } => {
+                let df = input.execute()?;
+                let rows = df.shape().0;
+                let start = offset.min(rows);
+                let end = len.map_or(rows, |len| start.saturating_add(len).min(rows));
+                let indices: Vec<usize> = (start..end).collect();
+                Ok(df.take(&indices))
+            }
+            LogicalPlan::Join {
+                left,
+                right,
+                left_on,
+                right_on,
+                how,
+            } => join(
+                &left.execute()?,
+                &right.execute()?,
+                &left_on,
+                &right_on,
+                how,
+            ),
+        }
+    }
+
+    /// Output column names, when they are known without reading any file.
+    pub(crate) fn schema(&self) -> Option<Vec<String>> {
+        match self {
+            LogicalPlan::Scan {
+                options,
+                projection,
+                ..
+            } => projection.clone().or_else(|| options.columns.clone()),
+            LogicalPlan::Frame { df, projection, .. } => {
+                Some(projection.clone().unwrap_or_else(|| df.headers().to_vec()))
+            }
+            LogicalPlan::Filter { input, .. }
+            | LogicalPlan::Sort { input, .. }
+            | LogicalPlan::Slice { input, .. } => input.schema(),
+            LogicalPlan::Select { exprs, .. } => {
+                Some(exprs.iter().map(Expr::output_name).collect())
+            }
+            LogicalPlan::WithColumns { input, exprs } => {
+                let mut names = input.schema()?;
+                for name in exprs.iter().map(Expr::output_name) {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+                Some(names)
+            }
+            LogicalPlan::GroupBy { keys, aggs, .. } => {
+                Some(keys.iter().chain(aggs).map(Expr::output_name).collect())
+            }
+            LogicalPlan::Join { left, right, .. } => {
+                let left = left.schema()?;
+                let right = join_names(&left, &right.schema()?);
+                Some(left.into_iter().chain(right).collect())
+            }
+        }
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        let pad = "  ".repeat(indent);
+        match self {
+            LogicalPlan::Scan {
+                path,
+                projection,
+                predicate,
+                ..
+            } => {
+                writeln!(f, "{}CSV SCAN {}", pad, path)?;
+                fmt_source(f, &pad, projection, predicate)
+            }
+            LogicalPlan::Frame {
+                df,
+                projection,
+                predicate,
+            } => {
+                writeln!(f, "{}DF [{}]", pad, df.headers().join(", "))?;
+                fmt_source(f, &pad, projection, predicate)
+            }
+            LogicalPlan::Filter { input, predicate } => {
+                writeln!(f, "{}FILTER {}", pad, predicate)?;
+                input.fmt_indented(f, indent + 1)
+            }
+            LogicalPlan::Select { input, exprs } => {
+                writeln!(f, "{}SELECT [{}]", pad, list(exprs))?;
+                input.fmt_indented(f, indent + 1)
+            }
+            LogicalPlan::WithColumns { input, exprs } => {
+                writeln!(f, "{}WITH COLUMNS [{}]", pad, list(exprs))?;
+                input.fmt_indented(f, indent + 1)
+            }
+            LogicalPlan::GroupBy { input, keys, aggs } => {
+                writeln!(f, "{}AGGREGATE [{}] BY [{}]", pad, list(aggs), list(keys))?;
+                input.fmt_indented(f, indent + 1)
+            }
+            LogicalPlan::Sort {
+                input,
+                by,
+                descending,
+            } => {
+                let keys: Vec<String> = by
+                    .iter()
+                    .enumerate()
+                    .map(|(i, expr)| match descending.get(i) {
+                        Some(true) => format!("{} DESC", expr),
+                        _ => expr.to_string(),
+                    })
+                    .collect();
+                writeln!(f, "{}SORT BY [{}]", pad, keys.join(", "))?;
+                input.fmt_indented(f, indent + 1)
+            }
+            LogicalPlan::Slice { input, offset, len } => {
+                match len {
+                    Some(len) => writeln!(f, "{}SLICE offset={} len={}", pad, offset, len)?,
+                    None => writeln!(f, "{}SLICE offset={}", pad, offset)?,
+                }
+                input.fmt_indented(f, indent + 1)
+            }
+            LogicalPlan::Join {
+                left,
+                right,
+                left_on,
+                right_on,
+                how,
+            } => {
+                if *how == JoinType::Cross {
+                    writeln!(f, "{}CROSS JOIN", pad)?;
+                } else {
+                    writeln!(
+                        f,
+                        "{}{} JOIN ON [{}] = [{}]",
+                        pad,
+                        how,
+                        list(left_on),
+                        list(right_on)
+                    )?;
+                }
+                left.fmt_indented(f, indent + 1)?;
+                right.fmt_indented(f, indent + 1)
+            }
+        }
+    }
+}
+
+impl fmt::Display for LogicalPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+fn fmt_source(
+    f: &mut fmt::Formatter<'_>,
+    pad: &str,
+    projection: &Option<Vec<String>>,
+    predicate: &Option<Expr>,
+) -> fmt::Result {
+    match projection {
+        Some(names) => writeln!(f, "{}  PROJECT [{}]", pad, names.join(", "))?,
+        None => writeln!(f, "{}  PROJECT *", pad)?,
+    }
+    if let Some(predicate) = predicate {
+        writeln!(f, "{}  SELECTION {}", pad, predicate)?;
+    }
+    Ok(())
+}
+
+fn list(exprs: &[Expr]) -> String {
+    exprs
+        .iter()
+        .map(|expr| expr.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn apply_predicate(df: DataFrame, predicate: Option<&Expr>) -> Result<DataFrame, DataFrameError> {
+    match predicate {
+        Some(predicate) => {
+            let mask = fit(predicate.evaluate(&df)?, df.shape().0);
+            df.filter(mask.as_ref())
+        }
+        None => Ok(df),
+    }
+}
+
+/// Repeats a one-row result (a literal or an aggregate) to `rows` rows.
+fn fit(column: Box<dyn ColumnArray>, rows: usize) -> Box<dyn ColumnArray> {
+    if column.len() == 1 && rows != 1 {
+        column.take(&vec![0; rows])
+    } else {
+        column
+    }
+}
+
+fn select(df: &DataFrame, exprs: &[Expr]) -> Result<DataFrame, DataFrameError> {
+    let columns = exprs
+        .iter()
+        .map(|expr| expr.evaluate(df))
+        .collect::<Result<Vec<_>, _>>()?;
+    let rows = columns.iter().map(|column| column.len()).max().unwrap_or(0);
+
+    let mut headers: Vec<String> = Vec::with_capacity(exprs.len());
+    for expr in exprs {
+        let name = expr.output_name();
+        if headers.contains(&name) {
+            return Err(DataFrameError::InvalidArgument(format!(
+                "duplicate column name '{}' in select; use alias() to rename",
+                name
+            )));
+        }
+        headers.push(name);
+    }
+    let columns = columns
+        .into_iter()
+        .map(|column| fit(column, rows))
+        .collect();
+    DataFrame::from_columns(Some(headers), columns)
+}
+
+fn with_columns(df: DataFrame, exprs: &[Expr]) -> Result<DataFrame, DataFrameError> {
+    let rows = df.shape().0;
+    let columns = exprs
+        .iter()
+        .map(|expr| expr.evaluate(&df))
+        .collect::<Result<Vec<_>, _>>()?;
+    exprs
+        .iter()
+        .zip(columns)
+        .try_fold(df, |df, (expr, column)| {
+            df.with_column(&expr.output_name(), fit(column, rows))
+        })
+}
+
+/// Groups rows by the key values (in order of first appearance) and evaluates
+/// each aggregation once per group.
+fn group_by(df: &DataFrame, keys: &[Expr], aggs: &[Expr]) -> Result<DataFrame, DataFrameError> {
+    let rows = df.shape().0;
+    let key_columns = keys
+        .iter()
+        .map(|key| key.evaluate(df).map(|column| fit(column, rows)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut positions: HashMap<Vec<ValueKey>, usize> = HashMap::new();
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    if keys.is_empty() {
+        // A global aggregation yields one row even for an empty input.
+        groups.push((0..rows).collect());
+    }
+    for row in (0..rows).filter(|_| !keys.is_empty()) {
+        let key: Vec<ValueKey> = key_columns
+            .iter()
+            .map(|column| ValueKey::from(column.get(row)))
+            .collect();
+        let position = *positions.entry(key).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[position].push(row);
+    }
+
+    let firsts: Vec<usize> = groups
+        .iter()
+        .map(|group| group.first().copied().unwrap_or(usize::MAX))
+        .collect();
+    let mut headers: Vec<String> = keys.iter().map(|key| key.output_name()).collect();
+    let mut columns: Vec<Box<dyn ColumnArray>> = key_columns
+        .iter()
+        .map(|column| column.take(&firsts))
+        .collect();
+
+    for agg in aggs {
+        // Only the referenced columns are gathered per group; `len()` needs at least one.
+        let mut names = agg.referenced_columns();
+        if names.is_empty() {
+            names.extend(df.headers().first().cloned());
+        }
+        let source = df.select(&names)?;
+        let values = groups
+            .iter()
+            .map(|group| {
+                let result = agg.evaluate(&source.take(group))?;
+                if result.len() != 1 {
+                    return Err(DataFrameError::InvalidArgument(format!(
+                        "'{}' must produce one value per group",
+                        agg
+                    )));
+                }
+                Ok(result.get(0))
+            })
+            .collect::<Result<Vec<Option<CellValue>>, _>>()?;
+        headers.push(agg.output_name());
+        columns.push(values.into());
+    }
+    DataFrame::from_columns(Some(headers), columns)
+}
+
+fn sort(df: &DataFrame, by: &[Expr], descending: &[bool]) -> Result<DataFrame, DataFrameError> {
+    let rows = df.shape().0;
+    let keys = by
+        .iter()
+        .map(|expr| expr.evaluate(df).map(|column| fit(column, rows)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut indices: Vec<usize> = (0..rows).collect();
+    indices.sort_by(|&a, &b| {
+        for (i, key) in keys.iter().enumerate() {
+            let ordering = match (key.get(a), key.get(b)) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(x), Some(y)) => {
+                    let ordering = compare_cells(&x, &y).unwrap_or(Ordering::Equal);
+                    if descending.get(i).copied().unwrap_or(false) {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                }
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    });
+    Ok(df.take(&indices))
+}
+
+/// Names for the right-side columns of a join, suffixing those the left side already uses.
+pub(crate) fn join_names(left: &[String], right: &[String]) -> Vec<String> {
+    let mut taken: Vec<String> = left.to_vec();
+    right
+        .iter()
+        .map(|name| {
+            let mut unique = name.clone();
+            while taken.contains(&unique) {
+                unique.push_str("_right");
+            }
+            taken.push(unique.clone());
+            unique
+        })
+        .collect()
+}
+
+fn join(
+    left: &DataFrame,
+    right: &DataFrame,
+    left_on: &[Expr],
+    right_on: &[Expr],
+    how: JoinType,
+) -> Result<DataFrame, DataFrameError> {
+    if left_on.len() != right_on.len() || (how != JoinType::Cross && left_on.is_empty()) {
+        return Err(DataFrameError::InvalidArgument(format!(
+            "{} join needs the same number of keys on both sides, got {} and {}",
+            how,
+            left_on.len(),
+            right_on.len()
+        )));
+    }
+    let (left_rows, right_rows) = (left.shape().0, right.shape().0);
+
+    // Row pairs of the result; `usize::MAX` stands for a missing row, which `take` fills with nulls.
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    if how == JoinType::Cross {
+        for l in 0..left_rows {
+            pairs.extend((0..right_rows).map(|r| (l, r)));
+        }
+    } else {
+        let key_columns = |df: &DataFrame, exprs: &[Expr]| {
+            let rows = df.shape().0;
+            exprs
+                .iter()
+                .map(|expr| expr.evaluate(df).map(|column| fit(column, rows)))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        // Null keys never match, as in SQL.
+        let key_at = |columns: &[Box<dyn ColumnArray>], row: usize| {
+            columns
+                .iter()
+                .map(|column| column.get(row).map(|value| ValueKey::from(Some(value))))
+                .collect::<Option<Vec<ValueKey>>>()
+        };
+        let left_keys = key_columns(left, left_on)?;
+        let right_keys = key_columns(right, right_on)?;
+
+        let mut index: HashMap<Vec<ValueKey>, Vec<usize>> = HashMap::new();
+        for row in 0..right_rows {
+            if let Some(key) = key_at(&right_keys, row) {
+                index.entry(key).or_default().push(row);
+            }
+        }
+        let mut right_matched = vec![false; right_rows];
+        for l in 0..left_rows {
+            match key_at(&left_keys, l).and_then(|key| index.get(&key)) {
+                Some(matches) => {
+                    for &r in matches {
+                        right_matched[r] = true;
+                        pairs.push((l, r));
+                    }
+                }
+                None if matches!(how, JoinType::Left | JoinType::Full) => {
+                    pairs.push((l, usize::MAX))
+                }
+                None => {}
+            }
+        }
+        if matches!(how, JoinType::Right | JoinType::Full) {
+            pairs.extend(
+                (0..right_rows)
+                    .filter(|&r| !right_matched[r])
+                    .map(|r| (usize::MAX, r)),
+            );
+        }
+    }
+
+    let (left_indices, right_indices): (Vec<usize>, Vec<usize>) = pairs.into_iter().unzip();
+    let left_part = left.take(&left_indices);
+    let right_part = right.take(&right_indices);
+    let mut headers = left_part.headers().to_vec();
+    headers.extend(join_names(left_part.headers(), right_part.headers()));
+    let columns = left_part
+        .columns()
+        .iter()
+        .chain(right_part.columns())
+        .cloned()
+        .collect();
+    DataFrame::from_columns(Some(headers), columns)
+}
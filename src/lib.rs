@@ -25,16 +25,26 @@
 
 pub mod config;
 pub mod frame;
+pub mod io;
+pub mod lazy;
+pub mod output;
 pub mod reporter;
 pub mod scalar;
 pub mod series;
+pub mod shell;
+pub mod sql;
+pub mod stats;
 pub mod types;
 
 // Core data structures
-pub use frame::DataFrame;
+pub use frame::{DataFrame, DisplayOptions};
+pub use io::{CsvReadOptions, Encoding};
+pub use lazy::{col, len, lit, Expr, LazyFrame};
+pub use output::OutputFormat;
 pub use scalar::CellValue;
 pub use series::ColumnArray;
+pub use sql::SqlContext;
 pub use types::{CsvError, Dtype};
 
 // CLI-specific exports (optional for library users)
-pub use config::{parse_command, parse_config, Command, Config, ConfigError};
+pub use config::{cli, parse_command, parse_config, Command, Config, ConfigError};
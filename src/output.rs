@@ -0,0 +1,321 @@
+//! How command results are written: the `--format` of a frame and where it goes.
+//!
+//! Each format is a [`FrameWriter`]; [`OutputFormat::writer`] picks the one
+//! for a format and [`DataFrame::render`] renders a frame to a string.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::frame::DataFrameError;
+use crate::io::write_csv;
+use crate::{DataFrame, DisplayOptions};
+
+/// Serialization of a result frame, chosen with `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// The boxed table of `DataFrame`'s `Display`.
+    #[default]
+    Table,
+    Csv,
+    Tsv,
+    /// `{"headers": [...], "columns": [[...], ...]}`, as `DataFrame::to_json`.
+    Json,
+    /// One JSON object per row.
+    Ndjson,
+    /// A GitHub-flavored Markdown table.
+    Markdown,
+    Html,
+    /// A LaTeX `tabular` environment.
+    Latex,
+}
+
+const FORMAT_NAMES: [(&str, OutputFormat); 8] = [
+    ("table", OutputFormat::Table),
+    ("csv", OutputFormat::Csv),
+    ("tsv", OutputFormat::Tsv),
+    ("json", OutputFormat::Json),
+    ("ndjson", OutputFormat::Ndjson),
+    ("markdown", OutputFormat::Markdown),
+    ("html", OutputFormat::Html),
+    ("latex", OutputFormat::Latex),
+];
+
+impl OutputFormat {
+    /// The writer that produces this format.
+    pub fn writer(self) -> Box<dyn FrameWriter> {
+        match self {
+            OutputFormat::Table => Box::new(TableWriter::default()),
+            OutputFormat::Csv => Box::new(DelimitedWriter { delimiter: b',' }),
+            OutputFormat::Tsv => Box::new(DelimitedWriter { delimiter: b'\t' }),
+            OutputFormat::Json => Box::new(JsonWriter),
+            OutputFormat::Ndjson => Box::new(NdjsonWriter),
+            OutputFormat::Markdown => Box::new(MarkdownWriter),
+            OutputFormat::Html => Box::new(HtmlWriter),
+            OutputFormat::Latex => Box::new(LatexWriter),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, _) = FORMAT_NAMES
+            .iter()
+            .find(|(_, format)| format == self)
+            .expect("every format has a name");
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_lowercase();
+        let lower = match lower.as_str() {
+            "md" => "markdown",
+            "tex" => "latex",
+            other => other,
+        };
+        FORMAT_NAMES
+            .iter()
+            .find(|(name, _)| *name == lower)
+            .map(|&(_, format)| format)
+            .ok_or_else(|| {
+                let names: Vec<&str> = FORMAT_NAMES.iter().map(|(name, _)| *name).collect();
+                format!(
+                    "unknown format '{}', expected one of {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// Renders a whole frame in one output format.
+///
+/// Implement it to add a format of your own; the built-in ones are returned
+/// by [`OutputFormat::writer`].
+pub trait FrameWriter {
+    fn write_frame(&self, df: &DataFrame, writer: &mut dyn Write) -> Result<(), DataFrameError>;
+}
+
+/// The box-drawing table of `DataFrame`'s `Display`, laid out by `options`.
+#[derive(Debug, Clone, Default)]
+pub struct TableWriter {
+    pub options: DisplayOptions,
+}
+
+impl FrameWriter for TableWriter {
+    fn write_frame(&self, df: &DataFrame, writer: &mut dyn Write) -> Result<(), DataFrameError> {
+        writeln!(writer, "{}", df.display(&self.options)).map_err(io_error)
+    }
+}
+
+/// CSV with a header row and the given field separator.
+#[derive(Debug, Clone, Copy)]
+pub struct DelimitedWriter {
+    pub delimiter: u8,
+}
+
+impl FrameWriter for DelimitedWriter {
+    fn write_frame(&self, df: &DataFrame, writer: &mut dyn Write) -> Result<(), DataFrameError> {
+        write_csv(df, writer, self.delimiter)
+    }
+}
+
+/// The column-oriented document of `DataFrame::to_json`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonWriter;
+
+impl FrameWriter for JsonWriter {
+    fn write_frame(&self, df: &DataFrame, writer: &mut dyn Write) -> Result<(), DataFrameError> {
+        writeln!(writer, "{}", df.to_json()?).map_err(io_error)
+    }
+}
+
+/// Newline-delimited JSON: one object keyed by column name per row.
+///
+/// Keys follow the column order; a repeated column name gets a `_2`, `_3`,
+/// ... suffix so that no value is lost.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NdjsonWriter;
+
+impl FrameWriter for NdjsonWriter {
+    fn write_frame(&self, df: &DataFrame, writer: &mut dyn Write) -> Result<(), DataFrameError> {
+        // written by hand: a `serde_json::Map` would sort the keys
+        let keys: Vec<String> = unique_names(df.headers())
+            .into_iter()
+            .map(|name| Value::String(name).to_string())
+            .collect();
+        let columns: Vec<Vec<Value>> = df.columns().iter().map(|c| c.to_json()).collect();
+        for row in 0..df.shape().0 {
+            let fields: Vec<String> = keys
+                .iter()
+                .zip(&columns)
+                .map(|(key, values)| format!("{}:{}", key, values[row]))
+                .collect();
+            writeln!(writer, "{{{}}}", fields.join(",")).map_err(io_error)?;
+        }
+        Ok(())
+    }
+}
+
+/// `names` with each repeat suffixed by its occurrence number, skipping
+/// suffixed names that are already taken.
+fn unique_names(names: &[String]) -> Vec<String> {
+    let mut taken: HashSet<String> = HashSet::new();
+    names
+        .iter()
+        .map(|name| {
+            let mut unique = name.clone();
+            let mut occurrence = 1;
+            while taken.contains(&unique) {
+                occurrence += 1;
+                unique = format!("{}_{}", name, occurrence);
+            }
+            taken.insert(unique.clone());
+            unique
+        })
+        .collect()
+}
+
+/// A GitHub-flavored Markdown table; numeric columns are right-aligned.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarkdownWriter;
+
+impl FrameWriter for MarkdownWriter {
+    fn write_frame(&self, df: &DataFrame, writer: &mut dyn Write) -> Result<(), DataFrameError> {
+        let escape = |text: &str| text.replace('|', "\\|").replace('\n', " ");
+        let headers: Vec<String> = df.headers().iter().map(|name| escape(name)).collect();
+        let rules: Vec<&str> = numeric_columns(df)
+            .into_iter()
+            .map(|numeric| if numeric { "---:" } else { "---" })
+            .collect();
+        writeln!(writer, "| {} |", headers.join(" | ")).map_err(io_error)?;
+        writeln!(writer, "| {} |", rules.join(" | ")).map_err(io_error)?;
+        for row in cells(df) {
+            let row: Vec<String> = row.iter().map(|cell| escape(cell)).collect();
+            writeln!(writer, "| {} |", row.join(" | ")).map_err(io_error)?;
+        }
+        Ok(())
+    }
+}
+
+/// An HTML `<table>` with a `<thead>` and escaped cells.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HtmlWriter;
+
+impl FrameWriter for HtmlWriter {
+    fn write_frame(&self, df: &DataFrame, writer: &mut dyn Write) -> Result<(), DataFrameError> {
+        let mut html = String::from("<table>\n  <thead>\n    <tr>");
+        for name in df.headers() {
+            html.push_str(&format!("<th>{}</th>", escape_html(name)));
+        }
+        html.push_str("</tr>\n  </thead>\n  <tbody>\n");
+        let numeric = numeric_columns(df);
+        for row in cells(df) {
+            html.push_str("    <tr>");
+            for (cell, &numeric) in row.iter().zip(&numeric) {
+                if numeric {
+                    html.push_str(&format!("<td align=\"right\">{}</td>", escape_html(cell)));
+                } else {
+                    html.push_str(&format!("<td>{}</td>", escape_html(cell)));
+                }
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("  </tbody>\n</table>");
+        writeln!(writer, "{}", html).map_err(io_error)
+    }
+}
+
+/// A LaTeX `tabular`; numeric columns are right-aligned.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LatexWriter;
+
+impl FrameWriter for LatexWriter {
+    fn write_frame(&self, df: &DataFrame, writer: &mut dyn Write) -> Result<(), DataFrameError> {
+        let spec: String = numeric_columns(df)
+            .into_iter()
+            .map(|numeric| if numeric { 'r' } else { 'l' })
+            .collect();
+        let line = |cells: Vec<String>| format!("{} \\\\", cells.join(" & "));
+        let headers = df.headers().iter().map(|name| escape_latex(name)).collect();
+        let mut latex = format!("\\begin{{tabular}}{{{}}}\n\\hline\n", spec);
+        latex.push_str(&line(headers));
+        latex.push_str("\n\\hline\n");
+        for row in cells(df) {
+            latex.push_str(&line(row.iter().map(|cell| escape_latex(cell)).collect()));
+            latex.push('\n');
+        }
+        latex.push_str("\\hline\n\\end{tabular}");
+        writeln!(writer, "{}", latex).map_err(io_error)
+    }
+}
+
+/// Writes `df` to `writer` in `format`.
+pub fn write_frame<W: Write>(
+    df: &DataFrame,
+    format: OutputFormat,
+    mut writer: W,
+) -> Result<(), DataFrameError> {
+    format.writer().write_frame(df, &mut writer)
+}
+
+impl DataFrame {
+    /// The frame rendered in `format`.
+    pub fn render(&self, format: OutputFormat) -> Result<String, DataFrameError> {
+        let mut buffer = Vec::new();
+        write_frame(self, format, &mut buffer)?;
+        String::from_utf8(buffer).map_err(|e| DataFrameError::EncodingError(e.to_string()))
+    }
+}
+
+/// Cell text row by row; nulls are empty.
+fn cells(df: &DataFrame) -> impl Iterator<Item = Vec<String>> + '_ {
+    (0..df.shape().0).map(move |row| {
+        df.columns()
+            .iter()
+            .map(|column| column.get(row).map_or_else(String::new, |v| v.to_string()))
+            .collect()
+    })
+}
+
+fn numeric_columns(df: &DataFrame) -> Vec<bool> {
+    df.columns()
+        .iter()
+        .map(|column| column.dtype().is_numeric())
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn escape_latex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn io_error(e: std::io::Error) -> DataFrameError {
+    DataFrameError::IoError(e.to_string())
+}
@@ -1,6 +1,13 @@
+use std::collections::HashMap;
+
+use crate::frame::{pearson, DataFrameError};
+use crate::series::histogram::quantile_sorted;
+use crate::series::StringStats;
 use crate::ColumnArray;
 use crate::DataFrame;
 
+/// Statistics of every column: numeric summaries, nulls and type, plus
+/// length, empty and distinct counts for string and categorical columns.
 pub fn generate_info_report(df: &DataFrame) -> DataFrame {
     let original_headers = df.headers();
 
@@ -13,6 +20,11 @@ pub fn generate_info_report(df: &DataFrame) -> DataFrame {
         "max".to_string(),
         "null_count".to_string(),
         "dtype".to_string(),
+        "min_length".to_string(),
+        "max_length".to_string(),
+        "mean_length".to_string(),
+        "empty_count".to_string(),
+        "distinct_count".to_string(),
     ];
 
     let mut column_names: Vec<Option<String>> = Vec::new();
@@ -22,6 +34,11 @@ pub fn generate_info_report(df: &DataFrame) -> DataFrame {
     let mut max_col: Vec<Option<f64>> = Vec::new();
     let mut null_count_col: Vec<usize> = Vec::new();
     let mut dtype_col: Vec<String> = Vec::new();
+    let mut min_length_col: Vec<Option<i64>> = Vec::new();
+    let mut max_length_col: Vec<Option<i64>> = Vec::new();
+    let mut mean_length_col: Vec<Option<f64>> = Vec::new();
+    let mut empty_col: Vec<Option<i64>> = Vec::new();
+    let mut distinct_col: Vec<Option<i64>> = Vec::new();
 
     for (col_idx, header) in original_headers.iter().enumerate() {
         if let Some(column) = df.get_column(col_idx) {
@@ -32,6 +49,16 @@ pub fn generate_info_report(df: &DataFrame) -> DataFrame {
             max_col.push(column.max());
             null_count_col.push(column.null_count());
             dtype_col.push(format!("{:?}", column.dtype()));
+
+            // String statistics; null for other types
+            let stats = column.str().ok().map(|strings| strings.stats());
+            let stat =
+                |f: fn(&StringStats) -> Option<usize>| stats.as_ref().and_then(f).map(|n| n as i64);
+            min_length_col.push(stat(|s| s.min_length));
+            max_length_col.push(stat(|s| s.max_length));
+            mean_length_col.push(stats.as_ref().and_then(|s| s.mean_length));
+            empty_col.push(stat(|s| Some(s.empty_count)));
+            distinct_col.push(stat(|s| Some(s.distinct_count)));
         }
     }
 
@@ -43,30 +70,506 @@ pub fn generate_info_report(df: &DataFrame) -> DataFrame {
         max_col.into(),        // Vec<Option<f64>> -> Box<dyn ColumnArray>
         null_count_col.into(), // Vec<Option<i64>> -> Box<dyn ColumnArray>
         dtype_col.into(),
+        min_length_col.into(),
+        max_length_col.into(),
+        mean_length_col.into(),
+        empty_col.into(),
+        distinct_col.into(),
     ];
 
     DataFrame::new(Some(headers), columns).unwrap()
 }
 
+/// Null count and share of missing values of every column.
 pub fn generate_na_report(df: &DataFrame) -> DataFrame {
-    let original_headers = df.headers();
+    generate_detailed_na_report(df).to_frame()
+}
 
-    // Include column names as the first column
-    let mut column_names: Vec<Option<String>> = Vec::new();
-    let headers = vec!["column".to_string(), "null_count".to_string()];
-    let mut null_count_col: Vec<usize> = Vec::new();
+/// One combination of missing columns and how many rows have exactly it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NullPattern {
+    /// Indices of the columns that are null, in frame order.
+    pub missing: Vec<usize>,
+    pub rows: usize,
+}
 
-    for (col_idx, header) in original_headers.iter().enumerate() {
-        if let Some(column) = df.get_column(col_idx) {
-            column_names.push(Some(header.clone())); // Original column name
-            null_count_col.push(column.null_count());
+/// Where a frame's nulls are: per column, per row and which go together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NaReport {
+    pub rows: usize,
+    /// Every column with its null count, in frame order.
+    pub columns: Vec<(String, usize)>,
+    /// Rows without a single null.
+    pub complete_rows: usize,
+    /// Rows where every value is null.
+    pub empty_rows: usize,
+    /// Distinct combinations of missing columns, most frequent first.
+    pub patterns: Vec<NullPattern>,
+    /// The columns with some but not all values missing, which are the ones
+    /// `nullity_correlation` compares.
+    pub correlated_columns: Vec<String>,
+    /// Pearson correlation of the null indicators of each pair of
+    /// `correlated_columns`: 1 when they are always missing together, -1 when
+    /// one is missing exactly where the other is not.
+    pub nullity_correlation: Vec<Vec<Option<f64>>>,
+}
+
+impl NaReport {
+    /// Rows with at least one null.
+    pub fn rows_with_nulls(&self) -> usize {
+        self.rows - self.complete_rows
+    }
+
+    pub fn missing_cells(&self) -> usize {
+        self.columns.iter().map(|(_, nulls)| nulls).sum()
+    }
+
+    /// One row per column: its null count and percentage missing.
+    pub fn to_frame(&self) -> DataFrame {
+        let headers = vec![
+            "column".to_string(),
+            "null_count".to_string(),
+            "percent".to_string(),
+        ];
+        let names: Vec<String> = self.columns.iter().map(|(name, _)| name.clone()).collect();
+        let counts: Vec<usize> = self.columns.iter().map(|&(_, nulls)| nulls).collect();
+        let percents: Vec<f64> = counts
+            .iter()
+            .map(|&nulls| percent(nulls, self.rows))
+            .collect();
+        let columns: Vec<Box<dyn ColumnArray>> = vec![names.into(), counts.into(), percents.into()];
+        DataFrame::new(Some(headers), columns).unwrap()
+    }
+
+    /// Row-level counts: complete rows, rows with any null and all-null rows.
+    pub fn summary_to_frame(&self) -> DataFrame {
+        let cells = self.rows * self.columns.len();
+        let measures = [
+            ("rows", self.rows, None),
+            (
+                "complete_rows",
+                self.complete_rows,
+                Some(percent(self.complete_rows, self.rows)),
+            ),
+            (
+                "rows_with_nulls",
+                self.rows_with_nulls(),
+                Some(percent(self.rows_with_nulls(), self.rows)),
+            ),
+            (
+                "empty_rows",
+                self.empty_rows,
+                Some(percent(self.empty_rows, self.rows)),
+            ),
+            (
+                "missing_cells",
+                self.missing_cells(),
+                Some(percent(self.missing_cells(), cells)),
+            ),
+        ];
+        measures_frame(&measures)
+    }
+
+    /// The `limit` most common patterns as a text matrix: one column per
+    /// column that has nulls, `NA` where the pattern is missing it, then the
+    /// number and share of rows with that pattern.
+    pub fn patterns_to_frame(&self, limit: usize) -> DataFrame {
+        let shown: Vec<usize> = (0..self.columns.len())
+            .filter(|&col| self.columns[col].1 > 0)
+            .collect();
+        let patterns = &self.patterns[..limit.min(self.patterns.len())];
+
+        let mut headers: Vec<String> = shown
+            .iter()
+            .map(|&col| self.columns[col].0.clone())
+            .collect();
+        let mut columns: Vec<Box<dyn ColumnArray>> = shown
+            .iter()
+            .map(|col| {
+                let marks: Vec<String> = patterns
+                    .iter()
+                    .map(|pattern| {
+                        if pattern.missing.contains(col) {
+                            "NA".to_string()
+                        } else {
+                            String::new()
+                        }
+                    })
+                    .collect();
+                marks.into()
+            })
+            .collect();
+        let rows: Vec<usize> = patterns.iter().map(|pattern| pattern.rows).collect();
+        let percents: Vec<f64> = rows.iter().map(|&rows| percent(rows, self.rows)).collect();
+        headers.extend(["rows".to_string(), "percent".to_string()]);
+        columns.extend([rows.into(), percents.into()]);
+        DataFrame::new(Some(headers), columns).unwrap()
+    }
+
+    /// The nullity correlation matrix, laid out like `DataFrame::corr`.
+    pub fn correlation_to_frame(&self) -> DataFrame {
+        let mut headers = vec!["column".to_string()];
+        headers.extend(self.correlated_columns.iter().cloned());
+        let mut columns: Vec<Box<dyn ColumnArray>> = vec![self.correlated_columns.clone().into()];
+        for col in 0..self.correlated_columns.len() {
+            let values: Vec<Option<f64>> = self
+                .nullity_correlation
+                .iter()
+                .map(|row| row[col])
+                .collect();
+            columns.push(values.into());
         }
+        DataFrame::new(Some(headers), columns).unwrap()
     }
+}
 
-    let columns: Vec<Box<dyn ColumnArray>> = vec![
-        column_names.into(),   // Vec<Option<String>> -> Box<dyn ColumnArray>
-        null_count_col.into(), // Vec<Option<i64>> -> Box<dyn ColumnArray>
-    ];
+/// Counts nulls per column and per row, groups rows by which columns are
+/// missing and correlates the missingness of the partly missing columns.
+pub fn generate_detailed_na_report(df: &DataFrame) -> NaReport {
+    let rows = df.shape().0;
+    let columns: Vec<(String, usize)> = df
+        .headers()
+        .iter()
+        .zip(df.columns())
+        .map(|(name, column)| (name.clone(), column.null_count()))
+        .collect();
+
+    let mut patterns: Vec<NullPattern> = Vec::new();
+    let mut pattern_of: HashMap<Vec<usize>, usize> = HashMap::new();
+    for row in 0..rows {
+        let missing: Vec<usize> = (0..columns.len())
+            .filter(|&col| df.columns()[col].get(row).is_none())
+            .collect();
+        match pattern_of.get(&missing) {
+            Some(&index) => patterns[index].rows += 1,
+            None => {
+                pattern_of.insert(missing.clone(), patterns.len());
+                patterns.push(NullPattern { missing, rows: 1 });
+            }
+        }
+    }
+    let complete_rows = pattern_of.get(&Vec::new()).map_or(0, |&i| patterns[i].rows);
+    let all: Vec<usize> = (0..columns.len()).collect();
+    let empty_rows = match pattern_of.get(&all) {
+        Some(&i) if !all.is_empty() => patterns[i].rows,
+        _ => 0,
+    };
+    // stable, so equally common patterns stay in order of first appearance
+    patterns.sort_by_key(|pattern| std::cmp::Reverse(pattern.rows));
+
+    let partial: Vec<usize> = (0..columns.len())
+        .filter(|&col| columns[col].1 > 0 && columns[col].1 < rows)
+        .collect();
+    let indicators: Vec<Vec<f64>> = partial
+        .iter()
+        .map(|&col| {
+            (0..rows)
+                .map(|row| df.columns()[col].get(row).is_none() as u8 as f64)
+                .collect()
+        })
+        .collect();
+    let nullity_correlation = indicators
+        .iter()
+        .map(|a| indicators.iter().map(|b| pearson(a, b)).collect())
+        .collect();
+
+    NaReport {
+        rows,
+        complete_rows,
+        empty_rows,
+        patterns,
+        correlated_columns: partial.iter().map(|&col| columns[col].0.clone()).collect(),
+        nullity_correlation,
+        columns,
+    }
+}
+
+/// Thresholds used by `generate_outlier_report`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlierOptions {
+    /// Values beyond `Q1 - k * IQR` or `Q3 + k * IQR` are outliers (Tukey's fences).
+    pub iqr_factor: f64,
+    /// Values more than this many standard deviations from the mean are outliers.
+    pub z_threshold: f64,
+    /// Values whose modified z-score `0.6745 * |x - median| / MAD` exceeds this are outliers.
+    pub mad_threshold: f64,
+}
+
+impl Default for OutlierOptions {
+    fn default() -> Self {
+        OutlierOptions {
+            iqr_factor: 1.5,
+            z_threshold: 3.0,
+            mad_threshold: 3.5,
+        }
+    }
+}
+
+impl OutlierOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
+    pub fn with_iqr_factor(mut self, iqr_factor: f64) -> Self {
+        self.iqr_factor = iqr_factor;
+        self
+    }
+
+    pub fn with_z_threshold(mut self, z_threshold: f64) -> Self {
+        self.z_threshold = z_threshold;
+        self
+    }
+
+    pub fn with_mad_threshold(mut self, mad_threshold: f64) -> Self {
+        self.mad_threshold = mad_threshold;
+        self
+    }
+}
+
+/// Outliers of one numeric column, as row indices per method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnOutliers {
+    pub column: String,
+    pub lower_fence: f64,
+    pub upper_fence: f64,
+    pub iqr_rows: Vec<usize>,
+    pub zscore_rows: Vec<usize>,
+    pub mad_rows: Vec<usize>,
+}
+
+impl ColumnOutliers {
+    /// Rows flagged by at least one method, in ascending order.
+    pub fn rows(&self) -> Vec<usize> {
+        let mut rows: Vec<usize> = self
+            .iqr_rows
+            .iter()
+            .chain(&self.zscore_rows)
+            .chain(&self.mad_rows)
+            .copied()
+            .collect();
+        rows.sort_unstable();
+        rows.dedup();
+        rows
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlierReport {
+    pub columns: Vec<ColumnOutliers>,
+}
+
+impl OutlierReport {
+    /// Rows flagged in any column by any method, in ascending order.
+    pub fn flagged_rows(&self) -> Vec<usize> {
+        let mut rows: Vec<usize> = self.columns.iter().flat_map(|c| c.rows()).collect();
+        rows.sort_unstable();
+        rows.dedup();
+        rows
+    }
+
+    /// One row per column with the IQR fences and the outlier count of each method.
+    pub fn to_frame(&self) -> DataFrame {
+        let headers = vec![
+            "column".to_string(),
+            "lower_fence".to_string(),
+            "upper_fence".to_string(),
+            "iqr".to_string(),
+            "zscore".to_string(),
+            "mad".to_string(),
+        ];
+        let names: Vec<String> = self.columns.iter().map(|c| c.column.clone()).collect();
+        let lower: Vec<f64> = self.columns.iter().map(|c| c.lower_fence).collect();
+        let upper: Vec<f64> = self.columns.iter().map(|c| c.upper_fence).collect();
+        let iqr: Vec<usize> = self.columns.iter().map(|c| c.iqr_rows.len()).collect();
+        let zscore: Vec<usize> = self.columns.iter().map(|c| c.zscore_rows.len()).collect();
+        let mad: Vec<usize> = self.columns.iter().map(|c| c.mad_rows.len()).collect();
+
+        let columns: Vec<Box<dyn ColumnArray>> = vec![
+            names.into(),
+            lower.into(),
+            upper.into(),
+            iqr.into(),
+            zscore.into(),
+            mad.into(),
+        ];
+        DataFrame::new(Some(headers), columns).unwrap()
+    }
+}
+
+/// Flags unusual values in every integer and float column.
+///
+/// Each column is checked with Tukey's IQR fences, the z-score and the
+/// MAD-based modified z-score. Nulls and NaN are never flagged; a method
+/// flags nothing when the column has no spread by its measure.
+pub fn generate_outlier_report(df: &DataFrame, options: &OutlierOptions) -> OutlierReport {
+    let mut columns = Vec::new();
+
+    for (header, column) in df.headers().iter().zip(df.columns()) {
+        if !column.dtype().is_numeric() {
+            continue;
+        }
+        let rows: Vec<(usize, f64)> = (0..column.len())
+            .filter_map(|row| column.get_f64(row).map(|value| (row, value)))
+            .filter(|(_, value)| !value.is_nan())
+            .collect();
+        if rows.is_empty() {
+            continue;
+        }
+
+        let mut sorted: Vec<f64> = rows.iter().map(|&(_, value)| value).collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let n = sorted.len() as f64;
+
+        let q1 = quantile_sorted(&sorted, 0.25);
+        let q3 = quantile_sorted(&sorted, 0.75);
+        let lower_fence = q1 - options.iqr_factor * (q3 - q1);
+        let upper_fence = q3 + options.iqr_factor * (q3 - q1);
+
+        let mean = sorted.iter().sum::<f64>() / n;
+        let std = (sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n).sqrt();
+
+        let median = quantile_sorted(&sorted, 0.5);
+        let mut deviations: Vec<f64> = sorted.iter().map(|x| (x - median).abs()).collect();
+        deviations.sort_by(|a, b| a.total_cmp(b));
+        let mad = quantile_sorted(&deviations, 0.5);
+
+        let flagged = |test: &dyn Fn(f64) -> bool| -> Vec<usize> {
+            rows.iter()
+                .filter(|&&(_, value)| test(value))
+                .map(|&(row, _)| row)
+                .collect()
+        };
+
+        columns.push(ColumnOutliers {
+            column: header.clone(),
+            lower_fence,
+            upper_fence,
+            iqr_rows: flagged(&|x| x < lower_fence || x > upper_fence),
+            zscore_rows: flagged(&|x| std > 0.0 && ((x - mean) / std).abs() > options.z_threshold),
+            mad_rows: flagged(&|x| {
+                mad > 0.0 && (0.6745 * (x - median) / mad).abs() > options.mad_threshold
+            }),
+        });
+    }
+
+    OutlierReport { columns }
+}
+
+/// Row-level problems of a frame: missing cells, empty rows and duplicates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityReport {
+    pub rows: usize,
+    pub columns: usize,
+    pub missing_cells: usize,
+    /// Rows where every value is null.
+    pub empty_rows: usize,
+    /// Row indices of each set of identical rows, ordered by their first row.
+    pub duplicate_groups: Vec<Vec<usize>>,
+}
+
+impl QualityReport {
+    /// Rows that repeat an earlier row.
+    pub fn duplicate_rows(&self) -> usize {
+        self.duplicate_groups
+            .iter()
+            .map(|rows| rows.len() - 1)
+            .sum()
+    }
+
+    /// One row per measure, with its count and share of the rows or cells.
+    pub fn to_frame(&self) -> DataFrame {
+        let cells = self.rows * self.columns;
+        let measures = [
+            ("rows", self.rows, None),
+            ("columns", self.columns, None),
+            (
+                "missing_cells",
+                self.missing_cells,
+                Some(percent(self.missing_cells, cells)),
+            ),
+            (
+                "empty_rows",
+                self.empty_rows,
+                Some(percent(self.empty_rows, self.rows)),
+            ),
+            (
+                "duplicate_rows",
+                self.duplicate_rows(),
+                Some(percent(self.duplicate_rows(), self.rows)),
+            ),
+            ("duplicate_groups", self.duplicate_groups.len(), None),
+        ];
+        measures_frame(&measures)
+    }
+
+    /// The duplicates section: one row per set of identical rows, largest first.
+    pub fn duplicates_to_frame(&self) -> DataFrame {
+        let mut groups: Vec<&Vec<usize>> = self.duplicate_groups.iter().collect();
+        groups.sort_by_key(|rows| std::cmp::Reverse(rows.len()));
+
+        let headers = vec![
+            "first_row".to_string(),
+            "copies".to_string(),
+            "rows".to_string(),
+        ];
+        let first: Vec<usize> = groups.iter().map(|rows| rows[0]).collect();
+        let copies: Vec<usize> = groups.iter().map(|rows| rows.len()).collect();
+        let rows: Vec<String> = groups
+            .iter()
+            .map(|rows| {
+                rows.iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        let columns: Vec<Box<dyn ColumnArray>> = vec![first.into(), copies.into(), rows.into()];
+        DataFrame::new(Some(headers), columns).unwrap()
+    }
+}
+
+/// Counts missing cells, all-null rows and rows identical on the `subset`
+/// columns (all columns when empty).
+pub fn generate_quality_report<S: AsRef<str>>(
+    df: &DataFrame,
+    subset: &[S],
+) -> Result<QualityReport, DataFrameError> {
+    let (rows, columns) = df.shape();
+    let missing_cells = df.columns().iter().map(|c| c.null_count()).sum();
+    let empty_rows = (0..rows)
+        .filter(|&row| columns > 0 && df.columns().iter().all(|c| c.get(row).is_none()))
+        .count();
+    Ok(QualityReport {
+        rows,
+        columns,
+        missing_cells,
+        empty_rows,
+        duplicate_groups: df.duplicate_groups(subset)?,
+    })
+}
+
+/// `count` as a percentage of `total`, rounded to two decimals.
+fn percent(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (10_000.0 * count as f64 / total as f64).round() / 100.0
+    }
+}
+
+/// A `measure` / `count` / `percent` frame, one row per measure.
+fn measures_frame(measures: &[(&str, usize, Option<f64>)]) -> DataFrame {
+    let headers = vec![
+        "measure".to_string(),
+        "count".to_string(),
+        "percent".to_string(),
+    ];
+    let names: Vec<String> = measures
+        .iter()
+        .map(|(name, _, _)| name.to_string())
+        .collect();
+    let counts: Vec<usize> = measures.iter().map(|&(_, count, _)| count).collect();
+    let percents: Vec<Option<f64>> = measures.iter().map(|&(_, _, percent)| percent).collect();
+    let columns: Vec<Box<dyn ColumnArray>> = vec![names.into(), counts.into(), percents.into()];
     DataFrame::new(Some(headers), columns).unwrap()
 }
@@ -1,3 +1,5 @@
+use crate::series::temporal::format_duration;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum CellValue {
     Str(String),
@@ -5,6 +7,8 @@ pub enum CellValue {
     Integer(i64),
     Boolean(bool),
     Date(String),
+    /// A duration in microseconds.
+    Duration(i64),
     Null,
 }
 
@@ -20,6 +24,7 @@ impl CellValue {
             CellValue::Integer(_) => "integer",
             CellValue::Boolean(_) => "boolean",
             CellValue::Date(_) => "date",
+            CellValue::Duration(_) => "duration",
             CellValue::Null => "null",
         }
     }
@@ -33,7 +38,74 @@ impl std::fmt::Display for CellValue {
             CellValue::Integer(n) => write!(f, "{}", n),
             CellValue::Boolean(b) => write!(f, "{}", b),
             CellValue::Date(d) => write!(f, "{}", d),
+            CellValue::Duration(micros) => write!(f, "{}", format_duration(*micros)),
             CellValue::Null => write!(f, ""),
         }
     }
 }
+
+/// A hashable stand-in for an optional cell value, used to group equal values.
+///
+/// Floats compare by bit pattern (with all NaNs folded into one key) and
+/// nulls are equal to each other.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValueKey {
+    Null,
+    Str(String),
+    Float(u64),
+    Integer(i64),
+    Boolean(bool),
+    Date(String),
+    Duration(i64),
+}
+
+impl From<Option<CellValue>> for ValueKey {
+    fn from(value: Option<CellValue>) -> Self {
+        match value {
+            None | Some(CellValue::Null) => ValueKey::Null,
+            Some(CellValue::Str(s)) => ValueKey::Str(s),
+            Some(CellValue::Float(n)) if n.is_nan() => ValueKey::Float(f64::NAN.to_bits()),
+            Some(CellValue::Float(n)) => ValueKey::Float(n.to_bits()),
+            Some(CellValue::Integer(n)) => ValueKey::Integer(n),
+            Some(CellValue::Boolean(b)) => ValueKey::Boolean(b),
+            Some(CellValue::Date(d)) => ValueKey::Date(d),
+            Some(CellValue::Duration(micros)) => ValueKey::Duration(micros),
+        }
+    }
+}
+
+impl From<i64> for CellValue {
+    fn from(value: i64) -> Self {
+        CellValue::Integer(value)
+    }
+}
+
+impl From<i32> for CellValue {
+    fn from(value: i32) -> Self {
+        CellValue::Integer(value as i64)
+    }
+}
+
+impl From<f64> for CellValue {
+    fn from(value: f64) -> Self {
+        CellValue::Float(value)
+    }
+}
+
+impl From<bool> for CellValue {
+    fn from(value: bool) -> Self {
+        CellValue::Boolean(value)
+    }
+}
+
+impl From<&str> for CellValue {
+    fn from(value: &str) -> Self {
+        CellValue::Str(value.to_string())
+    }
+}
+
+impl From<String> for CellValue {
+    fn from(value: String) -> Self {
+        CellValue::Str(value)
+    }
+}
@@ -0,0 +1,382 @@
+//! Element-wise arithmetic on numeric columns.
+//!
+//! Kernels work directly on the value buffers and combine validity bitmaps, so a
+//! result is null wherever an input is null. Integer operands stay integers for
+//! `+`, `-`, `*`, `%` and non-negative integer powers, with overflow reported as
+//! an error; division and anything involving a float produce floats. Booleans
+//! count as the integers 0 and 1.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::frame::DataFrameError;
+use crate::series::{Bitmap, BooleanColumn, ColumnArray, FloatColumn, IntegerColumn};
+use crate::CellValue;
+
+/// A binary arithmetic operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    /// True division; always produces floats.
+    Div,
+    /// Remainder with the sign of the dividend; integer `x % 0` is null.
+    Rem,
+    Pow,
+}
+
+impl fmt::Display for ArithOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            ArithOp::Add => "+",
+            ArithOp::Sub => "-",
+            ArithOp::Mul => "*",
+            ArithOp::Div => "/",
+            ArithOp::Rem => "%",
+            ArithOp::Pow => "^",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+/// One side of an arithmetic operation: a column or a scalar broadcast to every row.
+#[derive(Debug, Clone, Copy)]
+pub enum Operand<'a> {
+    Column(&'a dyn ColumnArray),
+    Integer(i64),
+    Float(f64),
+}
+
+impl<'a> From<&'a dyn ColumnArray> for Operand<'a> {
+    fn from(column: &'a dyn ColumnArray) -> Self {
+        Operand::Column(column)
+    }
+}
+
+impl<'a> From<&'a Box<dyn ColumnArray>> for Operand<'a> {
+    fn from(column: &'a Box<dyn ColumnArray>) -> Self {
+        Operand::Column(column.as_ref())
+    }
+}
+
+impl From<i64> for Operand<'_> {
+    fn from(value: i64) -> Self {
+        Operand::Integer(value)
+    }
+}
+
+impl From<f64> for Operand<'_> {
+    fn from(value: f64) -> Self {
+        Operand::Float(value)
+    }
+}
+
+impl Operand<'_> {
+    /// A scalar operand from a cell value; `None` for non-numeric values.
+    pub fn scalar(value: &CellValue) -> Option<Operand<'static>> {
+        match value {
+            CellValue::Integer(n) => Some(Operand::Integer(*n)),
+            CellValue::Float(n) => Some(Operand::Float(*n)),
+            CellValue::Boolean(b) => Some(Operand::Integer(*b as i64)),
+            _ => None,
+        }
+    }
+}
+
+/// Numeric view of an operand.
+enum Side<'a> {
+    IntColumn(Cow<'a, [i64]>, &'a Bitmap),
+    FloatColumn(Cow<'a, [f64]>, &'a Bitmap),
+    Int(i64),
+    Float(f64),
+}
+
+impl<'a> Side<'a> {
+    fn of(operand: Operand<'a>) -> Result<Side<'a>, DataFrameError> {
+        let column = match operand {
+            Operand::Integer(value) => return Ok(Side::Int(value)),
+            Operand::Float(value) => return Ok(Side::Float(value)),
+            Operand::Column(column) => column,
+        };
+        let any = column.as_any();
+        if let Some(column) = any.downcast_ref::<IntegerColumn>() {
+            Ok(Side::IntColumn(
+                Cow::Borrowed(column.values()),
+                column.validity(),
+            ))
+        } else if let Some(column) = any.downcast_ref::<FloatColumn>() {
+            Ok(Side::FloatColumn(
+                Cow::Borrowed(column.values()),
+                column.validity(),
+            ))
+        } else if let Some(column) = any.downcast_ref::<BooleanColumn>() {
+            let values = (0..column.len())
+                .map(|i| column.value(i).unwrap_or_default() as i64)
+                .collect();
+            Ok(Side::IntColumn(Cow::Owned(values), column.validity()))
+        } else {
+            Err(DataFrameError::TypeError(format!(
+                "arithmetic needs numeric columns, got {:?}",
+                column.dtype()
+            )))
+        }
+    }
+
+    fn len(&self) -> Option<usize> {
+        match self {
+            Side::IntColumn(values, _) => Some(values.len()),
+            Side::FloatColumn(values, _) => Some(values.len()),
+            Side::Int(_) | Side::Float(_) => None,
+        }
+    }
+
+    fn validity(&self) -> Option<&Bitmap> {
+        match self {
+            Side::IntColumn(_, validity) | Side::FloatColumn(_, validity) => Some(validity),
+            Side::Int(_) | Side::Float(_) => None,
+        }
+    }
+
+    fn is_int(&self) -> bool {
+        matches!(self, Side::IntColumn(..) | Side::Int(_))
+    }
+
+    fn int(&self, index: usize) -> i64 {
+        match self {
+            Side::IntColumn(values, _) => values[index],
+            Side::Int(value) => *value,
+            _ => unreachable!("checked by is_int"),
+        }
+    }
+
+    fn float(&self, index: usize) -> f64 {
+        match self {
+            Side::IntColumn(values, _) => values[index] as f64,
+            Side::FloatColumn(values, _) => values[index],
+            Side::Int(value) => *value as f64,
+            Side::Float(value) => *value,
+        }
+    }
+}
+
+/// Applies `op` row by row to two operands, at least one of which must be a column.
+pub fn binary<'a>(
+    lhs: impl Into<Operand<'a>>,
+    op: ArithOp,
+    rhs: impl Into<Operand<'a>>,
+) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+    let lhs = Side::of(lhs.into())?;
+    let rhs = Side::of(rhs.into())?;
+
+    let len = match (lhs.len(), rhs.len()) {
+        (Some(l), Some(r)) if l != r => {
+            return Err(DataFrameError::ColumnsLengthMismatch {
+                column: format!("right operand of '{}'", op),
+                expected: l,
+                actual: r,
+            })
+        }
+        (Some(len), _) | (None, Some(len)) => len,
+        (None, None) => {
+            return Err(DataFrameError::InvalidArgument(
+                "arithmetic needs at least one column operand".to_string(),
+            ))
+        }
+    };
+    let mut validity = match (lhs.validity(), rhs.validity()) {
+        (Some(l), Some(r)) => l.and(r),
+        (Some(validity), None) | (None, Some(validity)) => validity.clone(),
+        (None, None) => unreachable!("one side is a column"),
+    };
+
+    let integer_result = lhs.is_int()
+        && rhs.is_int()
+        && match op {
+            ArithOp::Div => false,
+            // A negative exponent anywhere turns the whole result into floats.
+            ArithOp::Pow => (0..len).all(|i| !validity.get(i) || rhs.int(i) >= 0),
+            _ => true,
+        };
+
+    if integer_result {
+        let mut values = Vec::with_capacity(len);
+        for i in 0..len {
+            if !validity.get(i) {
+                values.push(0);
+                continue;
+            }
+            let (a, b) = (lhs.int(i), rhs.int(i));
+            let result = match op {
+                ArithOp::Add => a.checked_add(b),
+                ArithOp::Sub => a.checked_sub(b),
+                ArithOp::Mul => a.checked_mul(b),
+                ArithOp::Rem if b == 0 => {
+                    validity.set(i, false);
+                    values.push(0);
+                    continue;
+                }
+                ArithOp::Rem => a.checked_rem(b),
+                ArithOp::Pow => u32::try_from(b).ok().and_then(|b| a.checked_pow(b)),
+                ArithOp::Div => unreachable!("division produces floats"),
+            };
+            match result {
+                Some(value) => values.push(value),
+                None => {
+                    return Err(DataFrameError::ArithmeticError(format!(
+                        "integer overflow in {} {} {} (row {})",
+                        a, op, b, i
+                    )))
+                }
+            }
+        }
+        let column = IntegerColumn::from_parts(values, validity).expect("one value per row");
+        return Ok(Box::new(column));
+    }
+
+    let values = (0..len)
+        .map(|i| {
+            let (a, b) = (lhs.float(i), rhs.float(i));
+            match op {
+                ArithOp::Add => a + b,
+                ArithOp::Sub => a - b,
+                ArithOp::Mul => a * b,
+                ArithOp::Div => a / b,
+                ArithOp::Rem => a % b,
+                ArithOp::Pow => a.powf(b),
+            }
+        })
+        .collect();
+    let column = FloatColumn::from_parts(values, validity).expect("one value per row");
+    Ok(Box::new(column))
+}
+
+/// Negates every value; integer overflow (`-i64::MIN`) is an error.
+pub fn neg(column: &dyn ColumnArray) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+    unary(column, "negation", i64::checked_neg, |x| -x)
+}
+
+/// Absolute value of every value; integer overflow (`|i64::MIN|`) is an error.
+pub fn abs(column: &dyn ColumnArray) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+    unary(column, "absolute value", i64::checked_abs, f64::abs)
+}
+
+/// Rounds to `decimals` places, halves to even. Negative `decimals` round to
+/// tens, hundreds, ...; integer columns stay integers.
+pub fn round(
+    column: &dyn ColumnArray,
+    decimals: i32,
+) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+    let scale = 10f64.powi(decimals.abs());
+    if decimals >= 0 {
+        unary(column, "rounding", Some, |x| {
+            (x * scale).round_ties_even() / scale
+        })
+    } else {
+        let step = 10i64.checked_pow(decimals.unsigned_abs());
+        unary(
+            column,
+            "rounding",
+            |x| {
+                let step = step?;
+                let rounded = (x as f64 / step as f64).round_ties_even() as i64;
+                rounded.checked_mul(step)
+            },
+            |x| (x / scale).round_ties_even() * scale,
+        )
+    }
+}
+
+fn unary(
+    column: &dyn ColumnArray,
+    name: &str,
+    int_op: impl Fn(i64) -> Option<i64>,
+    float_op: impl Fn(f64) -> f64,
+) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+    match Side::of(Operand::Column(column))? {
+        Side::IntColumn(values, validity) => {
+            let mut result = Vec::with_capacity(values.len());
+            for (i, &value) in values.iter().enumerate() {
+                if !validity.get(i) {
+                    result.push(0);
+                    continue;
+                }
+                match int_op(value) {
+                    Some(value) => result.push(value),
+                    None => {
+                        return Err(DataFrameError::ArithmeticError(format!(
+                            "integer overflow in {} of {} (row {})",
+                            name, value, i
+                        )))
+                    }
+                }
+            }
+            let column =
+                IntegerColumn::from_parts(result, validity.clone()).expect("one value per row");
+            Ok(Box::new(column))
+        }
+        Side::FloatColumn(values, validity) => {
+            let result = values.iter().map(|&x| float_op(x)).collect();
+            let column =
+                FloatColumn::from_parts(result, validity.clone()).expect("one value per row");
+            Ok(Box::new(column))
+        }
+        Side::Int(_) | Side::Float(_) => unreachable!("operand is a column"),
+    }
+}
+
+impl dyn ColumnArray + '_ {
+    pub fn add<'a>(
+        &'a self,
+        rhs: impl Into<Operand<'a>>,
+    ) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        binary(self, ArithOp::Add, rhs)
+    }
+
+    pub fn sub<'a>(
+        &'a self,
+        rhs: impl Into<Operand<'a>>,
+    ) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        binary(self, ArithOp::Sub, rhs)
+    }
+
+    pub fn mul<'a>(
+        &'a self,
+        rhs: impl Into<Operand<'a>>,
+    ) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        binary(self, ArithOp::Mul, rhs)
+    }
+
+    pub fn div<'a>(
+        &'a self,
+        rhs: impl Into<Operand<'a>>,
+    ) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        binary(self, ArithOp::Div, rhs)
+    }
+
+    pub fn rem<'a>(
+        &'a self,
+        rhs: impl Into<Operand<'a>>,
+    ) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        binary(self, ArithOp::Rem, rhs)
+    }
+
+    pub fn pow<'a>(
+        &'a self,
+        rhs: impl Into<Operand<'a>>,
+    ) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        binary(self, ArithOp::Pow, rhs)
+    }
+
+    pub fn neg(&self) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        neg(self)
+    }
+
+    pub fn abs(&self) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        abs(self)
+    }
+
+    pub fn round(&self, decimals: i32) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        round(self, decimals)
+    }
+}
@@ -1,7 +1,16 @@
-use crate::{CellValue, Dtype};
+use crate::frame::DataFrameError;
+use crate::scalar::ValueKey;
+use crate::series::cast::cast;
+use crate::series::categorical::try_categorical;
+use crate::series::fill::{fill_null, interpolate};
+use crate::series::histogram::{bin_column, bin_edges, numeric_values, quantile_edges};
+use crate::series::temporal::{parse_dates, parse_datetimes};
+use crate::series::{Bins, Bitmap, CategoricalColumn, DurationColumn, FillStrategy, Histogram};
+use crate::{CellValue, DataFrame, Dtype};
 use serde_json::{json, Value};
+use std::collections::HashMap;
 
-pub trait ColumnArray: std::fmt::Debug {
+pub trait ColumnArray: std::fmt::Debug + Send + Sync {
     fn len(&self) -> usize;
     fn dtype(&self) -> Dtype;
     fn get(&self, index: usize) -> Option<CellValue>;
@@ -11,6 +20,11 @@ pub trait ColumnArray: std::fmt::Debug {
     }
     fn as_any(&self) -> &dyn std::any::Any;
 
+    /// The value at `index` as a number, for integer, float and boolean columns.
+    fn get_f64(&self, _index: usize) -> Option<f64> {
+        None
+    }
+
     fn mean(&self) -> Option<f64> {
         None
     }
@@ -29,25 +43,365 @@ pub trait ColumnArray: std::fmt::Debug {
     }
 
     fn to_json(&self) -> Vec<serde_json::Value>;
+
+    /// Gathers the rows at `indices` into a new column of the same type.
+    /// Out-of-range indices produce nulls.
+    fn take(&self, indices: &[usize]) -> Box<dyn ColumnArray>;
+
+    /// A copy of the whole column.
+    fn clone_box(&self) -> Box<dyn ColumnArray> {
+        let indices: Vec<usize> = (0..self.len()).collect();
+        self.take(&indices)
+    }
+
+    /// Counts occurrences of each distinct value.
+    ///
+    /// Returns a two-column frame: `value` (same type as this column) and either
+    /// `count` or, with `normalize`, `proportion`. With `sort` the most frequent
+    /// values come first, otherwise values appear in order of first occurrence;
+    /// with `dropna` nulls are not counted.
+    fn value_counts(&self, normalize: bool, sort: bool, dropna: bool) -> DataFrame {
+        let mut groups: HashMap<ValueKey, usize> = HashMap::new();
+        let mut firsts: Vec<usize> = Vec::new();
+        let mut counts: Vec<usize> = Vec::new();
+
+        for index in 0..self.len() {
+            let key = ValueKey::from(self.get(index));
+            if dropna && key == ValueKey::Null {
+                continue;
+            }
+            match groups.get(&key) {
+                Some(&group) => counts[group] += 1,
+                None => {
+                    groups.insert(key, firsts.len());
+                    firsts.push(index);
+                    counts.push(1);
+                }
+            }
+        }
+
+        let mut order: Vec<usize> = (0..firsts.len()).collect();
+        if sort {
+            order.sort_by(|&a, &b| counts[b].cmp(&counts[a]));
+        }
+
+        let firsts: Vec<usize> = order.iter().map(|&group| firsts[group]).collect();
+        let counts: Vec<usize> = order.iter().map(|&group| counts[group]).collect();
+        value_counts_frame(self.take(&firsts), counts, normalize)
+    }
+
+    /// Counts the non-null values of a numeric column into `bins`.
+    fn histogram(&self, bins: &Bins) -> Result<Histogram, DataFrameError> {
+        Histogram::from_values(&numeric_values(self)?, bins)
+    }
+
+    /// Replaces every value by the label of the bin it falls into, e.g. `[0, 10)`.
+    fn cut(&self, bins: &Bins) -> Result<CategoricalColumn, DataFrameError> {
+        let edges = bin_edges(&numeric_values(self)?, bins)?;
+        Ok(bin_column(self, &edges))
+    }
+
+    /// Like `cut`, with edges at the quantiles that split the values into
+    /// `quantiles` groups of about the same size.
+    fn qcut(&self, quantiles: usize) -> Result<CategoricalColumn, DataFrameError> {
+        let edges = quantile_edges(&numeric_values(self)?, quantiles)?;
+        Ok(bin_column(self, &edges))
+    }
+
+    /// A copy of the column with its nulls replaced as `strategy` says.
+    ///
+    /// Forward, backward and mode fills keep the column type; mean and median
+    /// fills return a float column.
+    fn fill_null(&self, strategy: &FillStrategy) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        fill_null(self, strategy)
+    }
+
+    /// A float copy of a numeric column with interior nulls linearly interpolated.
+    fn interpolate(&self) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        interpolate(self)
+    }
+
+    /// The column converted to `dtype`; nulls stay null.
+    ///
+    /// Values that do not convert (text that is not a number, NaN to an
+    /// integer, ...) become nulls, or with `strict` make the cast fail with an
+    /// error naming the first failing rows.
+    fn cast(&self, dtype: Dtype, strict: bool) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        cast(self, dtype, strict)
+    }
+}
+
+impl Clone for Box<dyn ColumnArray> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Assembles the `value` / `count` (or `proportion`) frame returned by `value_counts`.
+pub(crate) fn value_counts_frame(
+    values: Box<dyn ColumnArray>,
+    counts: Vec<usize>,
+    normalize: bool,
+) -> DataFrame {
+    let (name, counts): (&str, Box<dyn ColumnArray>) = if normalize {
+        let total: usize = counts.iter().sum();
+        let proportions: Vec<f64> = counts
+            .iter()
+            .map(|&count| count as f64 / total as f64)
+            .collect();
+        ("proportion", proportions.into())
+    } else {
+        ("count", counts.into())
+    };
+
+    DataFrame::from_columns(
+        Some(vec!["value".to_string(), name.to_string()]),
+        vec![values, counts],
+    )
+    .expect("value and count columns have equal length")
+}
+
+/// Integer values with a validity bitmap; null slots hold `0`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntegerColumn {
+    values: Vec<i64>,
+    validity: Bitmap,
+}
+
+/// Float values with a validity bitmap; null slots hold `0.0`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FloatColumn {
+    values: Vec<f64>,
+    validity: Bitmap,
+}
+
+/// Strings stored back to back in one buffer, delimited by `offsets`
+/// (`len + 1` entries); null slots are empty strings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StringColumn {
+    data: String,
+    offsets: Vec<usize>,
+    validity: Bitmap,
+}
+
+/// Booleans packed into a bitmap, with a second bitmap for validity.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BooleanColumn {
+    values: Bitmap,
+    validity: Bitmap,
+}
+
+impl IntegerColumn {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a column from a value buffer and its validity bitmap.
+    ///
+    /// Returns `None` if the lengths differ.
+    pub fn from_parts(values: Vec<i64>, validity: Bitmap) -> Option<Self> {
+        if values.len() != validity.len() {
+            return None;
+        }
+        Some(IntegerColumn { values, validity })
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        IntegerColumn {
+            values: Vec::with_capacity(capacity),
+            validity: Bitmap::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, value: Option<i64>) {
+        self.values.push(value.unwrap_or_default());
+        self.validity.push(value.is_some());
+    }
+
+    pub fn value(&self, index: usize) -> Option<i64> {
+        if self.validity.get(index) {
+            Some(self.values[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Option<i64>> + '_ {
+        (0..self.values.len()).map(move |index| self.value(index))
+    }
+
+    /// Non-null values in order.
+    pub fn valid_values(&self) -> impl Iterator<Item = i64> + '_ {
+        self.iter().flatten()
+    }
+
+    /// The raw value buffer, including the placeholders behind nulls.
+    pub fn values(&self) -> &[i64] {
+        &self.values
+    }
+
+    pub fn validity(&self) -> &Bitmap {
+        &self.validity
+    }
 }
 
-#[derive(Debug)]
-pub struct IntegerColumn(pub Vec<Option<i64>>);
+impl FloatColumn {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a column from a value buffer and its validity bitmap.
+    ///
+    /// Returns `None` if the lengths differ.
+    pub fn from_parts(values: Vec<f64>, validity: Bitmap) -> Option<Self> {
+        if values.len() != validity.len() {
+            return None;
+        }
+        Some(FloatColumn { values, validity })
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        FloatColumn {
+            values: Vec::with_capacity(capacity),
+            validity: Bitmap::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, value: Option<f64>) {
+        self.values.push(value.unwrap_or_default());
+        self.validity.push(value.is_some());
+    }
+
+    pub fn value(&self, index: usize) -> Option<f64> {
+        if self.validity.get(index) {
+            Some(self.values[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Option<f64>> + '_ {
+        (0..self.values.len()).map(move |index| self.value(index))
+    }
 
-#[derive(Debug)]
-pub struct FloatColumn(pub Vec<Option<f64>>);
+    /// Non-null values in order, NaN included.
+    pub fn valid_values(&self) -> impl Iterator<Item = f64> + '_ {
+        self.iter().flatten()
+    }
 
-#[derive(Debug)]
-pub struct StringColumn(pub Vec<Option<String>>);
+    /// The raw value buffer, including the placeholders behind nulls.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    pub fn validity(&self) -> &Bitmap {
+        &self.validity
+    }
+}
 
-#[derive(Debug)]
-pub struct BooleanColumn(pub Vec<Option<bool>>);
+impl Default for StringColumn {
+    fn default() -> Self {
+        StringColumn {
+            data: String::new(),
+            offsets: vec![0],
+            validity: Bitmap::new(),
+        }
+    }
+}
+
+impl StringColumn {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize, bytes: usize) -> Self {
+        let mut offsets = Vec::with_capacity(capacity + 1);
+        offsets.push(0);
+        StringColumn {
+            data: String::with_capacity(bytes),
+            offsets,
+            validity: Bitmap::with_capacity(capacity),
+        }
+    }
+
+    pub fn push<S: AsRef<str>>(&mut self, value: Option<S>) {
+        if let Some(value) = &value {
+            self.data.push_str(value.as_ref());
+        }
+        self.offsets.push(self.data.len());
+        self.validity.push(value.is_some());
+    }
+
+    pub fn value(&self, index: usize) -> Option<&str> {
+        if self.validity.get(index) {
+            Some(&self.data[self.offsets[index]..self.offsets[index + 1]])
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Option<&str>> + '_ {
+        (0..self.validity.len()).map(move |index| self.value(index))
+    }
+
+    /// Non-null values in order.
+    pub fn valid_values(&self) -> impl Iterator<Item = &str> + '_ {
+        self.iter().flatten()
+    }
+
+    pub fn validity(&self) -> &Bitmap {
+        &self.validity
+    }
+}
+
+impl BooleanColumn {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        BooleanColumn {
+            values: Bitmap::with_capacity(capacity),
+            validity: Bitmap::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, value: Option<bool>) {
+        self.values.push(value.unwrap_or_default());
+        self.validity.push(value.is_some());
+    }
+
+    pub fn value(&self, index: usize) -> Option<bool> {
+        if self.validity.get(index) {
+            Some(self.values.get(index))
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Option<bool>> + '_ {
+        (0..self.values.len()).map(move |index| self.value(index))
+    }
+
+    /// Non-null values in order.
+    pub fn valid_values(&self) -> impl Iterator<Item = bool> + '_ {
+        self.iter().flatten()
+    }
+
+    /// Number of non-null `true` values.
+    pub fn true_count(&self) -> usize {
+        self.valid_values().filter(|&x| x).count()
+    }
+
+    pub fn validity(&self) -> &Bitmap {
+        &self.validity
+    }
+}
 
 impl ColumnArray for IntegerColumn {
     fn to_json(&self) -> Vec<serde_json::Value> {
-        self.0
-            .iter()
-            .map(|&x| match x {
+        self.iter()
+            .map(|x| match x {
                 Some(x) => json!(x),
                 None => Value::Null,
             })
@@ -59,32 +413,45 @@ impl ColumnArray for IntegerColumn {
     }
 
     fn get(&self, index: usize) -> Option<CellValue> {
-        self.0.get(index)?.map(CellValue::Integer)
+        self.value(index).map(CellValue::Integer)
+    }
+
+    fn get_f64(&self, index: usize) -> Option<f64> {
+        self.value(index).map(|x| x as f64)
     }
 
     fn len(&self) -> usize {
-        self.0.len()
+        self.values.len()
     }
 
     fn null_count(&self) -> usize {
-        self.0.iter().filter(|x| x.is_none()).count()
+        self.validity.count_unset()
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
+    fn clone_box(&self) -> Box<dyn ColumnArray> {
+        Box::new(self.clone())
+    }
+
+    fn take(&self, indices: &[usize]) -> Box<dyn ColumnArray> {
+        let column: IntegerColumn = indices.iter().map(|&i| self.value(i)).collect();
+        Box::new(column)
+    }
+
     fn sum(&self) -> Option<f64> {
-        let sum: i64 = self.0.iter().filter_map(|&x| x).sum();
+        let sum: i64 = self.valid_values().sum();
         Some(sum as f64)
     }
 
     fn max(&self) -> Option<f64> {
-        self.0.iter().filter_map(|&x| x).max().map(|x| x as f64)
+        self.valid_values().max().map(|x| x as f64)
     }
 
     fn min(&self) -> Option<f64> {
-        self.0.iter().filter_map(|&x| x).min().map(|x| x as f64)
+        self.valid_values().min().map(|x| x as f64)
     }
 
     fn mean(&self) -> Option<f64> {
@@ -92,16 +459,15 @@ impl ColumnArray for IntegerColumn {
         if count == 0 {
             return None;
         }
-        let sum: i64 = self.0.iter().filter_map(|&x| x).sum();
+        let sum: i64 = self.valid_values().sum();
         Some(sum as f64 / count as f64)
     }
 }
 
 impl ColumnArray for FloatColumn {
     fn to_json(&self) -> Vec<serde_json::Value> {
-        self.0
-            .iter()
-            .map(|&x| match x {
+        self.iter()
+            .map(|x| match x {
                 Some(x) => json!(x),
                 None => Value::Null,
             })
@@ -113,54 +479,52 @@ impl ColumnArray for FloatColumn {
     }
 
     fn get(&self, index: usize) -> Option<CellValue> {
-        self.0.get(index)?.map(CellValue::Float)
+        self.value(index).map(CellValue::Float)
+    }
+
+    fn get_f64(&self, index: usize) -> Option<f64> {
+        self.value(index)
     }
 
     fn len(&self) -> usize {
-        self.0.len()
+        self.values.len()
     }
 
     fn null_count(&self) -> usize {
-        self.0.iter().filter(|x| x.is_none()).count()
+        self.validity.count_unset()
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
+    fn clone_box(&self) -> Box<dyn ColumnArray> {
+        Box::new(self.clone())
+    }
+
+    fn take(&self, indices: &[usize]) -> Box<dyn ColumnArray> {
+        let column: FloatColumn = indices.iter().map(|&i| self.value(i)).collect();
+        Box::new(column)
+    }
+
     fn sum(&self) -> Option<f64> {
-        Some(
-            self.0
-                .iter()
-                .filter_map(|&x| x)
-                .filter(|x| !x.is_nan())
-                .sum(),
-        )
+        Some(self.valid_values().filter(|x| !x.is_nan()).sum())
     }
 
     fn max(&self) -> Option<f64> {
-        self.0
-            .iter()
-            .filter_map(|&x| x) // Remove None
+        self.valid_values()
             .filter(|x| !x.is_nan()) // Remove NaN
             .max_by(|a, b| a.partial_cmp(b).unwrap()) // Safe to unwrap now
     }
 
     fn min(&self) -> Option<f64> {
-        self.0
-            .iter()
-            .filter_map(|&x| x)
+        self.valid_values()
             .filter(|x| !x.is_nan())
             .min_by(|a, b| a.partial_cmp(b).unwrap())
     }
 
     fn mean(&self) -> Option<f64> {
-        let valid_values: Vec<f64> = self
-            .0
-            .iter()
-            .filter_map(|&x| x)
-            .filter(|x| !x.is_nan())
-            .collect();
+        let valid_values: Vec<f64> = self.valid_values().filter(|x| !x.is_nan()).collect();
 
         if valid_values.is_empty() {
             return Some(0.0);
@@ -173,10 +537,9 @@ impl ColumnArray for FloatColumn {
 
 impl ColumnArray for StringColumn {
     fn to_json(&self) -> Vec<serde_json::Value> {
-        self.0
-            .iter()
-            .map(|x| match &x {
-                Some(ref x) => json!(x),
+        self.iter()
+            .map(|x| match x {
+                Some(x) => json!(x),
                 None => Value::Null,
             })
             .collect()
@@ -187,27 +550,35 @@ impl ColumnArray for StringColumn {
     }
 
     fn get(&self, index: usize) -> Option<CellValue> {
-        self.0.get(index)?.clone().map(CellValue::Str)
+        self.value(index).map(|x| CellValue::Str(x.to_string()))
     }
 
     fn len(&self) -> usize {
-        self.0.len()
+        self.validity.len()
     }
 
     fn null_count(&self) -> usize {
-        self.0.iter().filter(|x| x.is_none()).count()
+        self.validity.count_unset()
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn ColumnArray> {
+        Box::new(self.clone())
+    }
+
+    fn take(&self, indices: &[usize]) -> Box<dyn ColumnArray> {
+        let column: StringColumn = indices.iter().map(|&i| self.value(i)).collect();
+        Box::new(column)
+    }
 }
 
 impl ColumnArray for BooleanColumn {
     fn to_json(&self) -> Vec<serde_json::Value> {
-        self.0
-            .iter()
-            .map(|&x| match x {
+        self.iter()
+            .map(|x| match x {
                 Some(x) => json!(x),
                 None => Value::Null,
             })
@@ -219,31 +590,43 @@ impl ColumnArray for BooleanColumn {
     }
 
     fn get(&self, index: usize) -> Option<CellValue> {
-        self.0.get(index)?.map(CellValue::Boolean)
+        self.value(index).map(CellValue::Boolean)
+    }
+
+    fn get_f64(&self, index: usize) -> Option<f64> {
+        self.value(index).map(|x| if x { 1.0 } else { 0.0 })
     }
 
     fn len(&self) -> usize {
-        self.0.len()
+        self.values.len()
     }
 
     fn null_count(&self) -> usize {
-        self.0.iter().filter(|x| x.is_none()).count()
+        self.validity.count_unset()
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
+    fn clone_box(&self) -> Box<dyn ColumnArray> {
+        Box::new(self.clone())
+    }
+
+    fn take(&self, indices: &[usize]) -> Box<dyn ColumnArray> {
+        let column: BooleanColumn = indices.iter().map(|&i| self.value(i)).collect();
+        Box::new(column)
+    }
+
     fn sum(&self) -> Option<f64> {
-        Some(self.0.iter().filter_map(|&x| x).filter(|&x| x).count() as f64)
+        Some(self.true_count() as f64)
     }
 
     fn min(&self) -> Option<f64> {
         if self.non_null_count() == 0 {
             return Some(0.0);
         }
-        // let has_false = self.0.iter().any(|&x| x == Some(false));
-        let has_false = self.0.contains(&Some(false));
+        let has_false = self.true_count() < self.non_null_count();
         Some(if has_false { 0.0 } else { 1.0 })
     }
 
@@ -251,8 +634,7 @@ impl ColumnArray for BooleanColumn {
         if self.non_null_count() == 0 {
             return Some(0.0);
         }
-        // let has_true = self.0.iter().any(|&x| x == Some(true));
-        let has_true = self.0.contains(&Some(true));
+        let has_true = self.true_count() > 0;
         Some(if has_true { 1.0 } else { 0.0 })
     }
 
@@ -261,32 +643,51 @@ impl ColumnArray for BooleanColumn {
         if non_nulls == 0 {
             return None;
         }
-        let sum = self.0.iter().filter_map(|&x| x).filter(|&x| x).count();
-        Some(sum as f64 / non_nulls as f64)
+        Some(self.true_count() as f64 / non_nulls as f64)
     }
 }
 
 pub fn parse_column(column: Vec<&str>) -> Box<dyn ColumnArray> {
     if let Some(array) = parse_integers(&column) {
-        return Box::new(IntegerColumn(array));
+        return Box::new(array);
     }
 
     if let Some(array) = parse_floats(&column) {
-        return Box::new(FloatColumn(array));
+        return Box::new(array);
     }
 
     if let Some(array) = parse_bools(&column) {
-        return Box::new(BooleanColumn(array));
+        return Box::new(array);
     }
 
-    Box::new(StringColumn(parse_strings(&column)))
+    let cells: Vec<Option<&str>> = column
+        .iter()
+        .map(|&cell| if is_null_cell(cell) { None } else { Some(cell) })
+        .collect();
+    if cells.iter().any(Option::is_some) {
+        if let Some(array) = parse_dates(&cells) {
+            return Box::new(array);
+        }
+        if let Some(array) = parse_datetimes(&cells) {
+            return Box::new(array);
+        }
+    }
+    if let Some(array) = try_categorical(&cells) {
+        return Box::new(array);
+    }
+
+    Box::new(parse_strings(&column))
 }
 
-fn parse_integers(raw_data: &[&str]) -> Option<Vec<Option<i64>>> {
-    let mut result = Vec::new();
+fn is_null_cell(cell: &str) -> bool {
+    cell.is_empty() || cell.eq_ignore_ascii_case("na")
+}
+
+fn parse_integers(raw_data: &[&str]) -> Option<IntegerColumn> {
+    let mut result = IntegerColumn::with_capacity(raw_data.len());
 
     for cell in raw_data {
-        if cell.is_empty() || cell.to_lowercase() == "na" {
+        if is_null_cell(cell) {
             result.push(None);
         } else {
             match cell.parse::<i64>() {
@@ -298,11 +699,11 @@ fn parse_integers(raw_data: &[&str]) -> Option<Vec<Option<i64>>> {
     Some(result)
 }
 
-fn parse_floats(raw_data: &[&str]) -> Option<Vec<Option<f64>>> {
-    let mut result = Vec::new();
+fn parse_floats(raw_data: &[&str]) -> Option<FloatColumn> {
+    let mut result = FloatColumn::with_capacity(raw_data.len());
 
     for cell in raw_data {
-        if cell.is_empty() || cell.to_lowercase() == "na" {
+        if is_null_cell(cell) {
             result.push(None);
         } else {
             match cell.parse::<f64>() {
@@ -314,24 +715,24 @@ fn parse_floats(raw_data: &[&str]) -> Option<Vec<Option<f64>>> {
     Some(result)
 }
 
-fn parse_strings(raw_data: &[&str]) -> Vec<Option<String>> {
-    raw_data
-        .iter()
-        .map(|x| {
-            if x.is_empty() || x.to_lowercase() == "na" {
-                None
-            } else {
-                Some(x.to_string())
-            }
-        })
-        .collect()
+fn parse_strings(raw_data: &[&str]) -> StringColumn {
+    let bytes = raw_data.iter().map(|x| x.len()).sum();
+    let mut result = StringColumn::with_capacity(raw_data.len(), bytes);
+    for cell in raw_data {
+        if is_null_cell(cell) {
+            result.push(None::<&str>);
+        } else {
+            result.push(Some(cell));
+        }
+    }
+    result
 }
 
-fn parse_bools(raw_data: &[&str]) -> Option<Vec<Option<bool>>> {
-    let mut result = Vec::new();
+fn parse_bools(raw_data: &[&str]) -> Option<BooleanColumn> {
+    let mut result = BooleanColumn::with_capacity(raw_data.len());
 
     for cell in raw_data {
-        if cell.is_empty() || cell.to_lowercase() == "na" {
+        if is_null_cell(cell) {
             result.push(None);
         } else {
             match cell.to_lowercase().as_str() {
@@ -344,32 +745,100 @@ fn parse_bools(raw_data: &[&str]) -> Option<Vec<Option<bool>>> {
     Some(result)
 }
 
+impl From<Vec<Option<i64>>> for IntegerColumn {
+    fn from(data: Vec<Option<i64>>) -> Self {
+        data.into_iter().collect()
+    }
+}
+
+impl FromIterator<Option<i64>> for IntegerColumn {
+    fn from_iter<I: IntoIterator<Item = Option<i64>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut column = IntegerColumn::with_capacity(iter.size_hint().0);
+        for value in iter {
+            column.push(value);
+        }
+        column
+    }
+}
+
+impl From<Vec<Option<f64>>> for FloatColumn {
+    fn from(data: Vec<Option<f64>>) -> Self {
+        data.into_iter().collect()
+    }
+}
+
+impl FromIterator<Option<f64>> for FloatColumn {
+    fn from_iter<I: IntoIterator<Item = Option<f64>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut column = FloatColumn::with_capacity(iter.size_hint().0);
+        for value in iter {
+            column.push(value);
+        }
+        column
+    }
+}
+
+impl From<Vec<Option<bool>>> for BooleanColumn {
+    fn from(data: Vec<Option<bool>>) -> Self {
+        data.into_iter().collect()
+    }
+}
+
+impl FromIterator<Option<bool>> for BooleanColumn {
+    fn from_iter<I: IntoIterator<Item = Option<bool>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut column = BooleanColumn::with_capacity(iter.size_hint().0);
+        for value in iter {
+            column.push(value);
+        }
+        column
+    }
+}
+
+impl From<Vec<Option<String>>> for StringColumn {
+    fn from(data: Vec<Option<String>>) -> Self {
+        data.into_iter().collect()
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<Option<S>> for StringColumn {
+    fn from_iter<I: IntoIterator<Item = Option<S>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut column = StringColumn::with_capacity(iter.size_hint().0, 0);
+        for value in iter {
+            column.push(value);
+        }
+        column
+    }
+}
+
 impl From<Vec<i64>> for Box<dyn ColumnArray> {
     fn from(data: Vec<i64>) -> Self {
-        let data: Vec<Option<i64>> = data.into_iter().map(Some).collect();
-        Box::new(IntegerColumn(data))
+        let data: IntegerColumn = data.into_iter().map(Some).collect();
+        Box::new(data)
     }
 }
 
 // Vec<Option<i64>> -> IntegerColumn
 impl From<Vec<Option<i64>>> for Box<dyn ColumnArray> {
     fn from(data: Vec<Option<i64>>) -> Self {
-        Box::new(IntegerColumn(data))
+        Box::new(IntegerColumn::from(data))
     }
 }
 
 // Vec<f64> -> FloatColumn
 impl From<Vec<f64>> for Box<dyn ColumnArray> {
     fn from(data: Vec<f64>) -> Self {
-        let data: Vec<Option<f64>> = data.into_iter().map(Some).collect();
-        Box::new(FloatColumn(data))
+        let data: FloatColumn = data.into_iter().map(Some).collect();
+        Box::new(data)
     }
 }
 
 impl From<Vec<String>> for Box<dyn ColumnArray> {
     fn from(data: Vec<String>) -> Self {
-        let data: Vec<Option<String>> = data.into_iter().map(Some).collect();
-        Box::new(StringColumn(data))
+        let data: StringColumn = data.into_iter().map(Some).collect();
+        Box::new(data)
     }
 }
 
@@ -381,34 +850,112 @@ impl From<&[i64]> for Box<dyn ColumnArray> {
 
 impl From<Vec<Option<f64>>> for Box<dyn ColumnArray> {
     fn from(data: Vec<Option<f64>>) -> Self {
-        Box::new(FloatColumn(data))
+        Box::new(FloatColumn::from(data))
     }
 }
 
 impl From<Vec<Option<String>>> for Box<dyn ColumnArray> {
     fn from(data: Vec<Option<String>>) -> Self {
-        Box::new(StringColumn(data))
+        Box::new(StringColumn::from(data))
     }
 }
 
 // Vec<bool> -> BooleanColumn
 impl From<Vec<bool>> for Box<dyn ColumnArray> {
     fn from(data: Vec<bool>) -> Self {
-        let data: Vec<Option<bool>> = data.into_iter().map(Some).collect();
-        Box::new(BooleanColumn(data))
+        let data: BooleanColumn = data.into_iter().map(Some).collect();
+        Box::new(data)
     }
 }
 
 // Vec<Option<bool>> -> BooleanColumn
 impl From<Vec<Option<bool>>> for Box<dyn ColumnArray> {
     fn from(data: Vec<Option<bool>>) -> Self {
-        Box::new(BooleanColumn(data))
+        Box::new(BooleanColumn::from(data))
     }
 }
 
 impl From<Vec<usize>> for Box<dyn ColumnArray> {
     fn from(data: Vec<usize>) -> Self {
-        let input = data.iter().map(|&x| Some(x as i64)).collect();
-        Box::new(IntegerColumn(input))
+        let input: IntegerColumn = data.iter().map(|&x| Some(x as i64)).collect();
+        Box::new(input)
+    }
+}
+
+/// Builds a column from loose cell values, typed by the values present.
+///
+/// Integers mixed with floats become a float column; any string makes the
+/// whole column strings; a column with no values at all is a float column.
+/// Dates (or datetimes) and durations keep their type when they are the only
+/// values.
+impl From<Vec<Option<CellValue>>> for Box<dyn ColumnArray> {
+    fn from(data: Vec<Option<CellValue>>) -> Self {
+        let values = || data.iter().flatten().filter(|value| !value.is_null());
+        let has = |kind: fn(&CellValue) -> bool| values().any(kind);
+
+        if values().count() > 0 && values().all(|v| matches!(v, CellValue::Date(_))) {
+            let cells: Vec<Option<&str>> = data
+                .iter()
+                .map(|value| match value {
+                    Some(CellValue::Date(s)) => Some(s.as_str()),
+                    _ => None,
+                })
+                .collect();
+            if let Some(column) = parse_dates(&cells) {
+                return Box::new(column);
+            }
+            if let Some(column) = parse_datetimes(&cells) {
+                return Box::new(column);
+            }
+        }
+        if values().count() > 0 && values().all(|v| matches!(v, CellValue::Duration(_))) {
+            let mut column = DurationColumn::with_capacity(data.len());
+            for value in &data {
+                column.push_micros(match value {
+                    Some(CellValue::Duration(micros)) => Some(*micros),
+                    _ => None,
+                });
+            }
+            return Box::new(column);
+        }
+        if has(|v| matches!(v, CellValue::Str(_) | CellValue::Date(_))) {
+            let column: StringColumn = data
+                .iter()
+                .map(|value| match value {
+                    None | Some(CellValue::Null) => None,
+                    Some(value) => Some(value.to_string()),
+                })
+                .collect();
+            Box::new(column)
+        } else if values().count() > 0 && values().all(|v| matches!(v, CellValue::Boolean(_))) {
+            let column: BooleanColumn = data
+                .iter()
+                .map(|value| match value {
+                    Some(CellValue::Boolean(b)) => Some(*b),
+                    _ => None,
+                })
+                .collect();
+            Box::new(column)
+        } else if values().count() > 0 && values().all(|v| matches!(v, CellValue::Integer(_))) {
+            let column: IntegerColumn = data
+                .iter()
+                .map(|value| match value {
+                    Some(CellValue::Integer(n)) => Some(*n),
+                    _ => None,
+                })
+                .collect();
+            Box::new(column)
+        } else {
+            let column: FloatColumn = data
+                .iter()
+                .map(|value| match value {
+                    Some(CellValue::Integer(n)) => Some(*n as f64),
+                    Some(CellValue::Float(n)) => Some(*n),
+                    Some(CellValue::Boolean(b)) => Some(*b as i64 as f64),
+                    _ => None,
+                })
+                .collect();
+            Box::new(column)
+        }
     }
 }
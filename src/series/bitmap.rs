@@ -0,0 +1,120 @@
+/// A packed sequence of bits, used as the validity mask of a column
+/// (set = value present) and as the value buffer of boolean columns.
+///
+/// The number of unset bits is tracked on every write, so null counts are O(1).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bitmap {
+    words: Vec<u64>,
+    len: usize,
+    unset: usize,
+}
+
+impl Bitmap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(bits: usize) -> Self {
+        Bitmap {
+            words: Vec::with_capacity(bits.div_ceil(64)),
+            len: 0,
+            unset: 0,
+        }
+    }
+
+    /// A bitmap of `len` bits that are all `value`.
+    pub fn filled(len: usize, value: bool) -> Self {
+        let word = if value { u64::MAX } else { 0 };
+        let mut words = vec![word; len.div_ceil(64)];
+        if value && !len.is_multiple_of(64) {
+            if let Some(last) = words.last_mut() {
+                *last = (1u64 << (len % 64)) - 1;
+            }
+        }
+        Bitmap {
+            words,
+            len,
+            unset: if value { 0 } else { len },
+        }
+    }
+
+    pub fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(64) {
+            self.words.push(0);
+        }
+        if bit {
+            self.words[self.len / 64] |= 1 << (self.len % 64);
+        } else {
+            self.unset += 1;
+        }
+        self.len += 1;
+    }
+
+    /// Returns the bit at `index`; out-of-range indices read as unset.
+    pub fn get(&self, index: usize) -> bool {
+        index < self.len && self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    pub fn set(&mut self, index: usize, bit: bool) {
+        assert!(index < self.len, "bit index {} out of range", index);
+        let was_set = self.get(index);
+        if bit {
+            self.words[index / 64] |= 1 << (index % 64);
+        } else {
+            self.words[index / 64] &= !(1 << (index % 64));
+        }
+        match (was_set, bit) {
+            (true, false) => self.unset += 1,
+            (false, true) => self.unset -= 1,
+            _ => {}
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn count_set(&self) -> usize {
+        self.len - self.unset
+    }
+
+    pub fn count_unset(&self) -> usize {
+        self.unset
+    }
+
+    /// Bitwise AND of two bitmaps of the same length.
+    pub fn and(&self, other: &Bitmap) -> Bitmap {
+        assert_eq!(self.len, other.len, "bitmaps must have the same length");
+        let words: Vec<u64> = self
+            .words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| a & b)
+            .collect();
+        let set: usize = words.iter().map(|word| word.count_ones() as usize).sum();
+        Bitmap {
+            words,
+            len: self.len,
+            unset: self.len - set,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(move |index| self.get(index))
+    }
+}
+
+impl FromIterator<bool> for Bitmap {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut bitmap = Bitmap::with_capacity(iter.size_hint().0);
+        for bit in iter {
+            bitmap.push(bit);
+        }
+        bitmap
+    }
+}
@@ -0,0 +1,206 @@
+use crate::frame::DataFrameError;
+use crate::series::temporal::{parse_date, parse_datetime, DATETIME_FORMAT, DATE_FORMAT};
+use crate::series::{
+    BooleanColumn, CategoricalColumn, ColumnArray, DateColumn, DateTimeColumn, DurationColumn,
+    FloatColumn, IntegerColumn, StringColumn,
+};
+use crate::{CellValue, Dtype};
+
+/// Failing rows quoted in a strict cast error.
+const SHOWN_FAILURES: usize = 5;
+
+pub(crate) fn cast<C: ColumnArray + ?Sized>(
+    column: &C,
+    dtype: Dtype,
+    strict: bool,
+) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+    if column.dtype() == dtype {
+        return Ok(column.clone_box());
+    }
+    let mut failures: Vec<(usize, CellValue)> = Vec::new();
+    let mut convert = |index: usize, cast: fn(&CellValue) -> Option<CellValue>| {
+        let value = column.get(index)?;
+        let converted = cast(&value);
+        if converted.is_none() {
+            failures.push((index, value));
+        }
+        converted
+    };
+
+    let len = column.len();
+    let result: Box<dyn ColumnArray> = match dtype {
+        Dtype::Integer => Box::new(
+            (0..len)
+                .map(|i| match convert(i, to_integer) {
+                    Some(CellValue::Integer(n)) => Some(n),
+                    _ => None,
+                })
+                .collect::<IntegerColumn>(),
+        ),
+        Dtype::Float => Box::new(
+            (0..len)
+                .map(|i| match convert(i, to_float) {
+                    Some(CellValue::Float(x)) => Some(x),
+                    _ => None,
+                })
+                .collect::<FloatColumn>(),
+        ),
+        Dtype::Boolean => Box::new(
+            (0..len)
+                .map(|i| match convert(i, to_boolean) {
+                    Some(CellValue::Boolean(b)) => Some(b),
+                    _ => None,
+                })
+                .collect::<BooleanColumn>(),
+        ),
+        Dtype::Str => Box::new(
+            (0..len)
+                .map(|i| column.get(i).map(|value| value.to_string()))
+                .collect::<StringColumn>(),
+        ),
+        Dtype::Categorical => Box::new(
+            (0..len)
+                .map(|i| column.get(i).map(|value| value.to_string()))
+                .collect::<CategoricalColumn>(),
+        ),
+        Dtype::Date if reads_as_date(&column.dtype()) => Box::new(
+            (0..len)
+                .map(|i| match convert(i, to_date) {
+                    Some(CellValue::Date(s)) => parse_date(&s),
+                    _ => None,
+                })
+                .collect::<DateColumn>(),
+        ),
+        Dtype::DateTime if reads_as_date(&column.dtype()) => Box::new(
+            (0..len)
+                .map(|i| match convert(i, to_datetime) {
+                    Some(CellValue::Date(s)) => parse_datetime(&s),
+                    _ => None,
+                })
+                .collect::<DateTimeColumn>(),
+        ),
+        Dtype::Duration if column.dtype().is_numeric() => {
+            let mut result = DurationColumn::with_capacity(len);
+            for i in 0..len {
+                result.push_micros(match convert(i, to_integer) {
+                    Some(CellValue::Integer(n)) => Some(n),
+                    _ => None,
+                });
+            }
+            Box::new(result)
+        }
+        dtype => {
+            return Err(DataFrameError::TypeError(format!(
+                "cannot cast a {:?} column to {:?}",
+                column.dtype(),
+                dtype
+            )))
+        }
+    };
+
+    if strict && !failures.is_empty() {
+        let shown: Vec<String> = failures
+            .iter()
+            .take(SHOWN_FAILURES)
+            .map(|(row, value)| format!("row {} ('{}')", row, value))
+            .collect();
+        return Err(DataFrameError::TypeError(format!(
+            "cannot cast {} of {} values to {:?}: {}{}",
+            failures.len(),
+            column.non_null_count(),
+            dtype,
+            shown.join(", "),
+            if failures.len() > SHOWN_FAILURES {
+                ", ..."
+            } else {
+                ""
+            }
+        )));
+    }
+    Ok(result)
+}
+
+/// Dates and datetimes are parsed from text or converted between each other.
+fn reads_as_date(dtype: &Dtype) -> bool {
+    matches!(
+        dtype,
+        Dtype::Str | Dtype::Categorical | Dtype::Date | Dtype::DateTime
+    )
+}
+
+/// Floats are truncated toward zero; NaN, infinities and values outside the
+/// `i64` range fail, as does text that is not an integer.
+fn to_integer(value: &CellValue) -> Option<CellValue> {
+    let n = match value {
+        CellValue::Integer(n) => *n,
+        CellValue::Float(x) => {
+            let truncated = x.trunc();
+            // i64::MAX is not exactly representable; 2^63 is the first float above it
+            if !truncated.is_finite()
+                || truncated < i64::MIN as f64
+                || truncated >= -(i64::MIN as f64)
+            {
+                return None;
+            }
+            truncated as i64
+        }
+        CellValue::Boolean(b) => *b as i64,
+        CellValue::Duration(micros) => *micros,
+        CellValue::Str(s) | CellValue::Date(s) => s.trim().parse().ok()?,
+        CellValue::Null => return None,
+    };
+    Some(CellValue::Integer(n))
+}
+
+fn to_float(value: &CellValue) -> Option<CellValue> {
+    let x = match value {
+        CellValue::Integer(n) => *n as f64,
+        CellValue::Float(x) => *x,
+        CellValue::Boolean(b) => *b as i64 as f64,
+        CellValue::Duration(micros) => *micros as f64,
+        CellValue::Str(s) | CellValue::Date(s) => s.trim().parse().ok()?,
+        CellValue::Null => return None,
+    };
+    Some(CellValue::Float(x))
+}
+
+/// Numbers are true when non-zero (NaN fails); text must be one of the
+/// spellings CSV inference reads as a boolean.
+fn to_boolean(value: &CellValue) -> Option<CellValue> {
+    let b = match value {
+        CellValue::Integer(n) => *n != 0,
+        CellValue::Float(x) if x.is_nan() => return None,
+        CellValue::Float(x) => *x != 0.0,
+        CellValue::Boolean(b) => *b,
+        CellValue::Str(s) | CellValue::Date(s) => match s.trim().to_lowercase().as_str() {
+            "true" | "1" | "yes" => true,
+            "false" | "0" | "no" => false,
+            _ => return None,
+        },
+        CellValue::Duration(_) | CellValue::Null => return None,
+    };
+    Some(CellValue::Boolean(b))
+}
+
+/// Text must be a date, or a datetime whose time of day is dropped.
+fn to_date(value: &CellValue) -> Option<CellValue> {
+    let date = match value {
+        CellValue::Str(s) | CellValue::Date(s) => {
+            parse_date(s).or_else(|| parse_datetime(s).map(|datetime| datetime.date()))?
+        }
+        _ => return None,
+    };
+    Some(CellValue::Date(date.format(DATE_FORMAT).to_string()))
+}
+
+/// Text must be a datetime, or a date taken at midnight.
+fn to_datetime(value: &CellValue) -> Option<CellValue> {
+    let datetime = match value {
+        CellValue::Str(s) | CellValue::Date(s) => parse_datetime(s)
+            .or_else(|| parse_date(s).map(|date| date.and_time(chrono::NaiveTime::MIN)))?,
+        _ => return None,
+    };
+    Some(CellValue::Date(
+        datetime.format(DATETIME_FORMAT).to_string(),
+    ))
+}
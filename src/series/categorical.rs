@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+use crate::series::array::value_counts_frame;
+use crate::series::{Bitmap, ColumnArray};
+use crate::{CellValue, DataFrame, Dtype};
+
+/// Most distinct values a string column may have for inference to dictionary-encode it.
+pub const CATEGORICAL_MAX_CATEGORIES: usize = 256;
+
+/// Minimum average number of rows per distinct value for inference to dictionary-encode.
+pub const CATEGORICAL_MIN_REPEATS: usize = 2;
+
+/// Dictionary-encoded strings: every distinct value is stored once in
+/// `categories` and rows refer to it by index.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CategoricalColumn {
+    categories: Vec<String>,
+    codes: Vec<u32>,
+    validity: Bitmap,
+}
+
+impl CategoricalColumn {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a column from a dictionary and per-row codes (`None` = null).
+    ///
+    /// Returns `None` if a code does not point into `categories`.
+    pub fn from_parts(categories: Vec<String>, codes: Vec<Option<u32>>) -> Option<Self> {
+        let mut column = CategoricalColumn {
+            categories,
+            codes: Vec::with_capacity(codes.len()),
+            validity: Bitmap::with_capacity(codes.len()),
+        };
+        for code in codes {
+            if let Some(code) = code {
+                if code as usize >= column.categories.len() {
+                    return None;
+                }
+            }
+            column.codes.push(code.unwrap_or_default());
+            column.validity.push(code.is_some());
+        }
+        Some(column)
+    }
+
+    pub fn categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    /// The raw code buffer, including the placeholders behind nulls.
+    pub fn codes(&self) -> &[u32] {
+        &self.codes
+    }
+
+    pub fn code(&self, index: usize) -> Option<u32> {
+        if self.validity.get(index) {
+            Some(self.codes[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn value(&self, index: usize) -> Option<&str> {
+        self.code(index)
+            .map(|code| self.categories[code as usize].as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Option<&str>> + '_ {
+        (0..self.codes.len()).map(move |index| self.value(index))
+    }
+
+    pub fn validity(&self) -> &Bitmap {
+        &self.validity
+    }
+
+    /// Occurrences of every category, in dictionary order.
+    ///
+    /// Counting works on codes, so no string is hashed or compared, and
+    /// categories that no row uses are reported with a count of zero.
+    pub fn category_counts(&self) -> Vec<(&str, usize)> {
+        let mut counts = vec![0; self.categories.len()];
+        for code in (0..self.codes.len()).filter_map(|index| self.code(index)) {
+            counts[code as usize] += 1;
+        }
+        self.categories
+            .iter()
+            .map(|category| category.as_str())
+            .zip(counts)
+            .collect()
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<Option<S>> for CategoricalColumn {
+    fn from_iter<I: IntoIterator<Item = Option<S>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut column = CategoricalColumn {
+            categories: Vec::new(),
+            codes: Vec::with_capacity(iter.size_hint().0),
+            validity: Bitmap::with_capacity(iter.size_hint().0),
+        };
+        let mut lookup: HashMap<String, u32> = HashMap::new();
+
+        for value in iter {
+            let code = value.map(|value| {
+                let value = value.as_ref();
+                match lookup.get(value) {
+                    Some(&code) => code,
+                    None => {
+                        let code = column.categories.len() as u32;
+                        column.categories.push(value.to_string());
+                        lookup.insert(value.to_string(), code);
+                        code
+                    }
+                }
+            });
+            column.codes.push(code.unwrap_or_default());
+            column.validity.push(code.is_some());
+        }
+        column
+    }
+}
+
+impl ColumnArray for CategoricalColumn {
+    fn to_json(&self) -> Vec<serde_json::Value> {
+        self.iter()
+            .map(|x| match x {
+                Some(x) => json!(x),
+                None => Value::Null,
+            })
+            .collect()
+    }
+
+    fn dtype(&self) -> Dtype {
+        Dtype::Categorical
+    }
+
+    fn get(&self, index: usize) -> Option<CellValue> {
+        self.value(index).map(|x| CellValue::Str(x.to_string()))
+    }
+
+    fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    fn null_count(&self) -> usize {
+        self.validity.count_unset()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn ColumnArray> {
+        Box::new(self.clone())
+    }
+
+    fn take(&self, indices: &[usize]) -> Box<dyn ColumnArray> {
+        let codes = indices.iter().map(|&index| self.code(index)).collect();
+        Box::new(
+            CategoricalColumn::from_parts(self.categories.clone(), codes)
+                .expect("codes come from this column's dictionary"),
+        )
+    }
+
+    /// Counts per category, including categories that no row uses.
+    fn value_counts(&self, normalize: bool, sort: bool, dropna: bool) -> DataFrame {
+        let mut groups: Vec<(Option<u32>, usize)> = self
+            .category_counts()
+            .into_iter()
+            .enumerate()
+            .map(|(code, (_, count))| (Some(code as u32), count))
+            .collect();
+        if !dropna && self.null_count() > 0 {
+            groups.push((None, self.null_count()));
+        }
+        if sort {
+            groups.sort_by_key(|group| std::cmp::Reverse(group.1));
+        }
+
+        let (codes, counts): (Vec<Option<u32>>, Vec<usize>) = groups.into_iter().unzip();
+        let values = CategoricalColumn::from_parts(self.categories.clone(), codes)
+            .expect("codes come from this column's dictionary");
+        value_counts_frame(Box::new(values), counts, normalize)
+    }
+}
+
+/// Dictionary-encodes `values` if they have few enough distinct values,
+/// giving up as soon as the category limit is exceeded.
+pub(crate) fn try_categorical(values: &[Option<&str>]) -> Option<CategoricalColumn> {
+    let non_null = values.iter().flatten().count();
+    let limit = CATEGORICAL_MAX_CATEGORIES.min(non_null / CATEGORICAL_MIN_REPEATS);
+
+    let mut lookup: HashMap<&str, u32> = HashMap::new();
+    let mut categories = Vec::new();
+    let mut codes = Vec::with_capacity(values.len());
+
+    for value in values {
+        let code = match value {
+            None => None,
+            Some(value) => Some(match lookup.get(value) {
+                Some(&code) => code,
+                None => {
+                    if categories.len() >= limit {
+                        return None;
+                    }
+                    let code = categories.len() as u32;
+                    categories.push(value.to_string());
+                    lookup.insert(value, code);
+                    code
+                }
+            }),
+        };
+        codes.push(code);
+    }
+
+    CategoricalColumn::from_parts(categories, codes)
+}
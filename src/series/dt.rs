@@ -0,0 +1,310 @@
+//! Date and time operations, reached through `.dt()` on a date or datetime
+//! column.
+//!
+//! Every operation is null-aware: a null row stays null in the result, as
+//! does a row whose result falls outside the representable range.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike};
+
+use crate::frame::DataFrameError;
+use crate::series::temporal::{parse_date, parse_datetime};
+use crate::series::{ColumnArray, DateColumn, DateTimeColumn, DurationColumn, IntegerColumn};
+use crate::{CellValue, Dtype};
+
+/// A calendar or clock unit that [`DateTimeNamespace::truncate`] rounds down to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Year,
+    Quarter,
+    Month,
+    /// Weeks start on Monday.
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Period::Year => "year",
+            Period::Quarter => "quarter",
+            Period::Month => "month",
+            Period::Week => "week",
+            Period::Day => "day",
+            Period::Hour => "hour",
+            Period::Minute => "minute",
+            Period::Second => "second",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Period {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "year" => Ok(Period::Year),
+            "quarter" => Ok(Period::Quarter),
+            "month" => Ok(Period::Month),
+            "week" => Ok(Period::Week),
+            "day" => Ok(Period::Day),
+            "hour" => Ok(Period::Hour),
+            "minute" => Ok(Period::Minute),
+            "second" => Ok(Period::Second),
+            _ => Err(format!(
+                "unknown period '{}', expected year, quarter, month, week, day, hour, minute or second",
+                s
+            )),
+        }
+    }
+}
+
+/// The date and time operations of one column; see [`DateColumn::dt`].
+#[derive(Debug, Clone, Copy)]
+pub struct DateTimeNamespace<'a> {
+    column: &'a dyn ColumnArray,
+}
+
+impl DateColumn {
+    /// Date and time operations on this column.
+    pub fn dt(&self) -> DateTimeNamespace<'_> {
+        DateTimeNamespace { column: self }
+    }
+}
+
+impl DateTimeColumn {
+    /// Date and time operations on this column.
+    pub fn dt(&self) -> DateTimeNamespace<'_> {
+        DateTimeNamespace { column: self }
+    }
+}
+
+impl dyn ColumnArray + '_ {
+    /// Date and time operations on a date or datetime column.
+    pub fn dt(&self) -> Result<DateTimeNamespace<'_>, DataFrameError> {
+        match self.dtype() {
+            Dtype::Date | Dtype::DateTime => Ok(DateTimeNamespace { column: self }),
+            dtype => Err(DataFrameError::TypeError(format!(
+                "date operations need a date or datetime column, got {:?}",
+                dtype
+            ))),
+        }
+    }
+}
+
+impl DateTimeNamespace<'_> {
+    /// The values as datetimes; dates are taken at midnight.
+    fn values(&self) -> Vec<Option<NaiveDateTime>> {
+        datetimes(self.column)
+    }
+
+    fn is_date(&self) -> bool {
+        self.column.dtype() == Dtype::Date
+    }
+
+    fn map_integer(&self, f: impl Fn(NaiveDateTime) -> i64) -> Box<dyn ColumnArray> {
+        let column: IntegerColumn = self
+            .values()
+            .into_iter()
+            .map(|value| value.map(&f))
+            .collect();
+        Box::new(column)
+    }
+
+    /// A column of this column's type, or a datetime column when `as_date`
+    /// is false.
+    fn build(
+        values: impl Iterator<Item = Option<NaiveDateTime>>,
+        as_date: bool,
+    ) -> Box<dyn ColumnArray> {
+        if as_date {
+            Box::new(
+                values
+                    .map(|value| value.map(|datetime| datetime.date()))
+                    .collect::<DateColumn>(),
+            )
+        } else {
+            Box::new(values.collect::<DateTimeColumn>())
+        }
+    }
+
+    pub fn year(&self) -> Box<dyn ColumnArray> {
+        self.map_integer(|value| value.year() as i64)
+    }
+
+    /// The quarter of the year, 1 to 4.
+    pub fn quarter(&self) -> Box<dyn ColumnArray> {
+        self.map_integer(|value| value.month0() as i64 / 3 + 1)
+    }
+
+    /// The month, 1 to 12.
+    pub fn month(&self) -> Box<dyn ColumnArray> {
+        self.map_integer(|value| value.month() as i64)
+    }
+
+    /// The day of the month, 1 to 31.
+    pub fn day(&self) -> Box<dyn ColumnArray> {
+        self.map_integer(|value| value.day() as i64)
+    }
+
+    /// The ISO 8601 day of the week, Monday = 1 to Sunday = 7.
+    pub fn weekday(&self) -> Box<dyn ColumnArray> {
+        self.map_integer(|value| value.weekday().number_from_monday() as i64)
+    }
+
+    /// The hour, 0 to 23; always 0 for dates.
+    pub fn hour(&self) -> Box<dyn ColumnArray> {
+        self.map_integer(|value| value.hour() as i64)
+    }
+
+    pub fn minute(&self) -> Box<dyn ColumnArray> {
+        self.map_integer(|value| value.minute() as i64)
+    }
+
+    pub fn second(&self) -> Box<dyn ColumnArray> {
+        self.map_integer(|value| value.second() as i64)
+    }
+
+    /// The calendar date, without the time of day.
+    pub fn date(&self) -> Box<dyn ColumnArray> {
+        Self::build(self.values().into_iter(), true)
+    }
+
+    /// Each value rounded down to the start of its `period`, keeping the
+    /// column type; dates are already whole days, so finer periods leave
+    /// them as they are.
+    pub fn truncate(&self, period: Period) -> Box<dyn ColumnArray> {
+        let values = self
+            .values()
+            .into_iter()
+            .map(|value| value.and_then(|value| truncate(value, period)));
+        Self::build(values, self.is_date())
+    }
+
+    /// Each value moved by `delta`, which may be negative.
+    ///
+    /// A date column stays dates when `delta` is whole days and becomes
+    /// datetimes otherwise.
+    pub fn offset(&self, delta: TimeDelta) -> Box<dyn ColumnArray> {
+        let values = self
+            .values()
+            .into_iter()
+            .map(|value| value?.checked_add_signed(delta));
+        Self::build(values, self.is_date() && is_whole_days(delta))
+    }
+
+    /// Each value moved forward by the duration in the same row; see
+    /// [`offset`](Self::offset) for the result type.
+    pub fn add(&self, durations: &dyn ColumnArray) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        self.shift(durations, false)
+    }
+
+    /// Each value moved back by the duration in the same row; see
+    /// [`offset`](Self::offset) for the result type.
+    pub fn sub(&self, durations: &dyn ColumnArray) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        self.shift(durations, true)
+    }
+
+    fn shift(
+        &self,
+        durations: &dyn ColumnArray,
+        back: bool,
+    ) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        let durations = durations
+            .as_any()
+            .downcast_ref::<DurationColumn>()
+            .ok_or_else(|| {
+                DataFrameError::TypeError(format!(
+                    "dates can only be shifted by a Duration column, got {:?}",
+                    durations.dtype()
+                ))
+            })?;
+        check_length(self.column, durations)?;
+        let deltas: Vec<Option<TimeDelta>> = durations
+            .iter()
+            .map(|delta| if back { delta.map(|d| -d) } else { delta })
+            .collect();
+        let whole_days = deltas.iter().flatten().all(|&delta| is_whole_days(delta));
+        let values = self
+            .values()
+            .into_iter()
+            .zip(deltas)
+            .map(|(value, delta)| value?.checked_add_signed(delta?));
+        Ok(Self::build(values, self.is_date() && whole_days))
+    }
+
+    /// The time from each value of `other` to the value in the same row of
+    /// this column, as durations; dates count from midnight.
+    pub fn diff(&self, other: &dyn ColumnArray) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        let other = other.dt()?;
+        check_length(self.column, other.column)?;
+        let column: DurationColumn = self
+            .values()
+            .into_iter()
+            .zip(other.values())
+            .map(|(end, start)| Some(end? - start?))
+            .collect();
+        Ok(Box::new(column))
+    }
+}
+
+fn datetimes(column: &dyn ColumnArray) -> Vec<Option<NaiveDateTime>> {
+    if let Some(dates) = column.as_any().downcast_ref::<DateColumn>() {
+        return dates.iter().map(|date| date.map(midnight)).collect();
+    }
+    if let Some(datetimes) = column.as_any().downcast_ref::<DateTimeColumn>() {
+        return datetimes.iter().collect();
+    }
+    (0..column.len())
+        .map(|index| match column.get(index)? {
+            CellValue::Date(s) | CellValue::Str(s) => {
+                parse_datetime(&s).or_else(|| parse_date(&s).map(midnight))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn midnight(date: NaiveDate) -> NaiveDateTime {
+    date.and_time(NaiveTime::MIN)
+}
+
+fn truncate(value: NaiveDateTime, period: Period) -> Option<NaiveDateTime> {
+    let date = value.date();
+    let time = |hour, minute, second| date.and_hms_opt(hour, minute, second);
+    match period {
+        Period::Year => NaiveDate::from_ymd_opt(date.year(), 1, 1).map(midnight),
+        Period::Quarter => {
+            NaiveDate::from_ymd_opt(date.year(), date.month0() / 3 * 3 + 1, 1).map(midnight)
+        }
+        Period::Month => NaiveDate::from_ymd_opt(date.year(), date.month(), 1).map(midnight),
+        Period::Week => date
+            .checked_sub_signed(TimeDelta::days(date.weekday().num_days_from_monday() as i64))
+            .map(midnight),
+        Period::Day => Some(midnight(date)),
+        Period::Hour => time(value.hour(), 0, 0),
+        Period::Minute => time(value.hour(), value.minute(), 0),
+        Period::Second => time(value.hour(), value.minute(), value.second()),
+    }
+}
+
+fn is_whole_days(delta: TimeDelta) -> bool {
+    delta.subsec_nanos() == 0 && delta.num_seconds() % 86_400 == 0
+}
+
+fn check_length(column: &dyn ColumnArray, other: &dyn ColumnArray) -> Result<(), DataFrameError> {
+    if column.len() != other.len() {
+        return Err(DataFrameError::ColumnsLengthMismatch {
+            column: "right operand".to_string(),
+            expected: column.len(),
+            actual: other.len(),
+        });
+    }
+    Ok(())
+}
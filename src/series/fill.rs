@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::frame::DataFrameError;
+use crate::scalar::ValueKey;
+use crate::series::histogram::{numeric_values, quantile_sorted};
+use crate::series::{CategoricalColumn, ColumnArray, FloatColumn, StringColumn};
+use crate::{CellValue, Dtype};
+
+/// What [`ColumnArray::fill_null`] puts in place of a null.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FillStrategy {
+    /// The same value everywhere.
+    Value(CellValue),
+    /// The closest earlier non-null value; leading nulls stay null.
+    Forward,
+    /// The closest later non-null value; trailing nulls stay null.
+    Backward,
+    /// The mean of the non-null values (numeric columns).
+    Mean,
+    /// The median of the non-null values (numeric columns).
+    Median,
+    /// The most frequent non-null value, the earliest one on ties.
+    Mode,
+}
+
+impl FillStrategy {
+    /// Whether this strategy can fill a column of `dtype`.
+    ///
+    /// Numeric columns take integer and float values, boolean columns booleans,
+    /// string and categorical columns any value, as text, date and datetime
+    /// columns dates or text that parses as one, and duration columns
+    /// durations.
+    pub fn supports(&self, dtype: &Dtype) -> bool {
+        match self {
+            FillStrategy::Forward | FillStrategy::Backward | FillStrategy::Mode => true,
+            FillStrategy::Mean | FillStrategy::Median => dtype.is_numeric(),
+            FillStrategy::Value(value) => matches!(
+                (dtype, value),
+                (_, CellValue::Null)
+                    | (Dtype::Str | Dtype::Categorical, _)
+                    | (
+                        Dtype::Integer | Dtype::Float,
+                        CellValue::Integer(_) | CellValue::Float(_)
+                    )
+                    | (Dtype::Boolean, CellValue::Boolean(_))
+                    | (
+                        Dtype::Date | Dtype::DateTime,
+                        CellValue::Date(_) | CellValue::Str(_)
+                    )
+                    | (Dtype::Duration, CellValue::Duration(_))
+            ),
+        }
+    }
+}
+
+impl fmt::Display for FillStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FillStrategy::Value(value) => write!(f, "value {}", value),
+            FillStrategy::Forward => write!(f, "forward"),
+            FillStrategy::Backward => write!(f, "backward"),
+            FillStrategy::Mean => write!(f, "mean"),
+            FillStrategy::Median => write!(f, "median"),
+            FillStrategy::Mode => write!(f, "mode"),
+        }
+    }
+}
+
+/// Parses the strategies that need no value; a constant is given as
+/// `FillStrategy::Value`.
+impl FromStr for FillStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "forward" | "ffill" => Ok(FillStrategy::Forward),
+            "backward" | "bfill" => Ok(FillStrategy::Backward),
+            "mean" => Ok(FillStrategy::Mean),
+            "median" => Ok(FillStrategy::Median),
+            "mode" => Ok(FillStrategy::Mode),
+            _ => Err(format!(
+                "unknown fill strategy '{}', expected forward, backward, mean, median or mode",
+                s
+            )),
+        }
+    }
+}
+
+pub(crate) fn fill_null<C: ColumnArray + ?Sized>(
+    column: &C,
+    strategy: &FillStrategy,
+) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+    let dtype = column.dtype();
+    if !strategy.supports(&dtype) {
+        return Err(DataFrameError::TypeError(format!(
+            "cannot fill a {:?} column with {}",
+            dtype, strategy
+        )));
+    }
+    let len = column.len();
+    let is_null = |index: usize| column.get(index).is_none();
+
+    // Strategies that copy an existing value gather it with `take`, which
+    // keeps the column type; an out-of-range index leaves the null in place.
+    let indices: Vec<usize> = match strategy {
+        FillStrategy::Forward => {
+            let mut last = len;
+            (0..len)
+                .map(|index| {
+                    if !is_null(index) {
+                        last = index;
+                    }
+                    last
+                })
+                .collect()
+        }
+        FillStrategy::Backward => {
+            let mut next = len;
+            let mut indices: Vec<usize> = (0..len)
+                .rev()
+                .map(|index| {
+                    if !is_null(index) {
+                        next = index;
+                    }
+                    next
+                })
+                .collect();
+            indices.reverse();
+            indices
+        }
+        FillStrategy::Mode => {
+            let mode = mode_index(column).unwrap_or(len);
+            (0..len)
+                .map(|index| if is_null(index) { mode } else { index })
+                .collect()
+        }
+        FillStrategy::Mean | FillStrategy::Median => {
+            let mut values = numeric_values(column)?;
+            let fill = if values.is_empty() {
+                None
+            } else if *strategy == FillStrategy::Mean {
+                Some(values.iter().sum::<f64>() / values.len() as f64)
+            } else {
+                values.sort_by(|a, b| a.total_cmp(b));
+                Some(quantile_sorted(&values, 0.5))
+            };
+            let filled: FloatColumn = (0..len)
+                .map(|index| column.get_f64(index).or(fill))
+                .collect();
+            return Ok(Box::new(filled));
+        }
+        FillStrategy::Value(value) => return fill_value(column, value),
+    };
+    Ok(column.take(&indices))
+}
+
+/// The first row holding the most frequent non-null value.
+fn mode_index<C: ColumnArray + ?Sized>(column: &C) -> Option<usize> {
+    let mut counts: HashMap<ValueKey, (usize, usize)> = HashMap::new();
+    for index in 0..column.len() {
+        if let Some(value) = column.get(index) {
+            counts
+                .entry(ValueKey::from(Some(value)))
+                .or_insert((0, index))
+                .0 += 1;
+        }
+    }
+    counts
+        .into_values()
+        .max_by(|(count_a, first_a), (count_b, first_b)| {
+            count_a.cmp(count_b).then(first_b.cmp(first_a))
+        })
+        .map(|(_, first)| first)
+}
+
+fn fill_value<C: ColumnArray + ?Sized>(
+    column: &C,
+    value: &CellValue,
+) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+    let values = (0..column.len()).map(|index| column.get(index).or_else(|| Some(value.clone())));
+    let text = |value: Option<CellValue>| value.filter(|v| !v.is_null()).map(|v| v.to_string());
+    Ok(match column.dtype() {
+        Dtype::Str => Box::new(values.map(text).collect::<StringColumn>()),
+        Dtype::Categorical => Box::new(values.map(text).collect::<CategoricalColumn>()),
+        // the value may be text, so dates are read back from it
+        dtype @ (Dtype::Date | Dtype::DateTime) => {
+            let filled: StringColumn = values.map(text).collect();
+            filled.cast(dtype, true)?
+        }
+        // integers stay integers unless the value is a float
+        _ => values.collect::<Vec<_>>().into(),
+    })
+}
+
+/// Fills interior nulls by linear interpolation between the nearest non-null
+/// neighbours, by position. Leading and trailing nulls stay null.
+pub(crate) fn interpolate<C: ColumnArray + ?Sized>(
+    column: &C,
+) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+    if !column.dtype().is_numeric() {
+        return Err(DataFrameError::TypeError(format!(
+            "cannot interpolate a {:?} column",
+            column.dtype()
+        )));
+    }
+    let mut values: Vec<Option<f64>> = (0..column.len()).map(|i| column.get_f64(i)).collect();
+    let mut previous: Option<usize> = None;
+    for index in 0..values.len() {
+        let Some(end) = values[index] else { continue };
+        if let Some(start) = previous {
+            let from = values[start].expect("previous is a non-null row");
+            let step = (end - from) / (index - start) as f64;
+            for (offset, value) in values[start + 1..index].iter_mut().enumerate() {
+                *value = Some(from + step * (offset + 1) as f64);
+            }
+        }
+        previous = Some(index);
+    }
+    Ok(Box::new(FloatColumn::from(values)))
+}
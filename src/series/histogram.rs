@@ -0,0 +1,347 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::frame::DataFrameError;
+use crate::series::{CategoricalColumn, ColumnArray};
+use crate::{DataFrame, Dtype};
+
+/// Width in characters of the longest bar drawn by `Histogram`'s `Display`.
+const BAR_WIDTH: usize = 40;
+
+/// Eighth-block characters used to draw the fractional end of a bar.
+const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// The most bins a histogram, `cut` or `qcut` may produce; more is almost
+/// certainly a mistaken width or count, and would exhaust memory.
+pub const MAX_BINS: usize = 100_000;
+
+/// How the range of a numeric column is divided into bins.
+///
+/// Infinite values are left out when the range is computed, like NaN.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Bins {
+    /// This many equal-width bins between the minimum and the maximum.
+    Count(usize),
+    /// Bins of this width, starting at the minimum.
+    Width(f64),
+    /// Explicit, strictly increasing bin edges.
+    Edges(Vec<f64>),
+    /// `ceil(log2(n)) + 1` equal-width bins.
+    #[default]
+    Sturges,
+    /// Bin width `2 * IQR / n^(1/3)`; falls back to Sturges when the IQR is zero.
+    FreedmanDiaconis,
+}
+
+impl FromStr for Bins {
+    type Err = String;
+
+    /// Parses a bin count (`20`), a rule (`sturges`, `fd`) or a list of edges (`0,10,50`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.to_lowercase().as_str() {
+            "sturges" | "auto" => return Ok(Bins::Sturges),
+            "fd" | "freedman-diaconis" => return Ok(Bins::FreedmanDiaconis),
+            _ => {}
+        }
+        if s.contains(',') {
+            let edges = s
+                .split(',')
+                .map(|edge| edge.trim().parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|_| format!("invalid bin edges '{}'", s))?;
+            return Ok(Bins::Edges(edges));
+        }
+        s.parse::<usize>().map(Bins::Count).map_err(|_| {
+            format!(
+                "expected a bin count, 'sturges', 'fd' or comma-separated edges, got '{}'",
+                s
+            )
+        })
+    }
+}
+
+/// Bin edges and the number of values falling into each bin.
+///
+/// Bins are half-open `[lo, hi)` except the last, which also includes its upper edge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub edges: Vec<f64>,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Counts `values` into bins; NaN and infinities are ignored and values outside the
+    /// edges are not counted.
+    pub fn from_values(values: &[f64], bins: &Bins) -> Result<Histogram, DataFrameError> {
+        let values: Vec<f64> = values.iter().copied().filter(|x| x.is_finite()).collect();
+        let edges = bin_edges(&values, bins)?;
+        let mut counts = vec![0; edges.len() - 1];
+        for &value in &values {
+            if let Some(bin) = bin_index(&edges, value) {
+                counts[bin] += 1;
+            }
+        }
+        Ok(Histogram { edges, counts })
+    }
+
+    pub fn bin_count(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Interval labels such as `[0, 10)`, one per bin.
+    pub fn labels(&self) -> Vec<String> {
+        interval_labels(&self.edges)
+    }
+
+    /// A `bin` / `count` frame with one row per bin.
+    pub fn to_frame(&self) -> DataFrame {
+        let labels: Vec<String> = self.labels();
+        let counts: Vec<usize> = self.counts.clone();
+        DataFrame::from_columns(
+            Some(vec!["bin".to_string(), "count".to_string()]),
+            vec![labels.into(), counts.into()],
+        )
+        .expect("one label per bin")
+    }
+}
+
+impl fmt::Display for Histogram {
+    /// Draws one line per bin: label, count and a bar scaled to the largest bin.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels = self.labels();
+        let label_width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let largest = self.counts.iter().copied().max().unwrap_or(0);
+        let count_width = largest.to_string().len();
+
+        for (label, &count) in labels.iter().zip(&self.counts) {
+            let line = format!(
+                "{:<lw$}  {:>cw$}  {}",
+                label,
+                count,
+                bar(count, largest),
+                lw = label_width,
+                cw = count_width
+            );
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+fn bar(count: usize, largest: usize) -> String {
+    if largest == 0 {
+        return String::new();
+    }
+    let eighths = (count * BAR_WIDTH * 8 + largest / 2) / largest;
+    let mut bar = "█".repeat(eighths / 8);
+    if !eighths.is_multiple_of(8) {
+        bar.push(PARTIAL_BLOCKS[eighths % 8]);
+    }
+    bar
+}
+
+/// Non-null, non-NaN values of a numeric (integer, float or boolean) column.
+pub(crate) fn numeric_values<C: ColumnArray + ?Sized>(
+    column: &C,
+) -> Result<Vec<f64>, DataFrameError> {
+    match column.dtype() {
+        Dtype::Integer | Dtype::Float | Dtype::Boolean => Ok((0..column.len())
+            .filter_map(|index| column.get_f64(index))
+            .filter(|x| !x.is_nan())
+            .collect()),
+        dtype => Err(DataFrameError::TypeError(format!(
+            "expected a numeric column, got {:?}",
+            dtype
+        ))),
+    }
+}
+
+/// Bins every row of `column` into the intervals between `edges`.
+///
+/// Nulls, NaN and values outside the edges become nulls; the categories are the
+/// interval labels in ascending order, including bins no row falls into.
+pub(crate) fn bin_column<C: ColumnArray + ?Sized>(column: &C, edges: &[f64]) -> CategoricalColumn {
+    let codes = (0..column.len())
+        .map(|index| {
+            column
+                .get_f64(index)
+                .and_then(|value| bin_index(edges, value))
+                .map(|bin| bin as u32)
+        })
+        .collect();
+    CategoricalColumn::from_parts(interval_labels(edges), codes)
+        .expect("bin indices are below the number of labels")
+}
+
+/// Edges splitting `values` into `quantiles` groups of (roughly) equal size.
+///
+/// Repeated values can make neighbouring quantiles equal; such edges are merged,
+/// so fewer bins than requested may come back. NaN and infinities are ignored.
+pub(crate) fn quantile_edges(values: &[f64], quantiles: usize) -> Result<Vec<f64>, DataFrameError> {
+    if quantiles == 0 {
+        return Err(DataFrameError::InvalidArgument(
+            "number of quantiles must be positive".to_string(),
+        ));
+    }
+    check_bin_count(quantiles as f64)?;
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|x| x.is_finite()).collect();
+    if sorted.is_empty() {
+        return Err(no_values());
+    }
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let mut edges: Vec<f64> = (0..=quantiles)
+        .map(|i| quantile_sorted(&sorted, i as f64 / quantiles as f64))
+        .collect();
+    edges.dedup();
+    if edges.len() < 2 {
+        let value = edges[0];
+        edges = vec![value - 0.5, value + 0.5];
+    }
+    Ok(edges)
+}
+
+/// Linearly interpolated quantile `q` (0..=1) of ascending, non-empty `sorted`.
+pub(crate) fn quantile_sorted(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+/// Computes the edges `bins` describes for `values`, ignoring NaN and infinities.
+pub(crate) fn bin_edges(values: &[f64], bins: &Bins) -> Result<Vec<f64>, DataFrameError> {
+    if let Bins::Edges(edges) = bins {
+        // NaN compares false both ways, so it would pass the ordering check
+        if edges.len() < 2
+            || !edges.iter().all(|edge| edge.is_finite())
+            || edges.windows(2).any(|pair| pair[0] >= pair[1])
+        {
+            return Err(DataFrameError::InvalidArgument(
+                "bin edges must be at least two strictly increasing finite values".to_string(),
+            ));
+        }
+        return Ok(edges.clone());
+    }
+
+    let values: Vec<f64> = values.iter().copied().filter(|x| x.is_finite()).collect();
+    let min = values
+        .iter()
+        .copied()
+        .reduce(f64::min)
+        .ok_or_else(no_values)?;
+    let max = values
+        .iter()
+        .copied()
+        .reduce(f64::max)
+        .ok_or_else(no_values)?;
+    if min == max {
+        // A single value gets one unit-wide bin centred on it.
+        return Ok(vec![min - 0.5, max + 0.5]);
+    }
+    if !(max - min).is_finite() {
+        return Err(DataFrameError::InvalidArgument(
+            "the range of values is too wide to bin".to_string(),
+        ));
+    }
+
+    let count = match bins {
+        Bins::Count(0) => {
+            return Err(DataFrameError::InvalidArgument(
+                "number of bins must be positive".to_string(),
+            ))
+        }
+        Bins::Count(count) => check_bin_count(*count as f64)?,
+        Bins::Width(width) if !width.is_finite() || *width <= 0.0 => {
+            return Err(DataFrameError::InvalidArgument(
+                "bin width must be a positive finite number".to_string(),
+            ))
+        }
+        Bins::Width(width) => {
+            let count = check_bin_count(((max - min) / width).ceil().max(1.0))?;
+            return Ok((0..=count).map(|i| min + i as f64 * width).collect());
+        }
+        Bins::Sturges => sturges(values.len()),
+        Bins::FreedmanDiaconis => {
+            let mut sorted = values.to_vec();
+            sorted.sort_by(|a, b| a.total_cmp(b));
+            let iqr = quantile_sorted(&sorted, 0.75) - quantile_sorted(&sorted, 0.25);
+            if iqr > 0.0 {
+                let width = 2.0 * iqr / (values.len() as f64).cbrt();
+                check_bin_count(((max - min) / width).ceil().max(1.0))?
+            } else {
+                sturges(values.len())
+            }
+        }
+        Bins::Edges(_) => unreachable!("handled above"),
+    };
+
+    let width = (max - min) / count as f64;
+    let mut edges: Vec<f64> = (0..count).map(|i| min + i as f64 * width).collect();
+    edges.push(max);
+    Ok(edges)
+}
+
+/// `count` as a number of bins, if it is at most [`MAX_BINS`].
+fn check_bin_count(count: f64) -> Result<usize, DataFrameError> {
+    if count > MAX_BINS as f64 {
+        return Err(DataFrameError::InvalidArgument(format!(
+            "{} bins requested, at most {} are allowed",
+            count, MAX_BINS
+        )));
+    }
+    Ok(count as usize)
+}
+
+fn sturges(n: usize) -> usize {
+    (n as f64).log2().ceil() as usize + 1
+}
+
+fn no_values() -> DataFrameError {
+    DataFrameError::InvalidArgument("column has no numeric values".to_string())
+}
+
+/// The bin `value` falls into, or `None` if it lies outside the edges.
+fn bin_index(edges: &[f64], value: f64) -> Option<usize> {
+    let last = edges.len() - 1;
+    if value.is_nan() || value < edges[0] || value > edges[last] {
+        return None;
+    }
+    if value == edges[last] {
+        return Some(last - 1);
+    }
+    Some(edges.partition_point(|&edge| edge <= value) - 1)
+}
+
+fn interval_labels(edges: &[f64]) -> Vec<String> {
+    let last = edges.len().saturating_sub(2);
+    edges
+        .windows(2)
+        .enumerate()
+        .map(|(i, pair)| {
+            let close = if i == last { ']' } else { ')' };
+            format!(
+                "[{}, {}{}",
+                format_edge(pair[0]),
+                format_edge(pair[1]),
+                close
+            )
+        })
+        .collect()
+}
+
+/// Formats an edge with at most three decimals and no trailing zeros.
+fn format_edge(edge: f64) -> String {
+    let text = format!("{:.3}", edge);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
@@ -1,5 +1,24 @@
+pub mod arithmetic;
 pub mod array;
+pub mod bitmap;
+pub mod cast;
+pub mod categorical;
+pub mod dt;
+pub mod fill;
+pub mod histogram;
+pub mod strings;
+pub mod temporal;
+pub mod window;
 
+pub use arithmetic::{ArithOp, Operand};
 pub use array::{
-    BooleanColumn, ColumnArray, FloatColumn, IntegerColumn, StringColumn, parse_column,
+    parse_column, BooleanColumn, ColumnArray, FloatColumn, IntegerColumn, StringColumn,
 };
+pub use bitmap::Bitmap;
+pub use categorical::CategoricalColumn;
+pub use dt::{DateTimeNamespace, Period};
+pub use fill::FillStrategy;
+pub use histogram::{Bins, Histogram, MAX_BINS};
+pub use strings::{PadSide, StringNamespace, StringStats};
+pub use temporal::{DateColumn, DateTimeColumn, DurationColumn};
+pub use window::{RankMethod, Rolling};
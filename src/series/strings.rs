@@ -0,0 +1,278 @@
+//! String operations, reached through `.str()` on a string or categorical column.
+//!
+//! Every operation is null-aware: a null row stays null in the result.
+
+use std::fmt;
+use std::str::FromStr;
+
+use regex::Regex;
+
+use crate::frame::DataFrameError;
+use crate::series::{BooleanColumn, ColumnArray, IntegerColumn, StringColumn};
+use crate::{CellValue, Dtype};
+
+/// Where [`StringNamespace::pad`] adds the fill characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PadSide {
+    /// Before the text, right-aligning it.
+    #[default]
+    Left,
+    /// After the text, left-aligning it.
+    Right,
+    /// On both sides, centring it; an odd fill goes on the right.
+    Both,
+}
+
+impl fmt::Display for PadSide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PadSide::Left => "left",
+            PadSide::Right => "right",
+            PadSide::Both => "both",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for PadSide {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "left" => Ok(PadSide::Left),
+            "right" => Ok(PadSide::Right),
+            "both" => Ok(PadSide::Both),
+            _ => Err(format!(
+                "unknown side '{}', expected left, right or both",
+                s
+            )),
+        }
+    }
+}
+
+/// The string operations of one column; see [`StringColumn::str`].
+#[derive(Debug, Clone, Copy)]
+pub struct StringNamespace<'a> {
+    column: &'a dyn ColumnArray,
+}
+
+impl StringColumn {
+    /// String operations on this column.
+    pub fn str(&self) -> StringNamespace<'_> {
+        StringNamespace { column: self }
+    }
+}
+
+impl dyn ColumnArray + '_ {
+    /// String operations on a string or categorical column.
+    pub fn str(&self) -> Result<StringNamespace<'_>, DataFrameError> {
+        match self.dtype() {
+            Dtype::Str | Dtype::Categorical => Ok(StringNamespace { column: self }),
+            dtype => Err(DataFrameError::TypeError(format!(
+                "string operations need a string column, got {:?}",
+                dtype
+            ))),
+        }
+    }
+}
+
+impl StringNamespace<'_> {
+    fn values(&self) -> impl Iterator<Item = Option<String>> + '_ {
+        (0..self.column.len()).map(|index| match self.column.get(index) {
+            Some(CellValue::Str(s)) => Some(s),
+            value => value.map(|value| value.to_string()),
+        })
+    }
+
+    fn map_text(&self, f: impl Fn(&str) -> String) -> Box<dyn ColumnArray> {
+        let column: StringColumn = self.values().map(|value| value.map(|s| f(&s))).collect();
+        Box::new(column)
+    }
+
+    fn map_bool(&self, f: impl Fn(&str) -> bool) -> Box<dyn ColumnArray> {
+        let column: BooleanColumn = self.values().map(|value| value.map(|s| f(&s))).collect();
+        Box::new(column)
+    }
+
+    /// Length of each value in characters.
+    pub fn len(&self) -> Box<dyn ColumnArray> {
+        let column: IntegerColumn = self
+            .values()
+            .map(|value| value.map(|s| s.chars().count() as i64))
+            .collect();
+        Box::new(column)
+    }
+
+    pub fn lower(&self) -> Box<dyn ColumnArray> {
+        self.map_text(str::to_lowercase)
+    }
+
+    pub fn upper(&self) -> Box<dyn ColumnArray> {
+        self.map_text(str::to_uppercase)
+    }
+
+    /// Values without leading and trailing whitespace.
+    pub fn trim(&self) -> Box<dyn ColumnArray> {
+        self.map_text(|s| s.trim().to_string())
+    }
+
+    /// Whether each value contains `pattern` literally.
+    pub fn contains(&self, pattern: &str) -> Box<dyn ColumnArray> {
+        self.map_bool(|s| s.contains(pattern))
+    }
+
+    pub fn starts_with(&self, prefix: &str) -> Box<dyn ColumnArray> {
+        self.map_bool(|s| s.starts_with(prefix))
+    }
+
+    pub fn ends_with(&self, suffix: &str) -> Box<dyn ColumnArray> {
+        self.map_bool(|s| s.ends_with(suffix))
+    }
+
+    /// Whether the regular expression `pattern` matches somewhere in each value.
+    pub fn is_match(&self, pattern: &str) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        let regex = compile(pattern)?;
+        Ok(self.map_bool(|s| regex.is_match(s)))
+    }
+
+    /// Capture `group` of the first match of `pattern` (0 is the whole match);
+    /// null where it does not match or the group did not take part.
+    pub fn extract(
+        &self,
+        pattern: &str,
+        group: usize,
+    ) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        let regex = compile(pattern)?;
+        if group >= regex.captures_len() {
+            return Err(DataFrameError::InvalidArgument(format!(
+                "'{}' has no capture group {}",
+                pattern, group
+            )));
+        }
+        let column: StringColumn = self
+            .values()
+            .map(|value| {
+                let s = value?;
+                let captures = regex.captures(&s)?;
+                captures.get(group).map(|m| m.as_str().to_string())
+            })
+            .collect();
+        Ok(Box::new(column))
+    }
+
+    /// Every match of `pattern` replaced by `replacement`, which may refer to
+    /// groups as `$1` or `${name}`.
+    pub fn replace(
+        &self,
+        pattern: &str,
+        replacement: &str,
+    ) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        let regex = compile(pattern)?;
+        Ok(self.map_text(|s| regex.replace_all(s, replacement).into_owned()))
+    }
+
+    /// Splits each value on `separator` into one column per part.
+    ///
+    /// With `parts`, at most that many columns come back and the last holds
+    /// the unsplit rest; otherwise there are as many as the longest split.
+    /// Values with fewer parts are null in the missing columns.
+    pub fn split(&self, separator: &str, parts: Option<usize>) -> Vec<Box<dyn ColumnArray>> {
+        let split: Vec<Option<Vec<String>>> = self
+            .values()
+            .map(|value| {
+                value.map(|s| match parts {
+                    Some(n) => s.splitn(n, separator).map(str::to_string).collect(),
+                    None => s.split(separator).map(str::to_string).collect(),
+                })
+            })
+            .collect();
+        let width = split.iter().flatten().map(Vec::len).max().unwrap_or(0);
+        (0..width)
+            .map(|part| {
+                let column: StringColumn = split
+                    .iter()
+                    .map(|pieces| pieces.as_ref().and_then(|pieces| pieces.get(part)))
+                    .collect();
+                Box::new(column) as Box<dyn ColumnArray>
+            })
+            .collect()
+    }
+
+    /// The characters from `start` (counted from the end when negative),
+    /// `length` of them or up to the end.
+    pub fn slice(&self, start: isize, length: Option<usize>) -> Box<dyn ColumnArray> {
+        self.map_text(|s| {
+            let count = s.chars().count();
+            let from = if start < 0 {
+                count.saturating_sub(start.unsigned_abs())
+            } else {
+                start as usize
+            };
+            s.chars()
+                .skip(from)
+                .take(length.unwrap_or(usize::MAX))
+                .collect()
+        })
+    }
+
+    /// Values shorter than `width` characters padded with `fill` on `side`.
+    pub fn pad(&self, width: usize, side: PadSide, fill: char) -> Box<dyn ColumnArray> {
+        self.map_text(|s| {
+            let missing = width.saturating_sub(s.chars().count());
+            let (left, right) = match side {
+                PadSide::Left => (missing, 0),
+                PadSide::Right => (0, missing),
+                PadSide::Both => (missing / 2, missing - missing / 2),
+            };
+            let fill = |n: usize| std::iter::repeat_n(fill, n);
+            fill(left).chain(s.chars()).chain(fill(right)).collect()
+        })
+    }
+
+    /// The values parsed as integers; see [`ColumnArray::cast`].
+    pub fn to_integer(&self, strict: bool) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        self.column.cast(Dtype::Integer, strict)
+    }
+
+    /// The values parsed as floats; see [`ColumnArray::cast`].
+    pub fn to_float(&self, strict: bool) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        self.column.cast(Dtype::Float, strict)
+    }
+
+    /// Length and content statistics of the non-null values.
+    pub fn stats(&self) -> StringStats {
+        let mut stats = StringStats::default();
+        let mut total = 0;
+        let mut distinct = std::collections::HashSet::new();
+        for s in self.values().flatten() {
+            let len = s.chars().count();
+            stats.min_length = Some(stats.min_length.map_or(len, |min| min.min(len)));
+            stats.max_length = Some(stats.max_length.map_or(len, |max| max.max(len)));
+            total += len;
+            if s.trim().is_empty() {
+                stats.empty_count += 1;
+            }
+            distinct.insert(s);
+        }
+        if !distinct.is_empty() {
+            stats.mean_length = Some(total as f64 / self.column.non_null_count() as f64);
+        }
+        stats.distinct_count = distinct.len();
+        stats
+    }
+}
+
+/// What [`StringNamespace::stats`] reports; lengths are in characters.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StringStats {
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub mean_length: Option<f64>,
+    /// Values that are empty or only whitespace.
+    pub empty_count: usize,
+    pub distinct_count: usize,
+}
+
+fn compile(pattern: &str) -> Result<Regex, DataFrameError> {
+    Regex::new(pattern).map_err(|e| DataFrameError::InvalidArgument(e.to_string()))
+}
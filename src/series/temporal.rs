@@ -0,0 +1,414 @@
+//! Date, datetime and duration columns.
+//!
+//! Dates are stored as days since 1970-01-01, datetimes (without a time zone)
+//! as microseconds since 1970-01-01 00:00:00 and durations as microseconds.
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeDelta};
+use serde_json::Value;
+
+use crate::series::{Bitmap, ColumnArray};
+use crate::{CellValue, Dtype};
+
+/// `NaiveDate::num_days_from_ce` of 1970-01-01.
+const EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_DAY: i64 = 86_400 * MICROS_PER_SECOND;
+
+/// How dates are written and the only layout inference reads as a date.
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// How datetimes are written; fractional seconds only appear when present.
+pub const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+
+/// Layouts inference and casts read as a datetime.
+const DATETIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M",
+];
+
+pub(crate) fn date_to_days(date: NaiveDate) -> i32 {
+    Datelike::num_days_from_ce(&date) - EPOCH_DAYS_FROM_CE
+}
+
+pub(crate) fn days_to_date(days: i32) -> Option<NaiveDate> {
+    NaiveDate::from_num_days_from_ce_opt(days.checked_add(EPOCH_DAYS_FROM_CE)?)
+}
+
+pub(crate) fn datetime_to_micros(datetime: NaiveDateTime) -> i64 {
+    datetime.and_utc().timestamp_micros()
+}
+
+pub(crate) fn micros_to_datetime(micros: i64) -> Option<NaiveDateTime> {
+    DateTime::from_timestamp_micros(micros).map(|datetime| datetime.naive_utc())
+}
+
+pub(crate) fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text.trim(), DATE_FORMAT).ok()
+}
+
+pub(crate) fn parse_datetime(text: &str) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    DATETIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+}
+
+/// A duration as `[-]D day(s) HH:MM:SS[.ffffff]`.
+pub fn format_duration(micros: i64) -> String {
+    let sign = if micros < 0 { "-" } else { "" };
+    let micros = micros.unsigned_abs();
+    let days = micros / MICROS_PER_DAY as u64;
+    let seconds = micros % MICROS_PER_DAY as u64 / MICROS_PER_SECOND as u64;
+    let fraction = micros % MICROS_PER_SECOND as u64;
+    let mut text = format!(
+        "{}{} {} {:02}:{:02}:{:02}",
+        sign,
+        days,
+        if days == 1 { "day" } else { "days" },
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    if fraction > 0 {
+        text.push_str(format!(".{:06}", fraction).trim_end_matches('0'));
+    }
+    text
+}
+
+/// Calendar dates without a time of day.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DateColumn {
+    days: Vec<i32>,
+    validity: Bitmap,
+}
+
+/// Dates with a time of day, without a time zone.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DateTimeColumn {
+    micros: Vec<i64>,
+    validity: Bitmap,
+}
+
+/// Signed lengths of time, e.g. the difference of two dates.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DurationColumn {
+    micros: Vec<i64>,
+    validity: Bitmap,
+}
+
+impl DateColumn {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        DateColumn {
+            days: Vec::with_capacity(capacity),
+            validity: Bitmap::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, value: Option<NaiveDate>) {
+        self.push_days(value.map(date_to_days));
+    }
+
+    /// Appends a date given as days since 1970-01-01.
+    pub fn push_days(&mut self, days: Option<i32>) {
+        self.days.push(days.unwrap_or_default());
+        self.validity.push(days.is_some());
+    }
+
+    /// Days since 1970-01-01 at `index`.
+    pub fn days(&self, index: usize) -> Option<i32> {
+        if self.validity.get(index) {
+            Some(self.days[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn value(&self, index: usize) -> Option<NaiveDate> {
+        self.days(index).and_then(days_to_date)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Option<NaiveDate>> + '_ {
+        (0..self.days.len()).map(move |index| self.value(index))
+    }
+
+    pub fn validity(&self) -> &Bitmap {
+        &self.validity
+    }
+}
+
+impl DateTimeColumn {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        DateTimeColumn {
+            micros: Vec::with_capacity(capacity),
+            validity: Bitmap::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, value: Option<NaiveDateTime>) {
+        self.push_micros(value.map(datetime_to_micros));
+    }
+
+    /// Appends a datetime given as microseconds since 1970-01-01 00:00:00.
+    pub fn push_micros(&mut self, micros: Option<i64>) {
+        self.micros.push(micros.unwrap_or_default());
+        self.validity.push(micros.is_some());
+    }
+
+    /// Microseconds since 1970-01-01 00:00:00 at `index`.
+    pub fn micros(&self, index: usize) -> Option<i64> {
+        if self.validity.get(index) {
+            Some(self.micros[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn value(&self, index: usize) -> Option<NaiveDateTime> {
+        self.micros(index).and_then(micros_to_datetime)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Option<NaiveDateTime>> + '_ {
+        (0..self.micros.len()).map(move |index| self.value(index))
+    }
+
+    pub fn validity(&self) -> &Bitmap {
+        &self.validity
+    }
+}
+
+impl DurationColumn {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        DurationColumn {
+            micros: Vec::with_capacity(capacity),
+            validity: Bitmap::with_capacity(capacity),
+        }
+    }
+
+    /// Appends a duration; one beyond ±2^63 microseconds is stored as null.
+    pub fn push(&mut self, value: Option<TimeDelta>) {
+        self.push_micros(value.and_then(|delta| delta.num_microseconds()));
+    }
+
+    pub fn push_micros(&mut self, micros: Option<i64>) {
+        self.micros.push(micros.unwrap_or_default());
+        self.validity.push(micros.is_some());
+    }
+
+    /// The duration at `index` in microseconds.
+    pub fn micros(&self, index: usize) -> Option<i64> {
+        if self.validity.get(index) {
+            Some(self.micros[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn value(&self, index: usize) -> Option<TimeDelta> {
+        self.micros(index).map(TimeDelta::microseconds)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Option<TimeDelta>> + '_ {
+        (0..self.micros.len()).map(move |index| self.value(index))
+    }
+
+    pub fn validity(&self) -> &Bitmap {
+        &self.validity
+    }
+}
+
+impl FromIterator<Option<NaiveDate>> for DateColumn {
+    fn from_iter<I: IntoIterator<Item = Option<NaiveDate>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut column = DateColumn::with_capacity(iter.size_hint().0);
+        for value in iter {
+            column.push(value);
+        }
+        column
+    }
+}
+
+impl FromIterator<Option<NaiveDateTime>> for DateTimeColumn {
+    fn from_iter<I: IntoIterator<Item = Option<NaiveDateTime>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut column = DateTimeColumn::with_capacity(iter.size_hint().0);
+        for value in iter {
+            column.push(value);
+        }
+        column
+    }
+}
+
+impl FromIterator<Option<TimeDelta>> for DurationColumn {
+    fn from_iter<I: IntoIterator<Item = Option<TimeDelta>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut column = DurationColumn::with_capacity(iter.size_hint().0);
+        for value in iter {
+            column.push(value);
+        }
+        column
+    }
+}
+
+fn text_json<C: ColumnArray + ?Sized>(column: &C) -> Vec<Value> {
+    (0..column.len())
+        .map(|index| match column.get(index) {
+            Some(value) => Value::String(value.to_string()),
+            None => Value::Null,
+        })
+        .collect()
+}
+
+impl ColumnArray for DateColumn {
+    fn to_json(&self) -> Vec<Value> {
+        text_json(self)
+    }
+
+    fn dtype(&self) -> Dtype {
+        Dtype::Date
+    }
+
+    fn get(&self, index: usize) -> Option<CellValue> {
+        self.value(index)
+            .map(|date| CellValue::Date(date.format(DATE_FORMAT).to_string()))
+    }
+
+    fn len(&self) -> usize {
+        self.days.len()
+    }
+
+    fn null_count(&self) -> usize {
+        self.validity.count_unset()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn ColumnArray> {
+        Box::new(self.clone())
+    }
+
+    fn take(&self, indices: &[usize]) -> Box<dyn ColumnArray> {
+        let mut column = DateColumn::with_capacity(indices.len());
+        for &index in indices {
+            column.push_days(self.days(index));
+        }
+        Box::new(column)
+    }
+}
+
+impl ColumnArray for DateTimeColumn {
+    fn to_json(&self) -> Vec<Value> {
+        text_json(self)
+    }
+
+    fn dtype(&self) -> Dtype {
+        Dtype::DateTime
+    }
+
+    fn get(&self, index: usize) -> Option<CellValue> {
+        self.value(index)
+            .map(|datetime| CellValue::Date(datetime.format(DATETIME_FORMAT).to_string()))
+    }
+
+    fn len(&self) -> usize {
+        self.micros.len()
+    }
+
+    fn null_count(&self) -> usize {
+        self.validity.count_unset()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn ColumnArray> {
+        Box::new(self.clone())
+    }
+
+    fn take(&self, indices: &[usize]) -> Box<dyn ColumnArray> {
+        let mut column = DateTimeColumn::with_capacity(indices.len());
+        for &index in indices {
+            column.push_micros(self.micros(index));
+        }
+        Box::new(column)
+    }
+}
+
+impl ColumnArray for DurationColumn {
+    fn to_json(&self) -> Vec<Value> {
+        text_json(self)
+    }
+
+    fn dtype(&self) -> Dtype {
+        Dtype::Duration
+    }
+
+    fn get(&self, index: usize) -> Option<CellValue> {
+        self.micros(index).map(CellValue::Duration)
+    }
+
+    fn len(&self) -> usize {
+        self.micros.len()
+    }
+
+    fn null_count(&self) -> usize {
+        self.validity.count_unset()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn ColumnArray> {
+        Box::new(self.clone())
+    }
+
+    fn take(&self, indices: &[usize]) -> Box<dyn ColumnArray> {
+        let mut column = DurationColumn::with_capacity(indices.len());
+        for &index in indices {
+            column.push_micros(self.micros(index));
+        }
+        Box::new(column)
+    }
+}
+
+/// A date column if every non-null cell is a `YYYY-MM-DD` date.
+pub(crate) fn parse_dates(cells: &[Option<&str>]) -> Option<DateColumn> {
+    let mut column = DateColumn::with_capacity(cells.len());
+    for cell in cells {
+        match cell {
+            None => column.push(None),
+            Some(text) => column.push(Some(parse_date(text)?)),
+        }
+    }
+    Some(column)
+}
+
+/// A datetime column if every non-null cell is an ISO 8601 date and time.
+pub(crate) fn parse_datetimes(cells: &[Option<&str>]) -> Option<DateTimeColumn> {
+    let mut column = DateTimeColumn::with_capacity(cells.len());
+    for cell in cells {
+        match cell {
+            None => column.push(None),
+            Some(text) => column.push(Some(parse_datetime(text)?)),
+        }
+    }
+    Some(column)
+}
@@ -0,0 +1,496 @@
+//! Window computations over the rows of a numeric column, in row order:
+//! rolling and expanding aggregates, running totals, shifts, differences and
+//! ranks.
+//!
+//! Nulls (and NaN, for the aggregates and ranks) are skipped rather than
+//! propagated: a window aggregates the values it has, and a null row stays
+//! null in the result. [`DataFrame::over`](crate::DataFrame::over) runs any
+//! of these separately within groups of rows.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::frame::DataFrameError;
+use crate::series::arithmetic::{binary, ArithOp};
+use crate::series::{ColumnArray, FloatColumn, IntegerColumn};
+use crate::{CellValue, Dtype};
+
+/// How `rank` numbers tied values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RankMethod {
+    /// The mean of the positions the ties span, e.g. 2.5 for a tie at 2 and 3.
+    #[default]
+    Average,
+    /// The lowest position the ties span.
+    Min,
+    /// The highest position the ties span.
+    Max,
+    /// The lowest position, with no gaps after ties: 1, 2, 2, 3.
+    Dense,
+    /// Distinct positions in row order: 1, 2, 3, 4.
+    Ordinal,
+}
+
+impl fmt::Display for RankMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RankMethod::Average => "average",
+            RankMethod::Min => "min",
+            RankMethod::Max => "max",
+            RankMethod::Dense => "dense",
+            RankMethod::Ordinal => "ordinal",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for RankMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "average" => Ok(RankMethod::Average),
+            "min" => Ok(RankMethod::Min),
+            "max" => Ok(RankMethod::Max),
+            "dense" => Ok(RankMethod::Dense),
+            "ordinal" | "first" => Ok(RankMethod::Ordinal),
+            _ => Err(format!(
+                "unknown rank method '{}', expected average, min, max, dense or ordinal",
+                s
+            )),
+        }
+    }
+}
+
+/// A moving window over a numeric column, from `rolling` or `expanding`.
+///
+/// Each row's window ends at that row. Every aggregate returns a float
+/// column that is null where the window holds fewer than `min_periods`
+/// non-null values.
+#[derive(Debug, Clone, Copy)]
+pub struct Rolling<'a> {
+    column: &'a dyn ColumnArray,
+    /// Rows in each window; `None` for every row so far.
+    window: Option<usize>,
+    min_periods: usize,
+}
+
+impl Rolling<'_> {
+    /// Non-null values a window needs for a result; at least 1.
+    pub fn with_min_periods(mut self, min_periods: usize) -> Self {
+        self.min_periods = min_periods.max(1);
+        self
+    }
+
+    pub fn sum(&self) -> Box<dyn ColumnArray> {
+        self.moments(|moments| Some(moments.sum()))
+    }
+
+    pub fn mean(&self) -> Box<dyn ColumnArray> {
+        self.moments(|moments| Some(moments.mean()))
+    }
+
+    /// Sample variance (`n - 1` denominator); null for a single value.
+    pub fn var(&self) -> Box<dyn ColumnArray> {
+        self.moments(Moments::variance)
+    }
+
+    /// Sample standard deviation (`n - 1` denominator); null for a single value.
+    pub fn std(&self) -> Box<dyn ColumnArray> {
+        self.moments(|moments| moments.variance().map(f64::sqrt))
+    }
+
+    pub fn min(&self) -> Box<dyn ColumnArray> {
+        self.extreme(|kept, new| kept < new)
+    }
+
+    pub fn max(&self) -> Box<dyn ColumnArray> {
+        self.extreme(|kept, new| kept > new)
+    }
+
+    /// The row that drops out of the window when it moves on to `row`.
+    fn leaving(&self, row: usize) -> Option<usize> {
+        self.window.and_then(|window| row.checked_sub(window))
+    }
+
+    fn moments(&self, result: impl Fn(&Moments) -> Option<f64>) -> Box<dyn ColumnArray> {
+        let values = numbers(self.column);
+        let mut moments = Moments::default();
+        let column: FloatColumn = (0..values.len())
+            .map(|row| {
+                if let Some(x) = values[row] {
+                    moments.add(x);
+                }
+                if let Some(x) = self.leaving(row).and_then(|old| values[old]) {
+                    moments.remove(x);
+                }
+                if moments.count < self.min_periods {
+                    return None;
+                }
+                result(&moments)
+            })
+            .collect();
+        Box::new(column)
+    }
+
+    /// Keeps a queue of candidate rows whose values are ordered by `keeps`,
+    /// so the front is always the window's extreme.
+    fn extreme(&self, keeps: impl Fn(f64, f64) -> bool) -> Box<dyn ColumnArray> {
+        let values = numbers(self.column);
+        let mut candidates: VecDeque<usize> = VecDeque::new();
+        let mut count = 0;
+        let column: FloatColumn = (0..values.len())
+            .map(|row| {
+                if let Some(x) = values[row] {
+                    while candidates.back().is_some_and(|&back| {
+                        !keeps(values[back].expect("candidates are non-null"), x)
+                    }) {
+                        candidates.pop_back();
+                    }
+                    candidates.push_back(row);
+                    count += 1;
+                }
+                if let Some(old) = self.leaving(row) {
+                    if values[old].is_some() {
+                        count -= 1;
+                    }
+                    if candidates.front() == Some(&old) {
+                        candidates.pop_front();
+                    }
+                }
+                if count < self.min_periods {
+                    return None;
+                }
+                candidates.front().and_then(|&front| values[front])
+            })
+            .collect();
+        Box::new(column)
+    }
+}
+
+/// Running count, sum, mean and sum of squared deviations (Welford's
+/// method, which stays accurate as values enter and leave).
+///
+/// NaN and infinities are only counted: folded into the running state they
+/// would leave it NaN after they leave the window.
+#[derive(Debug, Default)]
+struct Moments {
+    count: usize,
+    finite: usize,
+    sum: f64,
+    mean: f64,
+    m2: f64,
+    nan: usize,
+    positive_infinite: usize,
+    negative_infinite: usize,
+}
+
+impl Moments {
+    fn add(&mut self, x: f64) {
+        self.count += 1;
+        if !self.count_non_finite(x, true) {
+            return;
+        }
+        self.finite += 1;
+        self.sum += x;
+        let delta = x - self.mean;
+        self.mean += delta / self.finite as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    fn remove(&mut self, x: f64) {
+        self.count -= 1;
+        if !self.count_non_finite(x, false) {
+            return;
+        }
+        self.finite -= 1;
+        if self.finite == 0 {
+            (self.sum, self.mean, self.m2) = (0.0, 0.0, 0.0);
+            return;
+        }
+        self.sum -= x;
+        let delta = x - self.mean;
+        self.mean -= delta / self.finite as f64;
+        self.m2 -= delta * (x - self.mean);
+    }
+
+    /// Counts a non-finite `x` in or out; false when `x` is finite and
+    /// belongs in the running state instead.
+    fn count_non_finite(&mut self, x: f64, entering: bool) -> bool {
+        let counter = if x.is_nan() {
+            &mut self.nan
+        } else if x == f64::INFINITY {
+            &mut self.positive_infinite
+        } else if x == f64::NEG_INFINITY {
+            &mut self.negative_infinite
+        } else {
+            return true;
+        };
+        if entering {
+            *counter += 1;
+        } else {
+            *counter -= 1;
+        }
+        false
+    }
+
+    /// What the non-finite values make of a sum or mean, if there are any.
+    fn non_finite(&self) -> Option<f64> {
+        match (self.nan, self.positive_infinite, self.negative_infinite) {
+            (0, 0, 0) => None,
+            (0, _, 0) => Some(f64::INFINITY),
+            (0, 0, _) => Some(f64::NEG_INFINITY),
+            _ => Some(f64::NAN),
+        }
+    }
+
+    fn sum(&self) -> f64 {
+        self.non_finite().unwrap_or(self.sum)
+    }
+
+    fn mean(&self) -> f64 {
+        self.non_finite().unwrap_or(self.mean)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        (self.count > 1).then(|| match self.non_finite() {
+            Some(_) => f64::NAN,
+            None => self.m2.max(0.0) / (self.count - 1) as f64,
+        })
+    }
+}
+
+/// The running operations that [`cumulative`] computes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Running {
+    Sum,
+    Product,
+    Max,
+    Min,
+}
+
+impl fmt::Display for Running {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Running::Sum => "cumulative sum",
+            Running::Product => "cumulative product",
+            Running::Max => "cumulative max",
+            Running::Min => "cumulative min",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl dyn ColumnArray + '_ {
+    /// Aggregates over the last `window` rows, the current one included;
+    /// by default a result needs `window` non-null values.
+    pub fn rolling(&self, window: usize) -> Result<Rolling<'_>, DataFrameError> {
+        check_numeric(self, "rolling windows")?;
+        if window == 0 {
+            return Err(DataFrameError::InvalidArgument(
+                "a rolling window needs at least one row".to_string(),
+            ));
+        }
+        Ok(Rolling {
+            column: self,
+            window: Some(window),
+            min_periods: window,
+        })
+    }
+
+    /// Aggregates over every row up to and including the current one; by
+    /// default a result needs one non-null value.
+    pub fn expanding(&self) -> Result<Rolling<'_>, DataFrameError> {
+        check_numeric(self, "expanding windows")?;
+        Ok(Rolling {
+            column: self,
+            window: None,
+            min_periods: 1,
+        })
+    }
+
+    /// Running total. Integer and boolean columns give integers, with
+    /// overflow an error; float columns give floats.
+    pub fn cum_sum(&self) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        cumulative(self, Running::Sum)
+    }
+
+    /// Running product, typed like `cum_sum`.
+    pub fn cum_prod(&self) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        cumulative(self, Running::Product)
+    }
+
+    /// Largest value so far, typed like `cum_sum`.
+    pub fn cum_max(&self) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        cumulative(self, Running::Max)
+    }
+
+    /// Smallest value so far, typed like `cum_sum`.
+    pub fn cum_min(&self) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        cumulative(self, Running::Min)
+    }
+
+    /// The values moved down `periods` rows (up when negative), with nulls
+    /// in the rows left empty. Works on columns of any type.
+    pub fn shift(&self, periods: isize) -> Box<dyn ColumnArray> {
+        let len = self.len();
+        // `take` leaves a null for an out-of-range index
+        let indices: Vec<usize> = (0..len)
+            .map(|row| {
+                // `isize::MIN` has no negation, but shifts every row out anyway
+                periods
+                    .checked_neg()
+                    .and_then(|offset| row.checked_add_signed(offset))
+                    .filter(|&source| source < len)
+                    .unwrap_or(len)
+            })
+            .collect();
+        self.take(&indices)
+    }
+
+    /// Each value minus the one `periods` rows earlier (later when negative).
+    ///
+    /// Integer columns stay integers; date and datetime columns give
+    /// durations.
+    pub fn diff(&self, periods: isize) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        let earlier = self.shift(periods);
+        match self.dtype() {
+            Dtype::Date | Dtype::DateTime => self.dt()?.diff(earlier.as_ref()),
+            _ => binary(self, ArithOp::Sub, &earlier),
+        }
+    }
+
+    /// Relative change from the value `periods` rows earlier, as a float:
+    /// 0.5 for a rise from 2 to 3.
+    pub fn pct_change(&self, periods: isize) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        let ratio = binary(self, ArithOp::Div, &self.shift(periods))?;
+        binary(&ratio, ArithOp::Sub, 1.0)
+    }
+
+    /// The position of each value in sorted order, from 1; ties are numbered
+    /// by `method`. Average ranks are floats, the others integers.
+    pub fn rank(
+        &self,
+        method: RankMethod,
+        descending: bool,
+    ) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+        check_numeric(self, "ranking")?;
+        let values = numbers(self);
+        let mut order: Vec<usize> = (0..values.len()).filter(|&i| values[i].is_some()).collect();
+        let value = |row: usize| values[row].expect("only non-null rows are ordered");
+        order.sort_by(|&a, &b| {
+            let ordering = value(a).total_cmp(&value(b));
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        let mut ranks: Vec<Option<f64>> = vec![None; values.len()];
+        let mut start = 0;
+        let mut dense = 0;
+        while start < order.len() {
+            let end = start
+                + order[start..]
+                    .iter()
+                    .take_while(|&&row| value(row) == value(order[start]))
+                    .count();
+            dense += 1;
+            for (offset, &row) in order[start..end].iter().enumerate() {
+                ranks[row] = Some(match method {
+                    RankMethod::Average => (start + end + 1) as f64 / 2.0,
+                    RankMethod::Min => (start + 1) as f64,
+                    RankMethod::Max => end as f64,
+                    RankMethod::Dense => dense as f64,
+                    RankMethod::Ordinal => (start + offset + 1) as f64,
+                });
+            }
+            start = end;
+        }
+
+        Ok(match method {
+            RankMethod::Average => Box::new(FloatColumn::from(ranks)),
+            _ => Box::new(
+                ranks
+                    .into_iter()
+                    .map(|rank| rank.map(|rank| rank as i64))
+                    .collect::<IntegerColumn>(),
+            ),
+        })
+    }
+}
+
+fn check_numeric(column: &dyn ColumnArray, what: &str) -> Result<(), DataFrameError> {
+    match column.dtype() {
+        Dtype::Integer | Dtype::Float | Dtype::Boolean => Ok(()),
+        dtype => Err(DataFrameError::TypeError(format!(
+            "{} need a numeric column, got {:?}",
+            what, dtype
+        ))),
+    }
+}
+
+/// The values as floats, with NaN as null.
+fn numbers(column: &dyn ColumnArray) -> Vec<Option<f64>> {
+    (0..column.len())
+        .map(|row| column.get_f64(row).filter(|x| !x.is_nan()))
+        .collect()
+}
+
+fn cumulative(
+    column: &dyn ColumnArray,
+    running: Running,
+) -> Result<Box<dyn ColumnArray>, DataFrameError> {
+    check_numeric(column, "running totals")?;
+    if column.dtype() == Dtype::Float {
+        let mut total: Option<f64> = None;
+        let result: FloatColumn = (0..column.len())
+            .map(|row| {
+                let x = column.get_f64(row)?;
+                let next = match (total, running) {
+                    (None, _) => x,
+                    (Some(total), Running::Sum) => total + x,
+                    (Some(total), Running::Product) => total * x,
+                    (Some(total), Running::Max) => total.max(x),
+                    (Some(total), Running::Min) => total.min(x),
+                };
+                total = Some(next);
+                total
+            })
+            .collect();
+        return Ok(Box::new(result));
+    }
+
+    let mut total: Option<i64> = None;
+    let mut result = IntegerColumn::with_capacity(column.len());
+    for row in 0..column.len() {
+        let n = match column.get(row) {
+            Some(CellValue::Integer(n)) => n,
+            Some(CellValue::Boolean(b)) => b as i64,
+            _ => {
+                result.push(None);
+                continue;
+            }
+        };
+        let next = match (total, running) {
+            (None, _) => Some(n),
+            (Some(total), Running::Sum) => total.checked_add(n),
+            (Some(total), Running::Product) => total.checked_mul(n),
+            (Some(total), Running::Max) => Some(total.max(n)),
+            (Some(total), Running::Min) => Some(total.min(n)),
+        };
+        match next {
+            Some(next) => total = Some(next),
+            None => {
+                return Err(DataFrameError::ArithmeticError(format!(
+                    "integer overflow in {} (row {})",
+                    running, row
+                )))
+            }
+        }
+        result.push(total);
+    }
+    Ok(Box::new(result))
+}
@@ -0,0 +1,344 @@
+//! Interactive session over one loaded file, started by `csv_processor shell FILE`.
+//!
+//! The file is parsed once; `select`, `filter` and `sort` narrow the current
+//! view, `reset` goes back to the loaded frame and `save` writes the view out.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use crate::frame::DataFrameError;
+use crate::io::write_csv;
+use crate::reporter::{generate_info_report, generate_na_report};
+use crate::{DataFrame, SqlContext};
+
+/// Rows `head` shows, and that commands changing the view preview.
+pub const PREVIEW_ROWS: usize = 10;
+
+const COMMANDS: [&str; 12] = [
+    "head", "info", "na", "select", "filter", "sort", "freq", "save", "columns", "reset", "help",
+    "quit",
+];
+
+/// What a shell command produced.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShellOutput {
+    Text(String),
+    Quit,
+}
+
+/// The loaded frame and the current view of it.
+#[derive(Debug, Clone)]
+pub struct Shell {
+    loaded: DataFrame,
+    current: DataFrame,
+}
+
+impl Shell {
+    pub fn new(df: DataFrame) -> Self {
+        Shell {
+            current: df.clone(),
+            loaded: df,
+        }
+    }
+
+    /// The current view, after any `select`, `filter` and `sort`.
+    pub fn frame(&self) -> &DataFrame {
+        &self.current
+    }
+
+    /// Runs one command line.
+    pub fn execute(&mut self, line: &str) -> Result<ShellOutput, DataFrameError> {
+        let line = line.trim();
+        let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        let args = split_words(rest);
+
+        let text = match command.to_lowercase().as_str() {
+            "" => String::new(),
+            "head" => {
+                let n = match args.as_slice() {
+                    [] => PREVIEW_ROWS,
+                    [n] => parse_count(n)?,
+                    _ => return Err(usage("head [N]")),
+                };
+                self.current.head(n).to_string()
+            }
+            "info" => generate_info_report(&self.current).to_string(),
+            "na" => generate_na_report(&self.current).to_string(),
+            "columns" => self
+                .current
+                .headers()
+                .iter()
+                .zip(self.current.columns())
+                .map(|(name, column)| format!("{} ({:?})", name, column.dtype()))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "select" => {
+                if args.is_empty() {
+                    return Err(usage("select COLUMN..."));
+                }
+                self.current = self.current.select(&args)?;
+                self.preview()
+            }
+            "filter" => {
+                if rest.is_empty() {
+                    return Err(usage(
+                        "filter CONDITION, e.g. filter Age > 30 AND Sex = 'male'",
+                    ));
+                }
+                let mut context = SqlContext::new();
+                context.register("data", self.current.clone());
+                self.current = context
+                    .execute(&format!("SELECT * FROM data WHERE {}", rest))?
+                    .collect()?;
+                self.preview()
+            }
+            "sort" => {
+                let (column, descending) = match args.as_slice() {
+                    [column] => (column, false),
+                    [column, order] if order.eq_ignore_ascii_case("asc") => (column, false),
+                    [column, order] if order.eq_ignore_ascii_case("desc") => (column, true),
+                    _ => return Err(usage("sort COLUMN [asc|desc]")),
+                };
+                if self.current.column(column).is_none() {
+                    return Err(DataFrameError::ColumnNotFound(column.clone()));
+                }
+                self.current = self
+                    .current
+                    .clone()
+                    .lazy()
+                    .sort(column, descending)
+                    .collect()?;
+                self.preview()
+            }
+            "freq" => {
+                let (column, top) = match args.as_slice() {
+                    [column] => (column, PREVIEW_ROWS),
+                    [column, n] => (column, parse_count(n)?),
+                    _ => return Err(usage("freq COLUMN [N]")),
+                };
+                let values = self
+                    .current
+                    .column(column)
+                    .ok_or_else(|| DataFrameError::ColumnNotFound(column.clone()))?;
+                let counts = values.value_counts(false, true, false);
+                format!(
+                    "{} ({} distinct values)\n{}",
+                    column,
+                    counts.shape().0,
+                    counts.head(top)
+                )
+            }
+            "save" => {
+                let [path] = args.as_slice() else {
+                    return Err(usage("save PATH"));
+                };
+                self.save(path)?;
+                format!("Saved {} rows to {}", self.current.shape().0, path)
+            }
+            "reset" => {
+                self.current = self.loaded.clone();
+                self.preview()
+            }
+            "help" => HELP.to_string(),
+            "quit" | "exit" => return Ok(ShellOutput::Quit),
+            other => {
+                return Err(DataFrameError::InvalidArgument(format!(
+                    "unknown command '{}'; type 'help' for the list",
+                    other
+                )))
+            }
+        };
+        Ok(ShellOutput::Text(text))
+    }
+
+    /// Completion candidates for the word ending at `pos`: command names for
+    /// the first word, column names of the current view after it. Returns
+    /// where the completed word starts.
+    pub fn complete(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let before = &line[..pos];
+        let start = before
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| c.is_whitespace() || c == '"' || c == '\'')
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        let word = &before[start..];
+        let quoted = start > 0 && matches!(before.as_bytes()[start - 1], b'"' | b'\'');
+
+        if before[..start].trim().is_empty() {
+            let commands = COMMANDS
+                .iter()
+                .filter(|command| command.starts_with(&word.to_lowercase()))
+                .map(|command| command.to_string())
+                .collect();
+            return (start, commands);
+        }
+        let columns = self
+            .current
+            .headers()
+            .iter()
+            .filter(|name| name.to_lowercase().starts_with(&word.to_lowercase()))
+            .map(|name| {
+                // names with spaces need quotes unless the user already opened one
+                if name.contains(char::is_whitespace) && !quoted {
+                    format!("\"{}\"", name)
+                } else {
+                    name.clone()
+                }
+            })
+            .collect();
+        (start, columns)
+    }
+
+    fn preview(&self) -> String {
+        let rows = self.current.shape().0;
+        let mut text = self.current.head(PREVIEW_ROWS).to_string();
+        if rows > PREVIEW_ROWS {
+            text.push_str(&format!("\n(showing {} of {} rows)", PREVIEW_ROWS, rows));
+        }
+        text
+    }
+
+    fn save(&self, path: &str) -> Result<(), DataFrameError> {
+        let file =
+            File::create(path).map_err(|e| DataFrameError::IoError(format!("{}: {}", path, e)))?;
+        write_csv(&self.current, BufWriter::new(file), b',')
+    }
+}
+
+/// Reads commands until `quit` or end of input, keeping line history in
+/// `history` when given.
+pub fn run(df: DataFrame, history: Option<&Path>) -> Result<(), DataFrameError> {
+    let readline_error = |e: ReadlineError| DataFrameError::IoError(e.to_string());
+    let mut editor: Editor<ShellHelper, DefaultHistory> = Editor::new().map_err(readline_error)?;
+    let (rows, columns) = df.shape();
+    editor.set_helper(Some(ShellHelper {
+        shell: Shell::new(df),
+    }));
+    if let Some(path) = history {
+        // a missing history file just means a first session
+        let _ = editor.load_history(path);
+    }
+
+    println!(
+        "{} rows × {} columns loaded. Type 'help' for commands, Tab to complete column names.",
+        rows, columns
+    );
+    loop {
+        let line = match editor.readline("> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(error) => return Err(readline_error(error)),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        editor
+            .add_history_entry(line.as_str())
+            .map_err(readline_error)?;
+        let shell = &mut editor.helper_mut().expect("helper is set above").shell;
+        match shell.execute(&line) {
+            Ok(ShellOutput::Text(text)) => println!("{}", text),
+            Ok(ShellOutput::Quit) => break,
+            Err(error) => eprintln!("Error: {}", error),
+        }
+    }
+
+    if let Some(path) = history {
+        editor.save_history(path).map_err(readline_error)?;
+    }
+    Ok(())
+}
+
+const HELP: &str = "\
+head [N]                  First N rows (default 10)
+info                      Statistics of each column
+na                        Missing values per column
+columns                   Column names and types
+select COLUMN...          Keep only these columns
+filter CONDITION          Keep rows matching a SQL condition, e.g. Age > 30 AND Sex = 'male'
+sort COLUMN [asc|desc]    Sort rows by a column
+freq COLUMN [N]           Most frequent values of a column
+save PATH                 Write the current rows as CSV
+reset                     Go back to the loaded file
+quit                      Leave the shell (also Ctrl-D)";
+
+/// Splits arguments on whitespace, keeping quoted names such as `"Home Town"` whole.
+fn split_words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quote = None;
+    let mut started = false;
+    for c in text.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => word.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                started = true;
+            }
+            None if c.is_whitespace() => {
+                if started {
+                    words.push(std::mem::take(&mut word));
+                    started = false;
+                }
+            }
+            None => {
+                word.push(c);
+                started = true;
+            }
+        }
+    }
+    if started {
+        words.push(word);
+    }
+    words
+}
+
+fn parse_count(text: &str) -> Result<usize, DataFrameError> {
+    text.parse().map_err(|_| {
+        DataFrameError::InvalidArgument(format!("expected a number of rows, got '{}'", text))
+    })
+}
+
+fn usage(text: &str) -> DataFrameError {
+    DataFrameError::InvalidArgument(format!("usage: {}", text))
+}
+
+/// Lets rustyline complete commands and column names from the live session.
+struct ShellHelper {
+    shell: Shell,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(self.shell.complete(line, pos))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
@@ -0,0 +1,136 @@
+//! SQL queries over CSV files and frames.
+//!
+//! A [`SqlContext`] holds named tables; [`SqlContext::execute`] parses one
+//! `SELECT` statement and plans it as a [`LazyFrame`], so filters move down to
+//! the scan and unread columns are pruned just like in hand-built lazy queries.
+//!
+//! Supported: `WHERE`, `GROUP BY`, `HAVING`, `ORDER BY`, `LIMIT`/`OFFSET`,
+//! `DISTINCT`, inner/left/right/full/cross `JOIN`s, subqueries in `FROM`,
+//! `WITH`, the aggregates `COUNT`, `SUM`, `AVG`, `MIN`, `MAX` and the scalar
+//! functions `ABS`, `ROUND`, `UPPER`, `LOWER`, `TRIM`, `LENGTH` and `COALESCE`.
+
+mod planner;
+
+use std::path::Path;
+
+use sqlparser::ast::Statement;
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+
+use crate::frame::DataFrameError;
+use crate::io::{read_headers, CsvReadOptions};
+use crate::lazy::LazyFrame;
+use crate::DataFrame;
+use planner::Planner;
+
+/// A table a query can read from.
+#[derive(Debug, Clone)]
+pub(crate) struct Table {
+    name: String,
+    frame: LazyFrame,
+    columns: Vec<String>,
+}
+
+/// Named tables that SQL queries run against.
+#[derive(Debug, Clone, Default)]
+pub struct SqlContext {
+    tables: Vec<Table>,
+}
+
+impl SqlContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the CSV file at `path` under its file stem, so `data/sales.csv`
+    /// becomes the table `sales`. Only the header is read here.
+    ///
+    /// Returns the table name.
+    pub fn register_csv(
+        &mut self,
+        path: &str,
+        options: &CsvReadOptions,
+    ) -> Result<String, DataFrameError> {
+        let name = Path::new(path)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| {
+                DataFrameError::InvalidArgument(format!("cannot name a table after '{}'", path))
+            })?
+            .to_string();
+        let columns = read_headers(path, options)?;
+        let frame = LazyFrame::scan_csv_with_options(path, options.clone());
+        self.insert(Table {
+            name: name.clone(),
+            frame,
+            columns,
+        });
+        Ok(name)
+    }
+
+    /// Registers an in-memory frame as the table `name`.
+    pub fn register(&mut self, name: &str, df: DataFrame) {
+        let columns = df.headers().to_vec();
+        self.insert(Table {
+            name: name.to_string(),
+            frame: df.lazy(),
+            columns,
+        });
+    }
+
+    /// Names of the registered tables, in registration order.
+    pub fn table_names(&self) -> Vec<&str> {
+        self.tables
+            .iter()
+            .map(|table| table.name.as_str())
+            .collect()
+    }
+
+    /// Plans a single `SELECT` statement; run it with `collect()`.
+    pub fn execute(&self, query: &str) -> Result<LazyFrame, DataFrameError> {
+        let statements = Parser::parse_sql(&GenericDialect {}, query)
+            .map_err(|e| DataFrameError::SqlError(e.to_string()))?;
+        match statements.as_slice() {
+            [Statement::Query(query)] => Planner::new(&self.tables)
+                .plan_query(query)
+                .map(|(frame, _)| frame),
+            [_] => Err(DataFrameError::SqlError(
+                "only SELECT statements are supported".to_string(),
+            )),
+            _ => Err(DataFrameError::SqlError(format!(
+                "expected one statement, got {}",
+                statements.len()
+            ))),
+        }
+    }
+
+    fn insert(&mut self, table: Table) {
+        self.tables
+            .retain(|existing| !existing.name.eq_ignore_ascii_case(&table.name));
+        self.tables.push(table);
+    }
+}
+
+impl DataFrame {
+    /// Runs `query` over CSV files, each registered as a table named after its
+    /// file stem.
+    ///
+    /// ```rust,no_run
+    /// use csv_processor::DataFrame;
+    ///
+    /// let df = DataFrame::sql(
+    ///     &["orders.csv", "customers.csv"],
+    ///     "SELECT c.name, SUM(o.total) AS spent
+    ///      FROM orders o JOIN customers c ON o.customer_id = c.id
+    ///      GROUP BY c.name ORDER BY spent DESC LIMIT 10",
+    /// )?;
+    /// # Ok::<(), csv_processor::frame::DataFrameError>(())
+    /// ```
+    pub fn sql<P: AsRef<str>>(paths: &[P], query: &str) -> Result<DataFrame, DataFrameError> {
+        let mut context = SqlContext::new();
+        for path in paths {
+            context.register_csv(path.as_ref(), &CsvReadOptions::default())?;
+        }
+        context.execute(query)?.collect()
+    }
+}
@@ -0,0 +1,902 @@
+//! Turns a parsed `SELECT` into a [`LazyFrame`] plan.
+//!
+//! With more than one relation in `FROM`, every input column is renamed to
+//! `alias.column` before joining so that names stay unique; bare names in the
+//! query resolve to whichever relation has them.
+
+use sqlparser::ast::{
+    BinaryOperator, DuplicateTreatment, Expr as SqlExpr, Function, FunctionArg, FunctionArgExpr,
+    FunctionArguments, GroupByExpr, JoinConstraint, JoinOperator, ObjectName, OrderByExpr, Query,
+    Select, SelectItem, SetExpr, TableAlias, TableFactor, TableWithJoins, UnaryOperator, Value,
+};
+
+use crate::frame::DataFrameError;
+use crate::lazy::{coalesce, col, len, lit, BinaryOp, Expr, JoinType, LazyFrame};
+use crate::sql::Table;
+use crate::CellValue;
+
+type Result<T> = std::result::Result<T, DataFrameError>;
+
+/// An output column: the expression computing it and its name.
+type Item = (Expr, String);
+
+#[derive(Debug, Clone)]
+pub(crate) struct Planner {
+    tables: Vec<Table>,
+}
+
+impl Planner {
+    pub(crate) fn new(tables: &[Table]) -> Self {
+        Planner {
+            tables: tables.to_vec(),
+        }
+    }
+
+    /// Plans `query`, returning the frame and its column names.
+    pub(crate) fn plan_query(&self, query: &Query) -> Result<(LazyFrame, Vec<String>)> {
+        if let Some(with) = &query.with {
+            if with.recursive {
+                return Err(unsupported("WITH RECURSIVE"));
+            }
+            let mut inner = self.clone();
+            for cte in &with.cte_tables {
+                let (frame, columns) = inner.plan_query(&cte.query)?;
+                let (frame, columns) = rename_columns(frame, columns, &cte.alias)?;
+                inner.tables.push(Table {
+                    name: cte.alias.name.value.clone(),
+                    frame,
+                    columns,
+                });
+            }
+            return inner.plan_body(query);
+        }
+        self.plan_body(query)
+    }
+
+    fn plan_body(&self, query: &Query) -> Result<(LazyFrame, Vec<String>)> {
+        if query.fetch.is_some() || !query.limit_by.is_empty() {
+            return Err(unsupported("FETCH and LIMIT BY"));
+        }
+        match query.body.as_ref() {
+            SetExpr::Select(select) => self.plan_select(select, query),
+            _ => Err(unsupported("set operations such as UNION")),
+        }
+    }
+
+    fn plan_select(&self, select: &Select, query: &Query) -> Result<(LazyFrame, Vec<String>)> {
+        let group_by = match &select.group_by {
+            GroupByExpr::Expressions(exprs, modifiers) if modifiers.is_empty() => exprs,
+            _ => return Err(unsupported("GROUP BY ALL and grouping modifiers")),
+        };
+        let distinct = match &select.distinct {
+            None => false,
+            Some(sqlparser::ast::Distinct::Distinct) => true,
+            Some(sqlparser::ast::Distinct::On(_)) => return Err(unsupported("DISTINCT ON")),
+        };
+
+        let (mut frame, scope) = self.plan_from(&select.from)?;
+        if let Some(selection) = &select.selection {
+            let predicate = scope.convert(selection)?;
+            if predicate.is_aggregate() {
+                return Err(DataFrameError::SqlError(
+                    "aggregate functions are not allowed in WHERE".to_string(),
+                ));
+            }
+            frame = frame.filter(predicate);
+        }
+
+        let items = scope.select_items(&select.projection)?;
+        let order_by = query
+            .order_by
+            .as_ref()
+            .map_or(&[][..], |order_by| &order_by.exprs[..]);
+        let mut sort = order_by
+            .iter()
+            .map(|item| scope.order_key(item, &items))
+            .collect::<Result<Vec<_>>>()?;
+
+        let aggregated = !group_by.is_empty()
+            || select.having.is_some()
+            || items.iter().any(|(expr, _)| expr.is_aggregate());
+        let mut outputs = items.clone();
+        if aggregated {
+            let keys = group_by
+                .iter()
+                .enumerate()
+                .map(|(i, expr)| scope.group_key(i, expr, &items))
+                .collect::<Result<Vec<Item>>>()?;
+            let mut aggs = Vec::new();
+            let having = select
+                .having
+                .as_ref()
+                .map(|having| Ok(lift(&scope.convert(having)?, &keys, &mut aggs)))
+                .transpose()?;
+            for (expr, _) in outputs.iter_mut() {
+                *expr = lift(expr, &keys, &mut aggs);
+            }
+            for (expr, _) in sort.iter_mut() {
+                *expr = lift(expr, &keys, &mut aggs);
+            }
+
+            let mut available: Vec<String> = keys.iter().map(|(_, name)| name.clone()).collect();
+            available.extend(aggs.iter().map(Expr::output_name));
+            let lifted = having
+                .iter()
+                .chain(outputs.iter().map(|(expr, _)| expr))
+                .chain(sort.iter().map(|(expr, _)| expr));
+            for expr in lifted {
+                if let Some(name) = expr
+                    .referenced_columns()
+                    .into_iter()
+                    .find(|name| !available.contains(name))
+                {
+                    return Err(DataFrameError::SqlError(format!(
+                        "column \"{}\" must appear in the GROUP BY clause or be used in an aggregate function",
+                        name
+                    )));
+                }
+            }
+
+            frame = frame
+                .group_by(keys.into_iter().map(|(expr, name)| named(expr, &name)))
+                .agg(aggs);
+            if let Some(having) = having {
+                frame = frame.filter(having);
+            }
+        }
+
+        if !sort.is_empty() {
+            let (by, descending): (Vec<Expr>, Vec<bool>) = sort.into_iter().unzip();
+            frame = frame.sort_by(by, &descending);
+        }
+        let names: Vec<String> = outputs.iter().map(|(_, name)| name.clone()).collect();
+        frame = frame.select(outputs.into_iter().map(|(expr, name)| named(expr, &name)));
+        if distinct {
+            frame = frame
+                .group_by(names.iter().map(|name| col(name)))
+                .agg(Vec::new());
+        }
+
+        let offset = match &query.offset {
+            Some(offset) => count(&offset.value, "OFFSET")?,
+            None => 0,
+        };
+        let limit = query
+            .limit
+            .as_ref()
+            .map(|limit| count(limit, "LIMIT"))
+            .transpose()?;
+        if offset > 0 || limit.is_some() {
+            frame = frame.slice(offset, limit);
+        }
+        Ok((frame, names))
+    }
+
+    fn plan_from(&self, from: &[TableWithJoins]) -> Result<(LazyFrame, Scope)> {
+        let relations: usize = from.iter().map(|item| 1 + item.joins.len()).sum();
+        if relations == 0 {
+            return Err(DataFrameError::SqlError(
+                "a FROM clause is required".to_string(),
+            ));
+        }
+        let mut scope = Scope {
+            sources: Vec::new(),
+            qualified: relations > 1,
+        };
+
+        let mut result: Option<LazyFrame> = None;
+        for item in from {
+            let (mut frame, source) = self.plan_relation(&item.relation, scope.qualified)?;
+            let first = scope.sources.len();
+            scope.add(source)?;
+            for join in &item.joins {
+                let (right, source) = self.plan_relation(&join.relation, true)?;
+                let left = Scope {
+                    sources: scope.sources[first..].to_vec(),
+                    qualified: true,
+                };
+                let right_scope = Scope {
+                    sources: vec![source.clone()],
+                    qualified: true,
+                };
+                scope.add(source)?;
+                frame = plan_join(
+                    frame,
+                    right,
+                    &join.join_operator,
+                    &left,
+                    &right_scope,
+                    &scope,
+                )?;
+            }
+            result = Some(match result {
+                Some(left) => left.join(frame, Vec::new(), Vec::new(), JoinType::Cross),
+                None => frame,
+            });
+        }
+        let frame = result.expect("FROM has at least one relation");
+        Ok((frame, scope))
+    }
+
+    fn plan_relation(
+        &self,
+        relation: &TableFactor,
+        qualified: bool,
+    ) -> Result<(LazyFrame, Source)> {
+        let (frame, columns, alias) = match relation {
+            TableFactor::Table {
+                name, alias, args, ..
+            } => {
+                if args.is_some() {
+                    return Err(unsupported("table functions"));
+                }
+                let table = self.table(name)?;
+                let (frame, columns) = match alias {
+                    Some(alias) => {
+                        rename_columns(table.frame.clone(), table.columns.clone(), alias)?
+                    }
+                    None => (table.frame.clone(), table.columns.clone()),
+                };
+                let alias = alias
+                    .as_ref()
+                    .map_or_else(|| table.name.clone(), |alias| alias.name.value.clone());
+                (frame, columns, alias)
+            }
+            TableFactor::Derived {
+                lateral: false,
+                subquery,
+                alias,
+            } => {
+                let (frame, columns) = self.plan_query(subquery)?;
+                match alias {
+                    Some(alias) => {
+                        let (frame, columns) = rename_columns(frame, columns, alias)?;
+                        (frame, columns, alias.name.value.clone())
+                    }
+                    None => (frame, columns, "subquery".to_string()),
+                }
+            }
+            TableFactor::NestedJoin { .. } => return Err(unsupported("parenthesized joins")),
+            other => return Err(unsupported(&format!("FROM item {}", other))),
+        };
+
+        let source = Source { alias, columns };
+        let frame = if qualified {
+            frame.select(
+                source
+                    .columns
+                    .iter()
+                    .map(|column| col(column).alias(&source.qualify(column))),
+            )
+        } else {
+            frame
+        };
+        Ok((frame, source))
+    }
+
+    /// Later tables (CTEs) shadow earlier ones of the same name.
+    fn table(&self, name: &ObjectName) -> Result<&Table> {
+        let wanted = &name.0.last().expect("object names are not empty").value;
+        self.tables
+            .iter()
+            .rev()
+            .find(|table| table.name.eq_ignore_ascii_case(wanted))
+            .ok_or_else(|| DataFrameError::SqlError(format!("unknown table '{}'", name)))
+    }
+}
+
+/// Applies the column list of `alias AS t(a, b, ...)`.
+fn rename_columns(
+    frame: LazyFrame,
+    columns: Vec<String>,
+    alias: &TableAlias,
+) -> Result<(LazyFrame, Vec<String>)> {
+    if alias.columns.is_empty() {
+        return Ok((frame, columns));
+    }
+    if alias.columns.len() != columns.len() {
+        return Err(DataFrameError::SqlError(format!(
+            "'{}' has {} columns but {} names were given",
+            alias.name,
+            columns.len(),
+            alias.columns.len()
+        )));
+    }
+    let names: Vec<String> = alias
+        .columns
+        .iter()
+        .map(|column| column.name.value.clone())
+        .collect();
+    let frame = frame.select(
+        columns
+            .iter()
+            .zip(&names)
+            .map(|(column, name)| col(column).alias(name)),
+    );
+    Ok((frame, names))
+}
+
+fn plan_join(
+    left: LazyFrame,
+    right: LazyFrame,
+    operator: &JoinOperator,
+    left_scope: &Scope,
+    right_scope: &Scope,
+    scope: &Scope,
+) -> Result<LazyFrame> {
+    let (how, constraint) = match operator {
+        JoinOperator::Inner(constraint) => (JoinType::Inner, constraint),
+        JoinOperator::LeftOuter(constraint) => (JoinType::Left, constraint),
+        JoinOperator::RightOuter(constraint) => (JoinType::Right, constraint),
+        JoinOperator::FullOuter(constraint) => (JoinType::Full, constraint),
+        JoinOperator::CrossJoin => (JoinType::Cross, &JoinConstraint::None),
+        _ => return Err(unsupported("this join type")),
+    };
+
+    let (mut left_on, mut right_on, mut residual) = (Vec::new(), Vec::new(), Vec::new());
+    match constraint {
+        JoinConstraint::None => {}
+        JoinConstraint::On(condition) => {
+            let left_columns = left_scope.all_columns();
+            let right_columns = right_scope.all_columns();
+            for part in conjuncts(condition) {
+                let part = scope.convert(part)?;
+                match equi_key(&part, &left_columns, &right_columns) {
+                    Some((left_key, right_key)) => {
+                        left_on.push(left_key);
+                        right_on.push(right_key);
+                    }
+                    None => residual.push(part),
+                }
+            }
+        }
+        JoinConstraint::Using(names) => {
+            for name in names {
+                left_on.push(col(&left_scope.resolve(&name.value)?));
+                right_on.push(col(&right_scope.resolve(&name.value)?));
+            }
+        }
+        JoinConstraint::Natural => {
+            for name in &right_scope.sources[0].columns {
+                if let Ok(left_name) = left_scope.resolve(name) {
+                    left_on.push(col(&left_name));
+                    right_on.push(col(&right_scope.sources[0].qualify(name)));
+                }
+            }
+        }
+    }
+
+    let how = match how {
+        JoinType::Inner if left_on.is_empty() => JoinType::Cross,
+        JoinType::Left | JoinType::Right | JoinType::Full
+            if left_on.is_empty() || !residual.is_empty() =>
+        {
+            return Err(unsupported(
+                "an outer join condition other than column equalities",
+            ))
+        }
+        how => how,
+    };
+    let joined = left.join(right, left_on, right_on, how);
+    Ok(match residual.into_iter().reduce(Expr::and) {
+        Some(predicate) => joined.filter(predicate),
+        None => joined,
+    })
+}
+
+/// `a = b` where each side reads only one side of the join.
+fn equi_key(expr: &Expr, left: &[String], right: &[String]) -> Option<(Expr, Expr)> {
+    let Expr::Binary {
+        left: a,
+        op: BinaryOp::Eq,
+        right: b,
+    } = expr
+    else {
+        return None;
+    };
+    let reads_only = |expr: &Expr, columns: &[String]| {
+        let names = expr.referenced_columns();
+        !names.is_empty() && names.iter().all(|name| columns.contains(name))
+    };
+    if reads_only(a, left) && reads_only(b, right) {
+        Some((*a.clone(), *b.clone()))
+    } else if reads_only(a, right) && reads_only(b, left) {
+        Some((*b.clone(), *a.clone()))
+    } else {
+        None
+    }
+}
+
+fn conjuncts(expr: &SqlExpr) -> Vec<&SqlExpr> {
+    match expr {
+        SqlExpr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let mut parts = conjuncts(left);
+            parts.extend(conjuncts(right));
+            parts
+        }
+        SqlExpr::Nested(inner) => conjuncts(inner),
+        expr => vec![expr],
+    }
+}
+
+/// Replaces group keys and aggregates with references to the columns the
+/// grouping produces, collecting each distinct aggregate into `aggs`.
+fn lift(expr: &Expr, keys: &[Item], aggs: &mut Vec<Expr>) -> Expr {
+    expr.rewrite(&mut |expr| {
+        if let Some((_, name)) = keys.iter().find(|(key, _)| key == expr) {
+            return Some(col(name));
+        }
+        if !matches!(expr, Expr::Agg { .. } | Expr::Len) {
+            return None;
+        }
+        let position = aggs
+            .iter()
+            .position(|agg| matches!(agg, Expr::Alias(inner, _) if inner.as_ref() == expr))
+            .unwrap_or_else(|| {
+                aggs.push(expr.clone().alias(&format!("__agg{}", aggs.len())));
+                aggs.len() - 1
+            });
+        Some(col(&format!("__agg{}", position)))
+    })
+}
+
+fn named(expr: Expr, name: &str) -> Expr {
+    match &expr {
+        Expr::Column(column) if column == name => expr,
+        _ => expr.alias(name),
+    }
+}
+
+fn count(expr: &SqlExpr, clause: &str) -> Result<usize> {
+    match expr {
+        SqlExpr::Value(Value::Number(n, _)) => n.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| DataFrameError::SqlError(format!("{} must be a non-negative integer", clause)))
+}
+
+/// A 1-based position in the select list, as in `ORDER BY 2`.
+fn ordinal(expr: &SqlExpr) -> Option<usize> {
+    match expr {
+        SqlExpr::Value(Value::Number(n, _)) => n.parse().ok(),
+        _ => None,
+    }
+}
+
+fn unsupported(what: &str) -> DataFrameError {
+    DataFrameError::SqlError(format!("{} is not supported", what))
+}
+
+/// A relation in `FROM`: its alias and its column names.
+#[derive(Debug, Clone)]
+struct Source {
+    alias: String,
+    columns: Vec<String>,
+}
+
+impl Source {
+    fn qualify(&self, column: &str) -> String {
+        format!("{}.{}", self.alias, column)
+    }
+
+    /// An exact match, or else the only case-insensitive one.
+    fn find(&self, name: &str) -> Option<&String> {
+        if let Some(column) = self.columns.iter().find(|column| *column == name) {
+            return Some(column);
+        }
+        let mut matches = self
+            .columns
+            .iter()
+            .filter(|column| column.eq_ignore_ascii_case(name));
+        match (matches.next(), matches.next()) {
+            (Some(column), None) => Some(column),
+            _ => None,
+        }
+    }
+}
+
+/// The relations a query reads and how their columns are named in the plan.
+#[derive(Debug, Clone)]
+struct Scope {
+    sources: Vec<Source>,
+    qualified: bool,
+}
+
+impl Scope {
+    fn add(&mut self, source: Source) -> Result<()> {
+        if self
+            .sources
+            .iter()
+            .any(|existing| existing.alias.eq_ignore_ascii_case(&source.alias))
+        {
+            return Err(DataFrameError::SqlError(format!(
+                "table name '{}' is used more than once; give it an alias",
+                source.alias
+            )));
+        }
+        self.sources.push(source);
+        Ok(())
+    }
+
+    fn column(&self, source: &Source, column: &str) -> String {
+        if self.qualified {
+            source.qualify(column)
+        } else {
+            column.to_string()
+        }
+    }
+
+    fn all_columns(&self) -> Vec<String> {
+        self.sources
+            .iter()
+            .flat_map(|source| {
+                source
+                    .columns
+                    .iter()
+                    .map(|column| self.column(source, column))
+            })
+            .collect()
+    }
+
+    fn resolve(&self, name: &str) -> Result<String> {
+        let matches: Vec<(&Source, &String)> = self
+            .sources
+            .iter()
+            .filter_map(|source| source.find(name).map(|column| (source, column)))
+            .collect();
+        match matches.as_slice() {
+            [(source, column)] => Ok(self.column(source, column)),
+            [] => Err(DataFrameError::ColumnNotFound(name.to_string())),
+            _ => Err(DataFrameError::SqlError(format!(
+                "column reference '{}' is ambiguous",
+                name
+            ))),
+        }
+    }
+
+    fn resolve_qualified(&self, table: &str, name: &str) -> Result<String> {
+        let source = self
+            .sources
+            .iter()
+            .find(|source| source.alias.eq_ignore_ascii_case(table))
+            .ok_or_else(|| DataFrameError::SqlError(format!("unknown table '{}'", table)))?;
+        let column = source
+            .find(name)
+            .ok_or_else(|| DataFrameError::ColumnNotFound(format!("{}.{}", table, name)))?;
+        Ok(self.column(source, column))
+    }
+
+    fn select_items(&self, projection: &[SelectItem]) -> Result<Vec<Item>> {
+        let mut items: Vec<Item> = Vec::new();
+        for item in projection {
+            match item {
+                SelectItem::Wildcard(_) => {
+                    for source in &self.sources {
+                        items.extend(self.expand(source));
+                    }
+                }
+                SelectItem::QualifiedWildcard(name, _) => {
+                    let alias = &name.0.last().expect("object names are not empty").value;
+                    let source = self
+                        .sources
+                        .iter()
+                        .find(|source| source.alias.eq_ignore_ascii_case(alias))
+                        .ok_or_else(|| {
+                            DataFrameError::SqlError(format!("unknown table '{}'", name))
+                        })?;
+                    items.extend(self.expand(source));
+                }
+                SelectItem::ExprWithAlias { expr, alias } => {
+                    items.push((self.convert(expr)?, alias.value.clone()));
+                }
+                SelectItem::UnnamedExpr(expr) => {
+                    let converted = self.convert(expr)?;
+                    let mut name = match expr {
+                        SqlExpr::Identifier(ident) => ident.value.clone(),
+                        SqlExpr::CompoundIdentifier(parts) => parts
+                            .last()
+                            .expect("identifiers are not empty")
+                            .value
+                            .clone(),
+                        expr => expr.to_string(),
+                    };
+                    // `SELECT a.id, b.id` keeps both by falling back to the qualified name.
+                    if items.iter().any(|(_, taken)| *taken == name) {
+                        if let Expr::Column(qualified) = &converted {
+                            name = qualified.clone();
+                        }
+                    }
+                    items.push((converted, name));
+                }
+            }
+        }
+        Ok(items)
+    }
+
+    /// The columns of `source` for `*`, qualified only where the bare name is ambiguous.
+    fn expand(&self, source: &Source) -> Vec<Item> {
+        source
+            .columns
+            .iter()
+            .map(|column| {
+                let shared = self
+                    .sources
+                    .iter()
+                    .filter(|other| other.columns.contains(column))
+                    .count()
+                    > 1;
+                let name = if shared {
+                    source.qualify(column)
+                } else {
+                    column.clone()
+                };
+                (col(&self.column(source, column)), name)
+            })
+            .collect()
+    }
+
+    /// A `GROUP BY` item: an ordinal, an expression, or failing that a select alias.
+    fn group_key(&self, index: usize, expr: &SqlExpr, items: &[Item]) -> Result<Item> {
+        let key = match ordinal(expr) {
+            Some(position) => select_item(items, position)?.0.clone(),
+            None => match (self.convert(expr), expr) {
+                (Err(DataFrameError::ColumnNotFound(name)), SqlExpr::Identifier(ident)) => items
+                    .iter()
+                    .find(|(_, output)| *output == ident.value)
+                    .map(|(expr, _)| expr.clone())
+                    .ok_or(DataFrameError::ColumnNotFound(name))?,
+                (converted, _) => converted?,
+            },
+        };
+        if key.is_aggregate() {
+            return Err(DataFrameError::SqlError(
+                "aggregate functions are not allowed in GROUP BY".to_string(),
+            ));
+        }
+        let name = match &key {
+            Expr::Column(name) => name.clone(),
+            _ => format!("__key{}", index),
+        };
+        Ok((key, name))
+    }
+
+    /// An `ORDER BY` item: an ordinal, a select alias, or an expression; the
+    /// flag is true for descending.
+    fn order_key(&self, item: &OrderByExpr, items: &[Item]) -> Result<(Expr, bool)> {
+        if item.nulls_first == Some(true) {
+            return Err(unsupported("NULLS FIRST"));
+        }
+        let expr = match (&item.expr, ordinal(&item.expr)) {
+            (_, Some(position)) => select_item(items, position)?.0.clone(),
+            (SqlExpr::Identifier(ident), None)
+                if items.iter().any(|(_, name)| *name == ident.value) =>
+            {
+                items
+                    .iter()
+                    .find(|(_, name)| *name == ident.value)
+                    .map(|(expr, _)| expr.clone())
+                    .expect("checked above")
+            }
+            (expr, None) => self.convert(expr)?,
+        };
+        Ok((expr, item.asc == Some(false)))
+    }
+
+    fn convert(&self, expr: &SqlExpr) -> Result<Expr> {
+        Ok(match expr {
+            SqlExpr::Identifier(ident) => col(&self.resolve(&ident.value)?),
+            SqlExpr::CompoundIdentifier(parts) => match parts.as_slice() {
+                [table, column] => col(&self.resolve_qualified(&table.value, &column.value)?),
+                _ => return Err(unsupported(&format!("the column reference {}", expr))),
+            },
+            SqlExpr::Value(value) => literal(value)?,
+            SqlExpr::Nested(inner) => self.convert(inner)?,
+            SqlExpr::BinaryOp { left, op, right } => {
+                let (left, right) = (self.convert(left)?, self.convert(right)?);
+                match op {
+                    BinaryOperator::Plus => left + right,
+                    BinaryOperator::Minus => left - right,
+                    BinaryOperator::Multiply => left * right,
+                    BinaryOperator::Divide => left / right,
+                    BinaryOperator::Modulo => left % right,
+                    BinaryOperator::Eq => left.eq(right),
+                    BinaryOperator::NotEq => left.neq(right),
+                    BinaryOperator::Lt => left.lt(right),
+                    BinaryOperator::LtEq => left.lt_eq(right),
+                    BinaryOperator::Gt => left.gt(right),
+                    BinaryOperator::GtEq => left.gt_eq(right),
+                    BinaryOperator::And => left.and(right),
+                    BinaryOperator::Or => left.or(right),
+                    op => return Err(unsupported(&format!("the operator {}", op))),
+                }
+            }
+            SqlExpr::UnaryOp { op, expr } => {
+                let input = self.convert(expr)?;
+                match op {
+                    UnaryOperator::Not => !input,
+                    UnaryOperator::Plus => input,
+                    UnaryOperator::Minus => match input {
+                        Expr::Literal(CellValue::Integer(n)) => lit(-n),
+                        Expr::Literal(CellValue::Float(x)) => lit(-x),
+                        input => lit(0) - input,
+                    },
+                    op => return Err(unsupported(&format!("the operator {}", op))),
+                }
+            }
+            SqlExpr::IsNull(input) => self.convert(input)?.is_null(),
+            SqlExpr::IsNotNull(input) => self.convert(input)?.is_not_null(),
+            SqlExpr::InList {
+                expr,
+                list,
+                negated,
+            } => {
+                let input = self.convert(expr)?;
+                let any = list
+                    .iter()
+                    .map(|value| Ok(input.clone().eq(self.convert(value)?)))
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .reduce(Expr::or)
+                    .ok_or_else(|| DataFrameError::SqlError("IN () needs values".to_string()))?;
+                negate(any, *negated)
+            }
+            SqlExpr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => {
+                let input = self.convert(expr)?;
+                let within = input
+                    .clone()
+                    .gt_eq(self.convert(low)?)
+                    .and(input.lt_eq(self.convert(high)?));
+                negate(within, *negated)
+            }
+            SqlExpr::Like {
+                negated,
+                any: false,
+                expr,
+                pattern,
+                escape_char: None,
+            } => negate(self.convert(expr)?.like(&pattern_text(pattern)?), *negated),
+            SqlExpr::ILike {
+                negated,
+                any: false,
+                expr,
+                pattern,
+                escape_char: None,
+            } => negate(self.convert(expr)?.ilike(&pattern_text(pattern)?), *negated),
+            SqlExpr::Trim {
+                expr,
+                trim_where: None,
+                trim_what: None,
+                trim_characters: None,
+            } => self.convert(expr)?.trim(),
+            SqlExpr::Function(function) => self.function(function)?,
+            expr => return Err(unsupported(&format!("the expression {}", expr))),
+        })
+    }
+
+    fn function(&self, function: &Function) -> Result<Expr> {
+        if function.over.is_some() {
+            return Err(unsupported("window functions"));
+        }
+        if function.filter.is_some() || !function.within_group.is_empty() {
+            return Err(unsupported(&format!("{}", function)));
+        }
+        let name = function.name.to_string().to_lowercase();
+        let (distinct, args) = match &function.args {
+            FunctionArguments::None => (false, &[][..]),
+            FunctionArguments::List(list) if list.clauses.is_empty() => (
+                list.duplicate_treatment == Some(DuplicateTreatment::Distinct),
+                &list.args[..],
+            ),
+            _ => return Err(unsupported(&format!("{}", function))),
+        };
+        if name == "count" && matches!(args, [FunctionArg::Unnamed(FunctionArgExpr::Wildcard)]) {
+            return Ok(len());
+        }
+        let mut args = args
+            .iter()
+            .map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => self.convert(expr),
+                arg => Err(unsupported(&format!("the argument {}", arg))),
+            })
+            .collect::<Result<Vec<Expr>>>()?;
+        if distinct && name != "count" {
+            return Err(unsupported(&format!("DISTINCT in {}()", name)));
+        }
+
+        let arity = |expected: usize| {
+            if args.len() == expected {
+                Ok(())
+            } else {
+                Err(DataFrameError::SqlError(format!(
+                    "{}() takes {} argument(s), got {}",
+                    name,
+                    expected,
+                    args.len()
+                )))
+            }
+        };
+        match name.as_str() {
+            "coalesce" if !args.is_empty() => return Ok(coalesce(args)),
+            "round" if args.len() == 2 => {
+                let decimals = match args.pop() {
+                    Some(Expr::Literal(CellValue::Integer(n))) => i32::try_from(n).ok(),
+                    _ => None,
+                }
+                .ok_or_else(|| {
+                    DataFrameError::SqlError("round() decimals must be an integer".to_string())
+                })?;
+                return Ok(args.remove(0).round(decimals));
+            }
+            _ => arity(1)?,
+        }
+        let input = args.remove(0);
+        Ok(match name.as_str() {
+            "count" if distinct => input.n_unique(),
+            "count" => input.count(),
+            "sum" => input.sum(),
+            "avg" | "mean" => input.mean(),
+            "min" => input.min(),
+            "max" => input.max(),
+            "abs" => input.abs(),
+            "round" => input.round(0),
+            "upper" => input.upper(),
+            "lower" => input.lower(),
+            "trim" => input.trim(),
+            "length" | "char_length" | "character_length" => input.length(),
+            _ => {
+                return Err(DataFrameError::SqlError(format!(
+                    "unknown function {}()",
+                    name
+                )))
+            }
+        })
+    }
+}
+
+fn select_item(items: &[Item], position: usize) -> Result<&Item> {
+    position
+        .checked_sub(1)
+        .and_then(|index| items.get(index))
+        .ok_or_else(|| {
+            DataFrameError::SqlError(format!("position {} is not in the select list", position))
+        })
+}
+
+fn negate(expr: Expr, negated: bool) -> Expr {
+    if negated {
+        !expr
+    } else {
+        expr
+    }
+}
+
+fn pattern_text(pattern: &SqlExpr) -> Result<String> {
+    match pattern {
+        SqlExpr::Value(Value::SingleQuotedString(text)) => Ok(text.clone()),
+        _ => Err(unsupported("a LIKE pattern that is not a string literal")),
+    }
+}
+
+fn literal(value: &Value) -> Result<Expr> {
+    let value = match value {
+        Value::Number(n, _) => n
+            .parse::<i64>()
+            .map(CellValue::Integer)
+            .or_else(|_| n.parse::<f64>().map(CellValue::Float))
+            .map_err(|_| DataFrameError::SqlError(format!("invalid number {}", n)))?,
+        Value::SingleQuotedString(text) => CellValue::Str(text.clone()),
+        Value::Boolean(b) => CellValue::Boolean(*b),
+        Value::Null => CellValue::Null,
+        value => return Err(unsupported(&format!("the literal {}", value))),
+    };
+    Ok(Expr::Literal(value))
+}
@@ -0,0 +1,381 @@
+//! Statistical hypothesis tests on columns.
+//!
+//! Every test returns a [`TestResult`] with the test statistic, a two-sided
+//! p-value and, where the reference distribution has them, degrees of freedom.
+//! Numeric tests ignore nulls and NaN.
+
+mod distributions;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::frame::{DataFrame, DataFrameError};
+use crate::scalar::ValueKey;
+use crate::series::histogram::numeric_values;
+use crate::series::ColumnArray;
+use distributions::{chi_square_sf, kolmogorov_sf, normal_two_sided, t_two_sided};
+
+/// The hypothesis tests available from the `test` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestKind {
+    /// One-sample t-test, or Welch's t-test for two samples.
+    TTest,
+    /// Paired t-test on the row-wise differences of two columns.
+    Paired,
+    /// Chi-square test of independence of two categorical columns.
+    ChiSquare,
+    /// Mann–Whitney U rank test of two samples.
+    MannWhitney,
+    /// Two-sample Kolmogorov–Smirnov test.
+    KolmogorovSmirnov,
+}
+
+impl FromStr for TestKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ttest" | "t-test" | "welch" => Ok(TestKind::TTest),
+            "paired" => Ok(TestKind::Paired),
+            "chi2" | "chisq" | "chi-square" => Ok(TestKind::ChiSquare),
+            "mannwhitney" | "mwu" | "mann-whitney" => Ok(TestKind::MannWhitney),
+            "ks" | "kolmogorov-smirnov" => Ok(TestKind::KolmogorovSmirnov),
+            _ => Err(format!(
+                "unknown test '{}', expected ttest, paired, chi2, mannwhitney or ks",
+                s
+            )),
+        }
+    }
+}
+
+/// Outcome of a hypothesis test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestResult {
+    pub test: String,
+    pub statistic: f64,
+    pub p_value: f64,
+    /// Degrees of freedom; `None` for tests without them.
+    pub df: Option<f64>,
+}
+
+impl TestResult {
+    /// One row with the test name, statistic, p-value and degrees of freedom;
+    /// `df` is null for tests without them.
+    pub fn to_frame(&self) -> DataFrame {
+        let headers = ["test", "statistic", "p_value", "df"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        DataFrame::from_columns(
+            Some(headers),
+            vec![
+                vec![self.test.clone()].into(),
+                vec![self.statistic].into(),
+                vec![self.p_value].into(),
+                vec![self.df].into(),
+            ],
+        )
+        .expect("one value per column")
+    }
+}
+
+impl fmt::Display for TestResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.test)?;
+        writeln!(f, "statistic: {:.6}", self.statistic)?;
+        write!(f, "p-value:   {}", format_p_value(self.p_value))?;
+        if let Some(df) = self.df {
+            write!(f, "\ndf:        {}", format_df(df))?;
+        }
+        Ok(())
+    }
+}
+
+fn format_p_value(p: f64) -> String {
+    if p != 0.0 && p < 1e-4 {
+        format!("{:.4e}", p)
+    } else {
+        format!("{:.6}", p)
+    }
+}
+
+fn format_df(df: f64) -> String {
+    if df.fract() == 0.0 {
+        format!("{}", df)
+    } else {
+        format!("{:.3}", df)
+    }
+}
+
+/// Tests whether the mean of `sample` equals `mu`.
+pub fn ttest_one_sample(sample: &dyn ColumnArray, mu: f64) -> Result<TestResult, DataFrameError> {
+    let values = numeric_values(sample)?;
+    let (t, df) = one_sample_t(&values, mu)?;
+    Ok(TestResult {
+        test: "One-sample t-test".to_string(),
+        statistic: t,
+        p_value: t_two_sided(t, df),
+        df: Some(df),
+    })
+}
+
+/// Welch's t-test for equal means, not assuming equal variances.
+pub fn ttest_welch(a: &dyn ColumnArray, b: &dyn ColumnArray) -> Result<TestResult, DataFrameError> {
+    let a = numeric_values(a)?;
+    let b = numeric_values(b)?;
+    require_len(&a, 2)?;
+    require_len(&b, 2)?;
+
+    let (mean_a, var_a) = mean_variance(&a);
+    let (mean_b, var_b) = mean_variance(&b);
+    let se_a = var_a / a.len() as f64;
+    let se_b = var_b / b.len() as f64;
+    let t = (mean_a - mean_b) / (se_a + se_b).sqrt();
+    let df = (se_a + se_b).powi(2)
+        / (se_a.powi(2) / (a.len() - 1) as f64 + se_b.powi(2) / (b.len() - 1) as f64);
+
+    Ok(TestResult {
+        test: "Welch two-sample t-test".to_string(),
+        statistic: t,
+        p_value: t_two_sided(t, df),
+        df: Some(df),
+    })
+}
+
+/// Tests whether the mean difference between two row-aligned columns is zero.
+///
+/// Rows where either value is missing are skipped.
+pub fn ttest_paired(
+    a: &dyn ColumnArray,
+    b: &dyn ColumnArray,
+) -> Result<TestResult, DataFrameError> {
+    numeric_values(a)?;
+    numeric_values(b)?;
+    let differences: Vec<f64> = (0..a.len().min(b.len()))
+        .filter_map(|row| Some(a.get_f64(row)? - b.get_f64(row)?))
+        .filter(|d| !d.is_nan())
+        .collect();
+    let (t, df) = one_sample_t(&differences, 0.0)?;
+    Ok(TestResult {
+        test: "Paired t-test".to_string(),
+        statistic: t,
+        p_value: t_two_sided(t, df),
+        df: Some(df),
+    })
+}
+
+/// Chi-square test of independence on the contingency table of two columns.
+///
+/// Rows where either value is null are skipped; no continuity correction is applied.
+pub fn chi_square(a: &dyn ColumnArray, b: &dyn ColumnArray) -> Result<TestResult, DataFrameError> {
+    let mut rows: HashMap<ValueKey, usize> = HashMap::new();
+    let mut cols: HashMap<ValueKey, usize> = HashMap::new();
+    let mut cells: HashMap<(usize, usize), f64> = HashMap::new();
+
+    for index in 0..a.len().min(b.len()) {
+        let (x, y) = (ValueKey::from(a.get(index)), ValueKey::from(b.get(index)));
+        if x == ValueKey::Null || y == ValueKey::Null {
+            continue;
+        }
+        let next = rows.len();
+        let row = *rows.entry(x).or_insert(next);
+        let next = cols.len();
+        let col = *cols.entry(y).or_insert(next);
+        *cells.entry((row, col)).or_default() += 1.0;
+    }
+    if rows.len() < 2 || cols.len() < 2 {
+        return Err(DataFrameError::InvalidArgument(
+            "chi-square test needs at least two distinct values in each column".to_string(),
+        ));
+    }
+
+    let mut row_totals = vec![0.0; rows.len()];
+    let mut col_totals = vec![0.0; cols.len()];
+    for (&(row, col), &count) in &cells {
+        row_totals[row] += count;
+        col_totals[col] += count;
+    }
+    let total: f64 = row_totals.iter().sum();
+
+    let mut statistic = 0.0;
+    for (row, row_total) in row_totals.iter().enumerate() {
+        for (col, col_total) in col_totals.iter().enumerate() {
+            let expected = row_total * col_total / total;
+            let observed = cells.get(&(row, col)).copied().unwrap_or(0.0);
+            statistic += (observed - expected).powi(2) / expected;
+        }
+    }
+    let df = ((rows.len() - 1) * (cols.len() - 1)) as f64;
+
+    Ok(TestResult {
+        test: "Chi-square test of independence".to_string(),
+        statistic,
+        p_value: chi_square_sf(statistic, df),
+        df: Some(df),
+    })
+}
+
+/// Mann–Whitney U test, using the normal approximation with tie and continuity
+/// corrections. The statistic is U of the first sample.
+pub fn mann_whitney_u(
+    a: &dyn ColumnArray,
+    b: &dyn ColumnArray,
+) -> Result<TestResult, DataFrameError> {
+    let a = numeric_values(a)?;
+    let b = numeric_values(b)?;
+    require_len(&a, 1)?;
+    require_len(&b, 1)?;
+
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    let combined: Vec<f64> = a.iter().chain(&b).copied().collect();
+    let ranks = ranks(&combined);
+    let rank_sum: f64 = ranks[..a.len()].iter().sum();
+    let u1 = rank_sum - n1 * (n1 + 1.0) / 2.0;
+    let u = u1.max(n1 * n2 - u1);
+
+    let n = n1 + n2;
+    let ties: f64 = tie_sizes(&combined)
+        .into_iter()
+        .map(|t| t.powi(3) - t)
+        .sum();
+    let sigma = (n1 * n2 / 12.0 * ((n + 1.0) - ties / (n * (n - 1.0)))).sqrt();
+    let p_value = if sigma == 0.0 {
+        1.0
+    } else {
+        let z = (u - n1 * n2 / 2.0 - 0.5) / sigma;
+        normal_two_sided(z.max(0.0)).min(1.0)
+    };
+
+    Ok(TestResult {
+        test: "Mann-Whitney U test".to_string(),
+        statistic: u1,
+        p_value,
+        df: None,
+    })
+}
+
+/// Two-sample Kolmogorov–Smirnov test with the asymptotic p-value.
+pub fn ks_2samp(a: &dyn ColumnArray, b: &dyn ColumnArray) -> Result<TestResult, DataFrameError> {
+    let mut a = numeric_values(a)?;
+    let mut b = numeric_values(b)?;
+    require_len(&a, 1)?;
+    require_len(&b, 1)?;
+    a.sort_by(|x, y| x.total_cmp(y));
+    b.sort_by(|x, y| x.total_cmp(y));
+
+    // Walk both sorted samples, comparing the empirical CDFs after each distinct value.
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    let (mut i, mut j) = (0, 0);
+    let mut d: f64 = 0.0;
+    while i < a.len() && j < b.len() {
+        let value = a[i].min(b[j]);
+        while i < a.len() && a[i] == value {
+            i += 1;
+        }
+        while j < b.len() && b[j] == value {
+            j += 1;
+        }
+        d = d.max((i as f64 / n1 - j as f64 / n2).abs());
+    }
+
+    let en = (n1 * n2 / (n1 + n2)).sqrt();
+    Ok(TestResult {
+        test: "Two-sample Kolmogorov-Smirnov test".to_string(),
+        statistic: d,
+        p_value: kolmogorov_sf((en + 0.12 + 0.11 / en) * d),
+        df: None,
+    })
+}
+
+/// Splits `values` by the distinct non-null values of `by`, in order of first occurrence.
+///
+/// Returns each group's label together with its rows of `values`.
+pub fn split_groups(
+    values: &dyn ColumnArray,
+    by: &dyn ColumnArray,
+) -> Vec<(String, Box<dyn ColumnArray>)> {
+    let mut lookup: HashMap<ValueKey, usize> = HashMap::new();
+    let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+    for index in 0..values.len().min(by.len()) {
+        let Some(value) = by.get(index) else {
+            continue;
+        };
+        let group = *lookup
+            .entry(ValueKey::from(Some(value.clone())))
+            .or_insert_with(|| {
+                groups.push((value.to_string(), Vec::new()));
+                groups.len() - 1
+            });
+        groups[group].1.push(index);
+    }
+    groups
+        .into_iter()
+        .map(|(label, indices)| (label, values.take(&indices)))
+        .collect()
+}
+
+fn one_sample_t(values: &[f64], mu: f64) -> Result<(f64, f64), DataFrameError> {
+    require_len(values, 2)?;
+    let (mean, variance) = mean_variance(values);
+    let n = values.len() as f64;
+    Ok(((mean - mu) / (variance / n).sqrt(), n - 1.0))
+}
+
+/// Mean and sample variance (`n - 1` denominator).
+fn mean_variance(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, variance)
+}
+
+fn require_len(values: &[f64], min: usize) -> Result<(), DataFrameError> {
+    if values.len() < min {
+        return Err(DataFrameError::InvalidArgument(format!(
+            "test needs at least {} values per sample, got {}",
+            min,
+            values.len()
+        )));
+    }
+    Ok(())
+}
+
+/// 1-based ranks of `values`, with tied values sharing their average rank.
+pub(crate) fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &index in &order[start..end] {
+            ranks[index] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Sizes of the groups of tied values (groups of one included).
+fn tie_sizes(values: &[f64]) -> Vec<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let mut sizes = Vec::new();
+    let mut start = 0;
+    while start < sorted.len() {
+        let end = start
+            + sorted[start..]
+                .iter()
+                .take_while(|&&x| x == sorted[start])
+                .count();
+        sizes.push((end - start) as f64);
+        start = end;
+    }
+    sizes
+}
@@ -11,11 +11,21 @@ pub enum Dtype {
     Float,
     Integer,
     Boolean,
+    Categorical,
     Date,
     DateTime,
+    /// A signed length of time, e.g. the difference of two dates.
+    Duration,
     Null,
 }
 
+impl Dtype {
+    /// Whether values of this type are integers or floats.
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Dtype::Integer | Dtype::Float)
+    }
+}
+
 impl From<std::io::Error> for CsvError {
     fn from(error: std::io::Error) -> Self {
         CsvError::IoError(error)
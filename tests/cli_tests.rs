@@ -0,0 +1,102 @@
+use std::process::{Command, Output};
+
+fn temp_file(name: &str, bytes: &[u8]) -> String {
+    let path = std::env::temp_dir().join(name);
+    std::fs::write(&path, bytes).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn run(args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_csv_processor"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+/// "Müller" in windows-1252, with a null to clean
+fn windows_1252_file(name: &str) -> String {
+    temp_file(name, b"name,v\nM\xFCller,\nSmith,2\n")
+}
+
+#[test]
+fn test_cleaning_commands_keep_encoding_note_out_of_the_data() {
+    let file = windows_1252_file("csv_processor_cli_clean_1252.csv");
+    for args in [
+        vec!["fill", "--value", "0", &file],
+        vec!["dropna", &file],
+        vec!["interpolate", &file],
+    ] {
+        let output = run(&args);
+        assert!(stdout(&output).starts_with("name,v\n"), "{:?}", args);
+        assert!(stderr(&output).contains("windows-1252"), "{:?}", args);
+    }
+    let filled = run(&["fill", "--value", "0", &file]);
+    assert_eq!(stdout(&filled), "name,v\nMüller,0\nSmith,2\n");
+}
+
+#[test]
+fn test_outlier_rows_as_csv_keep_encoding_note_out_of_the_data() {
+    let mut bytes = b"name,v\n".to_vec();
+    for i in 0..20 {
+        bytes.extend_from_slice(b"M\xFCller,");
+        bytes.extend_from_slice(format!("{}\n", 10 + i % 3).as_bytes());
+    }
+    bytes.extend_from_slice(b"Smith,1000\n");
+    let file = temp_file("csv_processor_cli_outliers_1252.csv", &bytes);
+
+    let output = run(&["outliers", &file, "v", "--csv"]);
+    assert_eq!(stdout(&output), "name,v\nSmith,1000\n");
+    assert!(stderr(&output).contains("windows-1252"));
+}
+
+#[test]
+fn test_dedup_writes_every_row_as_csv() {
+    let mut text = String::from("email,note\n");
+    for i in 0..40 {
+        text.push_str(&format!("user{}@example.com,{}\n", i, "x".repeat(60)));
+    }
+    text.push_str("user0@example.com,again\n");
+    let file = temp_file("csv_processor_cli_dedup.csv", text.as_bytes());
+
+    let output = run(&["dedup", &file, "email"]);
+    let text = stdout(&output);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 41);
+    assert_eq!(lines[0], "email,note");
+    assert_eq!(lines[40], format!("user39@example.com,{}", "x".repeat(60)));
+    assert!(stderr(&output).contains("1 duplicate rows removed"));
+
+    let json = run(&["dedup", &file, "email", "-f", "json"]);
+    assert!(stdout(&json).starts_with("{\"columns\":"));
+}
+
+#[test]
+fn test_test_command_renders_result_in_the_requested_format() {
+    let file = temp_file(
+        "csv_processor_cli_test.csv",
+        b"group,v\na,1\na,2\na,3\nb,4\nb,5\nb,7\n",
+    );
+    let table = run(&["test", "ttest", &file, "v", "--by", "group"]);
+    assert!(stdout(&table).contains("p-value:"));
+
+    let csv = run(&["test", "ttest", &file, "v", "--by", "group", "-f", "csv"]);
+    let text = stdout(&csv);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", text);
+    assert_eq!(lines[0], "test,statistic,p_value,df");
+    assert!(lines[1].starts_with("Welch"), "{}", text);
+}
@@ -1,67 +1,68 @@
 use csv_processor::series::*;
+use csv_processor::CellValue;
 
 #[test]
 fn test_sum_int() {
-    let col = IntegerColumn(vec![Some(1), Some(2), Some(3)]);
+    let col = IntegerColumn::from(vec![Some(1), Some(2), Some(3)]);
     assert_eq!(col.sum(), Some(6.0));
 }
 
 #[test]
 fn test_max_int() {
-    let col = IntegerColumn(vec![Some(1), Some(2), Some(3)]);
+    let col = IntegerColumn::from(vec![Some(1), Some(2), Some(3)]);
     assert_eq!(col.max(), Some(3.0));
 }
 
 #[test]
 fn test_mean_int() {
-    let col = IntegerColumn(vec![Some(1), Some(2), Some(3)]);
+    let col = IntegerColumn::from(vec![Some(1), Some(2), Some(3)]);
     let mean_float: Option<f64> = col.mean();
     assert_eq!(mean_float, Some(2.0));
 }
 
 #[test]
 fn test_sum_float() {
-    let col = FloatColumn(vec![Some(1.0), Some(2.0), Some(3.0)]);
+    let col = FloatColumn::from(vec![Some(1.0), Some(2.0), Some(3.0)]);
     assert_eq!(col.sum(), Some(6.0));
 }
 
 #[test]
 fn test_max_float() {
-    let col = FloatColumn(vec![Some(1.0), Some(2.0), Some(3.0)]);
+    let col = FloatColumn::from(vec![Some(1.0), Some(2.0), Some(3.0)]);
     assert_eq!(col.max(), Some(3.0));
 }
 
 #[test]
 fn test_min_float() {
-    let col = FloatColumn(vec![Some(1.0), Some(2.0), Some(3.0)]);
+    let col = FloatColumn::from(vec![Some(1.0), Some(2.0), Some(3.0)]);
     assert_eq!(col.min(), Some(1.0));
 }
 
 #[test]
 fn test_mean_float() {
-    let col = FloatColumn(vec![Some(1.0), Some(2.0), Some(3.0)]);
+    let col = FloatColumn::from(vec![Some(1.0), Some(2.0), Some(3.0)]);
     let mean_float: Option<f64> = col.mean();
     assert_eq!(mean_float, Some(2.0));
 }
 
 #[test]
 fn test_sum_bool() {
-    let col = BooleanColumn(vec![Some(true), Some(false), Some(true)]);
+    let col = BooleanColumn::from(vec![Some(true), Some(false), Some(true)]);
     assert_eq!(col.sum(), Some(2.0));
 }
 
 #[test]
 fn test_mean_bool() {
-    let col = BooleanColumn(vec![Some(true), Some(false), Some(true)]);
+    let col = BooleanColumn::from(vec![Some(true), Some(false), Some(true)]);
     let mean_none = col.mean();
     assert_eq!(mean_none, Some(0.6666666666666666));
 }
 
 #[test]
 fn test_max_bool() {
-    let col = BooleanColumn(vec![Some(true), Some(false), Some(true)]);
-    let col2 = BooleanColumn(vec![Some(false), Some(false), Some(false)]);
-    let col3 = BooleanColumn(vec![Some(true), Some(true), Some(true)]);
+    let col = BooleanColumn::from(vec![Some(true), Some(false), Some(true)]);
+    let col2 = BooleanColumn::from(vec![Some(false), Some(false), Some(false)]);
+    let col3 = BooleanColumn::from(vec![Some(true), Some(true), Some(true)]);
 
     assert_eq!(col.max(), Some(1.0));
     assert_eq!(col2.max(), Some(0.0));
@@ -70,9 +71,9 @@ fn test_max_bool() {
 
 #[test]
 fn test_min_bool() {
-    let col = BooleanColumn(vec![Some(true), Some(false), Some(true)]);
-    let col2 = BooleanColumn(vec![Some(false), Some(false), Some(false)]);
-    let col3 = BooleanColumn(vec![Some(true), Some(true), Some(true)]);
+    let col = BooleanColumn::from(vec![Some(true), Some(false), Some(true)]);
+    let col2 = BooleanColumn::from(vec![Some(false), Some(false), Some(false)]);
+    let col3 = BooleanColumn::from(vec![Some(true), Some(true), Some(true)]);
 
     assert_eq!(col.min(), Some(0.0));
     assert_eq!(col2.min(), Some(0.0));
@@ -81,7 +82,7 @@ fn test_min_bool() {
 
 #[test]
 fn test_str_mean() {
-    let col = StringColumn(vec![
+    let col = StringColumn::from(vec![
         Some("apple".to_string()),
         Some("banana".to_string()),
         Some("cherry".to_string()),
@@ -92,7 +93,7 @@ fn test_str_mean() {
 
 #[test]
 fn test_str_sum() {
-    let col = StringColumn(vec![
+    let col = StringColumn::from(vec![
         Some("apple".to_string()),
         Some("banana".to_string()),
         Some("cherry".to_string()),
@@ -103,7 +104,7 @@ fn test_str_sum() {
 
 #[test]
 fn test_str_max() {
-    let col = StringColumn(vec![
+    let col = StringColumn::from(vec![
         Some("apple".to_string()),
         Some("banana".to_string()),
         Some("cucumber".to_string()),
@@ -114,7 +115,7 @@ fn test_str_max() {
 
 #[test]
 fn test_str_min() {
-    let col = StringColumn(vec![
+    let col = StringColumn::from(vec![
         Some("apple".to_string()),
         Some("banana".to_string()),
         Some("cucumber".to_string()),
@@ -122,3 +123,964 @@ fn test_str_min() {
 
     assert_eq!(col.min(), None);
 }
+
+#[test]
+fn test_bitmap_push_get_and_counts() {
+    let mut bitmap = Bitmap::new();
+    for i in 0..130 {
+        bitmap.push(i % 3 != 0);
+    }
+    assert_eq!(bitmap.len(), 130);
+    assert!(!bitmap.get(0));
+    assert!(bitmap.get(128));
+    assert_eq!(bitmap.count_unset(), 44);
+
+    bitmap.set(0, true);
+    assert_eq!(bitmap.count_unset(), 43);
+    assert_eq!(Bitmap::filled(70, true).count_set(), 70);
+}
+
+#[test]
+fn test_column_layout_keeps_nulls_in_validity() {
+    let col = IntegerColumn::from(vec![Some(1), None, Some(3)]);
+    assert_eq!(col.values(), &[1, 0, 3]);
+    assert_eq!(col.null_count(), 1);
+    assert_eq!(col.iter().collect::<Vec<_>>(), vec![Some(1), None, Some(3)]);
+}
+
+#[test]
+fn test_string_column_contiguous_buffer() {
+    let col: StringColumn = vec![Some("Müller"), None, Some(""), Some("Bob")]
+        .into_iter()
+        .collect();
+    assert_eq!(col.len(), 4);
+    assert_eq!(col.null_count(), 1);
+    assert_eq!(col.value(0), Some("Müller"));
+    assert_eq!(col.value(1), None);
+    assert_eq!(col.value(2), Some(""));
+    assert_eq!(col.value(3), Some("Bob"));
+}
+
+#[test]
+fn test_boolean_column_packed_values() {
+    let col = BooleanColumn::from(vec![Some(true), None, Some(false), Some(true)]);
+    assert_eq!(col.true_count(), 2);
+    assert_eq!(col.null_count(), 1);
+    assert_eq!(col.value(2), Some(false));
+}
+
+#[test]
+fn test_low_cardinality_strings_inferred_as_categorical() {
+    let col = parse_column(vec!["male", "female", "male", "", "male", "female"]);
+    assert_eq!(col.dtype(), csv_processor::Dtype::Categorical);
+    assert_eq!(col.null_count(), 1);
+
+    let col = col.as_any().downcast_ref::<CategoricalColumn>().unwrap();
+    assert_eq!(col.categories(), ["male", "female"]);
+    assert_eq!(col.category_counts(), vec![("male", 3), ("female", 2)]);
+}
+
+#[test]
+fn test_high_cardinality_strings_stay_strings() {
+    let col = parse_column(vec!["a", "b", "c", "a"]);
+    assert_eq!(col.dtype(), csv_processor::Dtype::Str);
+}
+
+#[test]
+fn test_categorical_from_parts_counts_unused_categories() {
+    let categories = vec!["S".to_string(), "C".to_string(), "Q".to_string()];
+    let col =
+        CategoricalColumn::from_parts(categories, vec![Some(0), Some(1), None, Some(0)]).unwrap();
+    assert_eq!(col.value(1), Some("C"));
+    assert_eq!(col.category_counts(), vec![("S", 2), ("C", 1), ("Q", 0)]);
+    assert!(CategoricalColumn::from_parts(vec![], vec![Some(0)]).is_none());
+}
+
+#[test]
+fn test_value_counts_sorted_with_nulls() {
+    let col = IntegerColumn::from(vec![Some(3), Some(1), None, Some(3), Some(1), Some(3)]);
+    let counts = col.value_counts(false, true, false);
+    assert_eq!(counts.headers(), ["value", "count"]);
+    assert_eq!(counts.shape(), (3, 2));
+    assert_eq!(
+        counts.get_column(0).unwrap().get(0),
+        Some(CellValue::Integer(3))
+    );
+    assert_eq!(
+        counts.get_column(1).unwrap().get(0),
+        Some(CellValue::Integer(3))
+    );
+    assert_eq!(counts.get_column(0).unwrap().get(2), None);
+
+    let counts = col.value_counts(false, true, true);
+    assert_eq!(counts.shape(), (2, 2));
+}
+
+#[test]
+fn test_value_counts_normalize() {
+    let col: StringColumn = vec![Some("a"), Some("b"), Some("a"), Some("a")]
+        .into_iter()
+        .collect();
+    let counts = col.value_counts(true, true, true);
+    assert_eq!(counts.headers(), ["value", "proportion"]);
+    assert_eq!(
+        counts.get_column(1).unwrap().get(0),
+        Some(CellValue::Float(0.75))
+    );
+}
+
+#[test]
+fn test_categorical_value_counts_include_unused_categories() {
+    let categories = vec!["S".to_string(), "C".to_string(), "Q".to_string()];
+    let col = CategoricalColumn::from_parts(categories, vec![Some(1), Some(0), Some(1)]).unwrap();
+    let counts = col.value_counts(false, true, true);
+    assert_eq!(counts.shape(), (3, 2));
+    assert_eq!(
+        counts.get_column(0).unwrap().get(0),
+        Some(CellValue::Str("C".to_string()))
+    );
+    assert_eq!(
+        counts.get_column(1).unwrap().get(2),
+        Some(CellValue::Integer(0))
+    );
+}
+
+#[test]
+fn test_histogram_fixed_count() {
+    let col = FloatColumn::from(vec![Some(0.0), Some(1.0), Some(2.5), None, Some(10.0)]);
+    let histogram = col.histogram(&Bins::Count(2)).unwrap();
+    assert_eq!(histogram.edges, vec![0.0, 5.0, 10.0]);
+    // The last bin includes its upper edge
+    assert_eq!(histogram.counts, vec![3, 1]);
+    assert_eq!(histogram.labels(), ["[0, 5)", "[5, 10]"]);
+}
+
+#[test]
+fn test_histogram_width_edges_and_rules() {
+    let col = IntegerColumn::from((1..=100).map(Some).collect::<Vec<_>>());
+    let histogram = col.histogram(&Bins::Width(25.0)).unwrap();
+    assert_eq!(histogram.counts, vec![25, 25, 25, 25]);
+
+    let histogram = col.histogram(&Bins::Edges(vec![0.0, 50.0, 60.0])).unwrap();
+    assert_eq!(histogram.counts, vec![49, 11]);
+
+    assert_eq!(col.histogram(&Bins::Sturges).unwrap().bin_count(), 8);
+    assert_eq!(
+        col.histogram(&Bins::FreedmanDiaconis).unwrap().bin_count(),
+        5
+    );
+    assert_eq!(col.histogram(&Bins::FreedmanDiaconis).unwrap().total(), 100);
+}
+
+#[test]
+fn test_histogram_rejects_bad_input() {
+    let col = IntegerColumn::from(vec![Some(1), Some(2)]);
+    assert!(col.histogram(&Bins::Count(0)).is_err());
+    assert!(col.histogram(&Bins::Edges(vec![3.0, 1.0])).is_err());
+    assert!(col.histogram(&Bins::Edges(vec![f64::NAN, 100.0])).is_err());
+    assert!(col.histogram(&Bins::Edges(vec![0.0, f64::NAN])).is_err());
+    assert!(col.cut(&Bins::Edges(vec![0.0, f64::INFINITY])).is_err());
+    let col: StringColumn = vec![Some("a"), Some("b")].into_iter().collect();
+    assert!(col.histogram(&Bins::Sturges).is_err());
+}
+
+#[test]
+fn test_histogram_skips_infinite_values() {
+    let col = FloatColumn::from(vec![
+        Some(1.0),
+        Some(2.0),
+        Some(f64::INFINITY),
+        Some(3.0),
+        Some(f64::NEG_INFINITY),
+    ]);
+    for bins in [Bins::Sturges, Bins::FreedmanDiaconis, Bins::Width(1.0)] {
+        let histogram = col.histogram(&bins).unwrap();
+        assert_eq!(histogram.edges[0], 1.0, "{:?}", bins);
+        assert_eq!(*histogram.edges.last().unwrap(), 3.0, "{:?}", bins);
+        assert_eq!(histogram.total(), 3, "{:?}", bins);
+    }
+    let binned = col.qcut(2).unwrap();
+    assert_eq!(binned.categories(), ["[1, 2)", "[2, 3]"]);
+    assert_eq!(binned.value(2), None);
+
+    let wide = FloatColumn::from(vec![Some(-f64::MAX), Some(f64::MAX)]);
+    assert!(wide.histogram(&Bins::Count(2)).is_err());
+}
+
+#[test]
+fn test_bin_count_is_capped() {
+    let col = IntegerColumn::from((1..=100).map(Some).collect::<Vec<_>>());
+    assert_eq!(
+        col.histogram(&Bins::Count(MAX_BINS)).unwrap().bin_count(),
+        MAX_BINS
+    );
+    assert!(col.histogram(&Bins::Count(100_000_000_000)).is_err());
+    assert!(col.histogram(&Bins::Width(0.0000001)).is_err());
+    assert!(col.histogram(&Bins::Width(f64::INFINITY)).is_err());
+    assert!(col.qcut(MAX_BINS + 1).is_err());
+}
+
+#[test]
+fn test_parse_bins() {
+    assert_eq!("12".parse::<Bins>(), Ok(Bins::Count(12)));
+    assert_eq!("FD".parse::<Bins>(), Ok(Bins::FreedmanDiaconis));
+    assert_eq!(
+        "0, 18,65".parse::<Bins>(),
+        Ok(Bins::Edges(vec![0.0, 18.0, 65.0]))
+    );
+    assert!("many".parse::<Bins>().is_err());
+}
+
+#[test]
+fn test_cut_labels_rows_by_bin() {
+    let col = IntegerColumn::from(vec![Some(5), Some(30), None, Some(70), Some(200)]);
+    let binned = col.cut(&Bins::Edges(vec![0.0, 18.0, 65.0, 100.0])).unwrap();
+    assert_eq!(binned.categories(), ["[0, 18)", "[18, 65)", "[65, 100]"]);
+    assert_eq!(binned.value(0), Some("[0, 18)"));
+    assert_eq!(binned.value(1), Some("[18, 65)"));
+    assert_eq!(binned.value(2), None);
+    assert_eq!(binned.value(3), Some("[65, 100]"));
+    // Out of range
+    assert_eq!(binned.value(4), None);
+}
+
+#[test]
+fn test_qcut_equal_sized_groups() {
+    let col = IntegerColumn::from((1..=8).map(Some).collect::<Vec<_>>());
+    let binned = col.qcut(4).unwrap();
+    assert_eq!(binned.categories().len(), 4);
+    assert!(binned
+        .category_counts()
+        .iter()
+        .all(|&(_, count)| count == 2));
+}
+
+fn boxed(column: impl ColumnArray + 'static) -> Box<dyn ColumnArray> {
+    Box::new(column)
+}
+
+#[test]
+fn test_integer_arithmetic_propagates_nulls() {
+    let a = boxed(IntegerColumn::from(vec![Some(1), None, Some(3)]));
+    let b = boxed(IntegerColumn::from(vec![Some(10), Some(20), None]));
+    let sum = a.add(&b).unwrap();
+    assert_eq!(sum.dtype(), csv_processor::Dtype::Integer);
+    assert_eq!(sum.get(0), Some(CellValue::Integer(11)));
+    assert_eq!(sum.null_count(), 2);
+
+    let powers = a.pow(2).unwrap();
+    assert_eq!(powers.get(2), Some(CellValue::Integer(9)));
+}
+
+#[test]
+fn test_arithmetic_type_promotion() {
+    let ints = boxed(IntegerColumn::from(vec![Some(7), Some(-7)]));
+    let floats = boxed(FloatColumn::from(vec![Some(0.5), Some(2.0)]));
+
+    assert_eq!(
+        ints.mul(&floats).unwrap().get(0),
+        Some(CellValue::Float(3.5))
+    );
+    // Division always produces floats
+    assert_eq!(ints.div(2).unwrap().get(0), Some(CellValue::Float(3.5)));
+    assert_eq!(ints.pow(-1).unwrap().dtype(), csv_processor::Dtype::Float);
+    // Remainder keeps the sign of the dividend; modulo zero is null
+    assert_eq!(ints.rem(3).unwrap().get(1), Some(CellValue::Integer(-1)));
+    assert_eq!(ints.rem(0).unwrap().null_count(), 2);
+
+    let bools = boxed(BooleanColumn::from(vec![Some(true), Some(false)]));
+    assert_eq!(bools.add(1).unwrap().get(0), Some(CellValue::Integer(2)));
+
+    let strings: StringColumn = vec![Some("a"), Some("b")].into_iter().collect();
+    assert!(boxed(strings).add(1).is_err());
+}
+
+#[test]
+fn test_integer_overflow_is_an_error() {
+    let big = boxed(IntegerColumn::from(vec![Some(i64::MAX), None]));
+    let error = big.add(1).unwrap_err().to_string();
+    assert!(error.starts_with("Arithmetic error: integer overflow"));
+    let min = boxed(IntegerColumn::from(vec![Some(i64::MIN)]));
+    assert!(min.neg().is_err());
+    assert!(min.abs().is_err());
+}
+
+#[test]
+fn test_unary_and_round() {
+    let floats = boxed(FloatColumn::from(vec![Some(-2.5), Some(1.256), None]));
+    assert_eq!(floats.abs().unwrap().get(0), Some(CellValue::Float(2.5)));
+    assert_eq!(floats.neg().unwrap().get(1), Some(CellValue::Float(-1.256)));
+    assert_eq!(
+        floats.round(0).unwrap().get(0),
+        Some(CellValue::Float(-2.0))
+    );
+    assert_eq!(
+        floats.round(2).unwrap().get(1),
+        Some(CellValue::Float(1.26))
+    );
+
+    let ints = boxed(IntegerColumn::from(vec![Some(1234), Some(1250)]));
+    let rounded = ints.round(-2).unwrap();
+    assert_eq!(rounded.get(0), Some(CellValue::Integer(1200)));
+    assert_eq!(rounded.get(1), Some(CellValue::Integer(1200)));
+}
+
+fn values(column: &dyn ColumnArray) -> Vec<Option<CellValue>> {
+    (0..column.len()).map(|i| column.get(i)).collect()
+}
+
+#[test]
+fn test_cast_strings_to_numbers() {
+    use csv_processor::Dtype;
+
+    let text: StringColumn = vec![Some(" 1"), Some("2"), None, Some("x"), Some("2.5")]
+        .into_iter()
+        .collect();
+    let ints = text.cast(Dtype::Integer, false).unwrap();
+    assert_eq!(ints.dtype(), Dtype::Integer);
+    assert_eq!(
+        values(ints.as_ref()),
+        [
+            Some(CellValue::Integer(1)),
+            Some(CellValue::Integer(2)),
+            None,
+            None,
+            None
+        ]
+    );
+
+    let error = text.cast(Dtype::Integer, true).unwrap_err().to_string();
+    assert!(error.contains("2 of 4 values"), "{}", error);
+    assert!(error.contains("row 3 ('x'), row 4 ('2.5')"), "{}", error);
+
+    let floats = text.cast(Dtype::Float, false).unwrap();
+    assert_eq!(floats.get(4), Some(CellValue::Float(2.5)));
+    assert_eq!(floats.null_count(), 2);
+}
+
+#[test]
+fn test_cast_between_numbers_and_booleans() {
+    use csv_processor::Dtype;
+
+    let ints = IntegerColumn::from(vec![Some(0), Some(3), None]);
+    let floats = ints.cast(Dtype::Float, true).unwrap();
+    assert_eq!(floats.get(1), Some(CellValue::Float(3.0)));
+    let bools = ints.cast(Dtype::Boolean, true).unwrap();
+    assert_eq!(
+        values(bools.as_ref()),
+        [
+            Some(CellValue::Boolean(false)),
+            Some(CellValue::Boolean(true)),
+            None
+        ]
+    );
+    assert_eq!(
+        bools.cast(Dtype::Integer, true).unwrap().get(1),
+        Some(CellValue::Integer(1))
+    );
+
+    // floats truncate toward zero; NaN and out-of-range values fail
+    let floats = FloatColumn::from(vec![Some(-2.7), Some(f64::NAN), Some(1e20)]);
+    assert!(floats.cast(Dtype::Integer, true).is_err());
+    let truncated = floats.cast(Dtype::Integer, false).unwrap();
+    assert_eq!(
+        values(truncated.as_ref()),
+        [Some(CellValue::Integer(-2)), None, None]
+    );
+
+    let text: StringColumn = vec![Some("Yes"), Some("0"), Some("maybe")]
+        .into_iter()
+        .collect();
+    assert!(text.cast(Dtype::Boolean, true).is_err());
+    assert_eq!(
+        text.cast(Dtype::Boolean, false).unwrap().get(0),
+        Some(CellValue::Boolean(true))
+    );
+}
+
+#[test]
+fn test_cast_to_text() {
+    use csv_processor::Dtype;
+
+    let floats = FloatColumn::from(vec![Some(1.5), None]);
+    let text = floats.cast(Dtype::Str, true).unwrap();
+    assert_eq!(text.dtype(), Dtype::Str);
+    assert_eq!(
+        values(text.as_ref()),
+        [Some(CellValue::Str("1.5".to_string())), None]
+    );
+
+    let labels = IntegerColumn::from(vec![Some(1), Some(1), Some(2)])
+        .cast(Dtype::Categorical, true)
+        .unwrap();
+    let labels = labels.as_any().downcast_ref::<CategoricalColumn>().unwrap();
+    assert_eq!(labels.categories().len(), 2);
+    assert_eq!(
+        labels.cast(Dtype::Integer, true).unwrap().get(2),
+        Some(CellValue::Integer(2))
+    );
+
+    // same type is a copy; date casts need a date column
+    assert_eq!(
+        floats.cast(Dtype::Float, true).unwrap().get(0),
+        Some(CellValue::Float(1.5))
+    );
+    assert!(floats.cast(Dtype::Date, false).is_err());
+}
+
+fn text_column(values: &[Option<&str>]) -> StringColumn {
+    values.iter().copied().collect()
+}
+
+fn strings(column: &dyn ColumnArray) -> Vec<Option<String>> {
+    (0..column.len())
+        .map(|i| column.get(i).map(|value| value.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_str_basic_operations_keep_nulls() {
+    let names = text_column(&[Some("  Ada "), None, Some("Émile")]);
+    let ops = names.str();
+    assert_eq!(
+        values(ops.len().as_ref()),
+        [
+            Some(CellValue::Integer(6)),
+            None,
+            Some(CellValue::Integer(5))
+        ]
+    );
+    assert_eq!(
+        strings(ops.trim().as_ref()),
+        [Some("Ada".to_string()), None, Some("Émile".to_string())]
+    );
+    assert_eq!(strings(ops.upper().as_ref())[2], Some("ÉMILE".to_string()));
+    assert_eq!(strings(ops.lower().as_ref())[0], Some("  ada ".to_string()));
+    assert_eq!(
+        values(ops.contains("Ad").as_ref()),
+        [
+            Some(CellValue::Boolean(true)),
+            None,
+            Some(CellValue::Boolean(false))
+        ]
+    );
+    assert_eq!(ops.starts_with("É").get(2), Some(CellValue::Boolean(true)));
+    assert_eq!(ops.ends_with(" ").get(0), Some(CellValue::Boolean(true)));
+}
+
+#[test]
+fn test_str_regex_operations() {
+    let emails = text_column(&[Some("ada@example.com"), Some("nobody"), None]);
+    let ops = emails.str();
+    assert_eq!(
+        values(ops.is_match(r"@\w+\.com$").unwrap().as_ref()),
+        [
+            Some(CellValue::Boolean(true)),
+            Some(CellValue::Boolean(false)),
+            None
+        ]
+    );
+    assert_eq!(
+        strings(ops.extract(r"@(\w+)", 1).unwrap().as_ref()),
+        [Some("example".to_string()), None, None]
+    );
+    assert_eq!(
+        strings(ops.replace(r"(\w+)@(\w+)", "$2 at $1").unwrap().as_ref())[0],
+        Some("example at ada.com".to_string())
+    );
+    assert!(ops.is_match("(").is_err());
+    assert!(ops.extract(r"@(\w+)", 2).is_err());
+}
+
+#[test]
+fn test_str_split_slice_pad_and_parse() {
+    let dates = text_column(&[Some("2024-01-15"), Some("2024-02"), None]);
+    let parts = dates.str().split("-", None);
+    assert_eq!(parts.len(), 3);
+    assert_eq!(strings(parts[1].as_ref())[1], Some("02".to_string()));
+    assert_eq!(
+        strings(parts[2].as_ref()),
+        [Some("15".to_string()), None, None]
+    );
+    let parts = dates.str().split("-", Some(2));
+    assert_eq!(strings(parts[1].as_ref())[0], Some("01-15".to_string()));
+
+    let ops = dates.str();
+    assert_eq!(
+        strings(ops.slice(0, Some(4)).as_ref())[0],
+        Some("2024".to_string())
+    );
+    assert_eq!(
+        strings(ops.slice(-2, None).as_ref())[0],
+        Some("15".to_string())
+    );
+    assert_eq!(
+        strings(ops.slice(20, None).as_ref())[0],
+        Some(String::new())
+    );
+
+    let codes = text_column(&[Some("7"), Some("42"), None]);
+    assert_eq!(
+        strings(codes.str().pad(3, PadSide::Left, '0').as_ref()),
+        [Some("007".to_string()), Some("042".to_string()), None]
+    );
+    assert_eq!(
+        strings(codes.str().pad(4, PadSide::Both, '*').as_ref())[0],
+        Some("*7**".to_string())
+    );
+    assert_eq!(
+        codes.str().to_integer(true).unwrap().get(1),
+        Some(CellValue::Integer(42))
+    );
+    assert_eq!("RIGHT".parse::<PadSide>(), Ok(PadSide::Right));
+}
+
+#[test]
+fn test_str_namespace_on_columns() {
+    let labels: CategoricalColumn = vec![Some("b"), Some("a"), Some("b"), Some(" "), None]
+        .into_iter()
+        .collect();
+    let column: &dyn ColumnArray = &labels;
+    let stats = column.str().unwrap().stats();
+    assert_eq!(
+        stats,
+        StringStats {
+            min_length: Some(1),
+            max_length: Some(1),
+            mean_length: Some(1.0),
+            empty_count: 1,
+            distinct_count: 3,
+        }
+    );
+
+    let numbers = IntegerColumn::from(vec![Some(1)]);
+    let column: &dyn ColumnArray = &numbers;
+    assert!(column.str().is_err());
+}
+
+fn texts(column: &dyn ColumnArray) -> Vec<Option<String>> {
+    (0..column.len())
+        .map(|i| column.get(i).map(|value| value.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_parse_column_infers_dates() {
+    use csv_processor::Dtype;
+
+    let dates = parse_column(vec!["2024-01-31", "", "2024-02-29"]);
+    assert_eq!(dates.dtype(), Dtype::Date);
+    assert_eq!(
+        values(dates.as_ref()),
+        [
+            Some(CellValue::Date("2024-01-31".to_string())),
+            None,
+            Some(CellValue::Date("2024-02-29".to_string()))
+        ]
+    );
+
+    let times = parse_column(vec!["2024-01-31 10:15:00", "2024-01-31T23:59:59.5"]);
+    assert_eq!(times.dtype(), Dtype::DateTime);
+    assert_eq!(
+        texts(times.as_ref()),
+        [
+            Some("2024-01-31 10:15:00".to_string()),
+            Some("2024-01-31 23:59:59.500".to_string())
+        ]
+    );
+
+    // an impossible date or a mix of dates and datetimes stays text
+    assert_ne!(parse_column(vec!["2023-02-29"]).dtype(), Dtype::Date);
+    assert_ne!(
+        parse_column(vec!["2024-01-31", "2024-01-31 10:00"]).dtype(),
+        Dtype::DateTime
+    );
+}
+
+#[test]
+fn test_dt_components() {
+    use csv_processor::Dtype;
+
+    let times = parse_column(vec!["2024-03-10 08:05:09", "", "2023-12-31 23:00:00"]);
+    let dt = times.dt().unwrap();
+    let ints = |column: Box<dyn ColumnArray>| values(column.as_ref());
+    let int = |n| Some(CellValue::Integer(n));
+    assert_eq!(ints(dt.year()), [int(2024), None, int(2023)]);
+    assert_eq!(ints(dt.quarter()), [int(1), None, int(4)]);
+    assert_eq!(ints(dt.month()), [int(3), None, int(12)]);
+    assert_eq!(ints(dt.day()), [int(10), None, int(31)]);
+    // Sunday and Sunday, ISO numbering
+    assert_eq!(ints(dt.weekday()), [int(7), None, int(7)]);
+    assert_eq!(ints(dt.hour()), [int(8), None, int(23)]);
+    assert_eq!(ints(dt.minute()), [int(5), None, int(0)]);
+    assert_eq!(ints(dt.second()), [int(9), None, int(0)]);
+    assert_eq!(dt.date().dtype(), Dtype::Date);
+
+    let error = IntegerColumn::from(vec![Some(1)]).clone_box();
+    assert!(matches!(
+        error.dt(),
+        Err(csv_processor::frame::DataFrameError::TypeError(_))
+    ));
+}
+
+#[test]
+fn test_dt_truncate() {
+    use csv_processor::Dtype;
+
+    let times = parse_column(vec!["2024-05-16 13:47:21", "2024-01-01 00:00:00"]);
+    let truncated = |period: &str| {
+        texts(
+            times
+                .dt()
+                .unwrap()
+                .truncate(period.parse().unwrap())
+                .as_ref(),
+        )
+    };
+    assert_eq!(
+        truncated("quarter"),
+        [
+            Some("2024-04-01 00:00:00".to_string()),
+            Some("2024-01-01 00:00:00".to_string())
+        ]
+    );
+    assert_eq!(
+        truncated("week")[0],
+        Some("2024-05-13 00:00:00".to_string())
+    );
+    assert_eq!(
+        truncated("hour")[0],
+        Some("2024-05-16 13:00:00".to_string())
+    );
+    assert_eq!(
+        truncated("minute")[0],
+        Some("2024-05-16 13:47:00".to_string())
+    );
+
+    let dates = parse_column(vec!["2024-05-16"]);
+    let month = dates.dt().unwrap().truncate(Period::Month);
+    assert_eq!(month.dtype(), Dtype::Date);
+    assert_eq!(texts(month.as_ref()), [Some("2024-05-01".to_string())]);
+    assert!("fortnight".parse::<Period>().is_err());
+}
+
+#[test]
+fn test_dt_arithmetic_and_differences() {
+    use csv_processor::Dtype;
+
+    let start = parse_column(vec!["2024-01-31", "2024-02-28", ""]);
+    let end = parse_column(vec!["2024-03-02", "2024-02-27", "2024-01-01"]);
+
+    let later = start.dt().unwrap().offset(chrono::TimeDelta::days(1));
+    assert_eq!(later.dtype(), Dtype::Date);
+    assert_eq!(
+        texts(later.as_ref()),
+        [
+            Some("2024-02-01".to_string()),
+            Some("2024-02-29".to_string()),
+            None
+        ]
+    );
+    let noon = start.dt().unwrap().offset(chrono::TimeDelta::hours(12));
+    assert_eq!(noon.dtype(), Dtype::DateTime);
+    assert_eq!(
+        texts(noon.as_ref())[0],
+        Some("2024-01-31 12:00:00".to_string())
+    );
+
+    let spans = end.dt().unwrap().diff(start.as_ref()).unwrap();
+    assert_eq!(spans.dtype(), Dtype::Duration);
+    assert_eq!(
+        texts(spans.as_ref()),
+        [
+            Some("31 days 00:00:00".to_string()),
+            Some("-1 day 00:00:00".to_string()),
+            None
+        ]
+    );
+
+    // start + (end - start) = end, and back again
+    let back = start.dt().unwrap().add(spans.as_ref()).unwrap();
+    assert_eq!(texts(back.as_ref())[..2], texts(end.as_ref())[..2]);
+    let again = end.dt().unwrap().sub(spans.as_ref()).unwrap();
+    assert_eq!(texts(again.as_ref())[..2], texts(start.as_ref())[..2]);
+
+    assert!(start.dt().unwrap().add(end.as_ref()).is_err());
+    assert!(start
+        .dt()
+        .unwrap()
+        .diff(IntegerColumn::from(vec![Some(1)]).clone_box().as_ref())
+        .is_err());
+}
+
+#[test]
+fn test_cast_dates_and_durations() {
+    use csv_processor::Dtype;
+
+    let text = text_column(&[Some("2024-06-01 18:30:00"), Some("soon"), None]);
+    assert!(text.cast(Dtype::Date, true).is_err());
+    let dates = text.cast(Dtype::Date, false).unwrap();
+    assert_eq!(
+        texts(dates.as_ref()),
+        [Some("2024-06-01".to_string()), None, None]
+    );
+    let times = dates.cast(Dtype::DateTime, true).unwrap();
+    assert_eq!(
+        texts(times.as_ref())[0],
+        Some("2024-06-01 00:00:00".to_string())
+    );
+
+    let micros = IntegerColumn::from(vec![Some(90_061_500_000), Some(-1), None]);
+    let durations = micros.cast(Dtype::Duration, true).unwrap();
+    assert_eq!(
+        texts(durations.as_ref()),
+        [
+            Some("1 day 01:01:01.5".to_string()),
+            Some("-0 days 00:00:00.000001".to_string()),
+            None
+        ]
+    );
+    assert_eq!(
+        durations.cast(Dtype::Integer, true).unwrap().get(0),
+        Some(CellValue::Integer(90_061_500_000))
+    );
+}
+
+fn floats(column: &dyn ColumnArray) -> Vec<Option<f64>> {
+    (0..column.len()).map(|i| column.get_f64(i)).collect()
+}
+
+#[test]
+fn test_rolling_and_expanding_windows() {
+    let column = boxed(IntegerColumn::from(vec![
+        Some(1),
+        Some(2),
+        None,
+        Some(4),
+        Some(5),
+    ]));
+    let pairs = column.rolling(2).unwrap();
+    assert_eq!(
+        floats(pairs.sum().as_ref()),
+        [None, Some(3.0), None, None, Some(9.0)]
+    );
+    let pairs = pairs.with_min_periods(1);
+    assert_eq!(
+        floats(pairs.sum().as_ref()),
+        [Some(1.0), Some(3.0), Some(2.0), Some(4.0), Some(9.0)]
+    );
+    assert_eq!(
+        floats(pairs.mean().as_ref()),
+        [Some(1.0), Some(1.5), Some(2.0), Some(4.0), Some(4.5)]
+    );
+
+    let triples = column.rolling(3).unwrap().with_min_periods(2);
+    assert_eq!(
+        floats(triples.min().as_ref()),
+        [None, Some(1.0), Some(1.0), Some(2.0), Some(4.0)]
+    );
+    assert_eq!(
+        floats(triples.max().as_ref()),
+        [None, Some(2.0), Some(2.0), Some(4.0), Some(5.0)]
+    );
+    let std = floats(triples.std().as_ref());
+    assert_eq!(std[..3], [None, Some(0.5f64.sqrt()), Some(0.5f64.sqrt())]);
+    assert!((std[3].unwrap() - 2.0f64.sqrt()).abs() < 1e-12);
+
+    let expanding = column.expanding().unwrap();
+    assert_eq!(
+        floats(expanding.max().as_ref()),
+        [Some(1.0), Some(2.0), Some(2.0), Some(4.0), Some(5.0)]
+    );
+    assert_eq!(floats(expanding.mean().as_ref())[4], Some(3.0));
+    assert_eq!(floats(expanding.var().as_ref())[0], None);
+
+    // infinities leave the window again without poisoning later results
+    let spiky = boxed(FloatColumn::from(vec![
+        Some(0.0),
+        Some(1.0),
+        Some(f64::INFINITY),
+        Some(2.0),
+        Some(3.0),
+        Some(4.0),
+    ]));
+    let pairs = spiky.rolling(2).unwrap();
+    assert_eq!(
+        floats(pairs.sum().as_ref()),
+        [
+            None,
+            Some(1.0),
+            Some(f64::INFINITY),
+            Some(f64::INFINITY),
+            Some(5.0),
+            Some(7.0)
+        ]
+    );
+    assert_eq!(floats(pairs.mean().as_ref())[4..], [Some(2.5), Some(3.5)]);
+    let var = floats(pairs.var().as_ref());
+    assert!(var[2].unwrap().is_nan());
+    assert_eq!(var[4..], [Some(0.5), Some(0.5)]);
+    assert_eq!(floats(pairs.std().as_ref())[5], Some(0.5f64.sqrt()));
+    let opposite = boxed(FloatColumn::from(vec![
+        Some(f64::INFINITY),
+        Some(f64::NEG_INFINITY),
+        Some(1.0),
+    ]));
+    let sums = floats(opposite.rolling(2).unwrap().sum().as_ref());
+    assert!(sums[1].unwrap().is_nan());
+    assert_eq!(sums[2], Some(f64::NEG_INFINITY));
+
+    assert!(column.rolling(0).is_err());
+    let text = boxed(text_column(&[Some("a")]));
+    assert!(text.rolling(2).is_err());
+    assert!(text.expanding().is_err());
+}
+
+#[test]
+fn test_cumulative_operations() {
+    use csv_processor::Dtype;
+
+    let column = boxed(IntegerColumn::from(vec![Some(2), None, Some(-3), Some(4)]));
+    let int = |n| Some(CellValue::Integer(n));
+    let sum = column.cum_sum().unwrap();
+    assert_eq!(sum.dtype(), Dtype::Integer);
+    assert_eq!(values(sum.as_ref()), [int(2), None, int(-1), int(3)]);
+    assert_eq!(
+        values(column.cum_prod().unwrap().as_ref()),
+        [int(2), None, int(-6), int(-24)]
+    );
+    assert_eq!(
+        values(column.cum_max().unwrap().as_ref()),
+        [int(2), None, int(2), int(4)]
+    );
+    assert_eq!(
+        values(column.cum_min().unwrap().as_ref()),
+        [int(2), None, int(-3), int(-3)]
+    );
+
+    let flags = boxed(BooleanColumn::from(vec![
+        Some(true),
+        Some(false),
+        Some(true),
+    ]));
+    assert_eq!(
+        values(flags.cum_sum().unwrap().as_ref()),
+        [int(1), int(1), int(2)]
+    );
+    let halves = boxed(FloatColumn::from(vec![Some(0.5), Some(0.5)]));
+    assert_eq!(
+        floats(halves.cum_prod().unwrap().as_ref()),
+        [Some(0.5), Some(0.25)]
+    );
+
+    let huge = boxed(IntegerColumn::from(vec![Some(i64::MAX), Some(1)]));
+    assert!(matches!(
+        huge.cum_sum(),
+        Err(csv_processor::frame::DataFrameError::ArithmeticError(_))
+    ));
+}
+
+#[test]
+fn test_shift_diff_and_pct_change() {
+    use csv_processor::Dtype;
+
+    let names = boxed(text_column(&[Some("a"), Some("b"), Some("c")]));
+    assert_eq!(
+        strings(names.shift(1).as_ref()),
+        [None, Some("a".to_string()), Some("b".to_string())]
+    );
+    assert_eq!(
+        strings(names.shift(-2).as_ref()),
+        [Some("c".to_string()), None, None]
+    );
+    assert_eq!(strings(names.shift(5).as_ref()), [None, None, None]);
+    for periods in [isize::MIN, isize::MAX] {
+        assert_eq!(strings(names.shift(periods).as_ref()), [None, None, None]);
+    }
+
+    let sales = boxed(IntegerColumn::from(vec![
+        Some(10),
+        Some(15),
+        None,
+        Some(12),
+    ]));
+    let diff = sales.diff(1).unwrap();
+    assert_eq!(diff.dtype(), Dtype::Integer);
+    assert_eq!(
+        values(diff.as_ref()),
+        [None, Some(CellValue::Integer(5)), None, None]
+    );
+    assert_eq!(
+        values(sales.diff(-1).unwrap().as_ref())[0],
+        Some(CellValue::Integer(-5))
+    );
+    assert_eq!(
+        floats(sales.pct_change(1).unwrap().as_ref()),
+        [None, Some(0.5), None, None]
+    );
+    assert_eq!(
+        values(sales.diff(isize::MIN).unwrap().as_ref()),
+        [None, None, None, None]
+    );
+    assert_eq!(
+        floats(sales.pct_change(isize::MIN).unwrap().as_ref()),
+        [None, None, None, None]
+    );
+
+    let days = parse_column(vec!["2024-01-01", "2024-01-03", "2024-01-10"]);
+    let gaps = days.diff(1).unwrap();
+    assert_eq!(gaps.dtype(), Dtype::Duration);
+    assert_eq!(
+        gaps.get(2).map(|gap| gap.to_string()),
+        Some("7 days 00:00:00".to_string())
+    );
+    assert!(names.diff(1).is_err());
+}
+
+#[test]
+fn test_rank_methods() {
+    let column = boxed(FloatColumn::from(vec![
+        Some(3.0),
+        Some(1.0),
+        Some(3.0),
+        None,
+        Some(2.0),
+    ]));
+    let ranks = |method: &str, descending: bool| {
+        floats(
+            column
+                .rank(method.parse().unwrap(), descending)
+                .unwrap()
+                .as_ref(),
+        )
+    };
+    assert_eq!(
+        ranks("average", false),
+        [Some(3.5), Some(1.0), Some(3.5), None, Some(2.0)]
+    );
+    assert_eq!(
+        ranks("min", false),
+        [Some(3.0), Some(1.0), Some(3.0), None, Some(2.0)]
+    );
+    assert_eq!(
+        ranks("max", false),
+        [Some(4.0), Some(1.0), Some(4.0), None, Some(2.0)]
+    );
+    assert_eq!(
+        ranks("dense", false),
+        [Some(3.0), Some(1.0), Some(3.0), None, Some(2.0)]
+    );
+    assert_eq!(
+        ranks("first", false),
+        [Some(3.0), Some(1.0), Some(4.0), None, Some(2.0)]
+    );
+    assert_eq!(
+        ranks("average", true),
+        [Some(1.5), Some(4.0), Some(1.5), None, Some(3.0)]
+    );
+    assert_eq!(
+        column.rank(RankMethod::Dense, false).unwrap().dtype(),
+        csv_processor::Dtype::Integer
+    );
+    assert!("median".parse::<RankMethod>().is_err());
+}
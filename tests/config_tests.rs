@@ -1,9 +1,11 @@
 use csv_processor::config::*;
+use csv_processor::series::Bins;
 
 #[test]
 fn test_parse_command_success() {
     assert_eq!(parse_command("na".to_string()).unwrap(), Command::CheckNAs);
     assert_eq!(parse_command("info".to_string()).unwrap(), Command::Info);
+    assert_eq!(parse_command("shell".to_string()).unwrap(), Command::Shell);
 }
 
 #[test]
@@ -20,3 +22,304 @@ fn test_parse_command_errors() {
         ))
     );
 }
+
+#[test]
+fn test_parse_config_read_options() {
+    let args: Vec<String> = [
+        "csv_processor",
+        "info",
+        "data.tsv",
+        "-d",
+        "tab",
+        "--no-header",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    let config = parse_config(&args).unwrap();
+    assert_eq!(config.command(), &Command::Info);
+    assert_eq!(config.read_options().delimiter, Some(b'\t'));
+    assert_eq!(config.read_options().has_header, Some(false));
+}
+
+#[test]
+fn test_parse_config_freq_columns() {
+    let args: Vec<String> = [
+        "csv_processor",
+        "freq",
+        "data.csv",
+        "Sex",
+        "-n",
+        "5",
+        "Embarked",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    let config = parse_config(&args).unwrap();
+    assert_eq!(config.command(), &Command::Freq);
+    assert_eq!(config.columns(), ["Sex", "Embarked"]);
+    assert_eq!(config.top(), 5);
+    assert!(!config.normalize());
+}
+
+#[test]
+fn test_parse_config_hist_bins() {
+    let args: Vec<String> = ["csv_processor", "hist", "data.csv", "--bins", "fd"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let config = parse_config(&args).unwrap();
+    assert_eq!(config.command(), &Command::Hist);
+    assert_eq!(config.bins(), &Bins::FreedmanDiaconis);
+
+    for width in ["0", "inf", "NaN"] {
+        let args: Vec<String> = ["csv_processor", "hist", "data.csv", "--bin-width", width]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert!(parse_config(&args).is_err(), "{}", width);
+    }
+}
+
+#[test]
+fn test_parse_config_test_kind_before_file() {
+    let args: Vec<String> = [
+        "csv_processor",
+        "test",
+        "ttest",
+        "titanic.csv",
+        "Fare",
+        "--by",
+        "Survived",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    let config = parse_config(&args).unwrap();
+    assert_eq!(config.command(), &Command::Test);
+    assert_eq!(config.test(), Some(csv_processor::stats::TestKind::TTest));
+    assert_eq!(config.filename(), "titanic.csv");
+    assert_eq!(config.columns(), ["Fare"]);
+    assert_eq!(config.by(), Some("Survived"));
+}
+
+fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_parse_config_help_and_version() {
+    let version = parse_config(&args(&["csv_processor", "--version"])).unwrap_err();
+    assert_eq!(
+        version,
+        ConfigError::DisplayHelp(format!("csv_processor {}\n", env!("CARGO_PKG_VERSION")))
+    );
+
+    let Err(ConfigError::DisplayHelp(help)) =
+        parse_config(&args(&["csv_processor", "hist", "--help"]))
+    else {
+        panic!("expected subcommand help");
+    };
+    assert!(help.contains("--bin-width"));
+    assert!(!help.contains("--normalize"));
+}
+
+#[test]
+fn test_parse_config_files_output_and_format() {
+    let config = parse_config(&args(&[
+        "csv_processor",
+        "-f",
+        "json",
+        "na",
+        "jan.csv",
+        "feb.csv",
+        "--output",
+        "report.json",
+    ]))
+    .unwrap();
+    assert_eq!(config.command(), &Command::CheckNAs);
+    assert_eq!(config.files(), ["jan.csv", "feb.csv"]);
+    assert_eq!(config.filename(), "jan.csv");
+    assert_eq!(config.output(), Some("report.json"));
+    assert_eq!(config.format(), csv_processor::OutputFormat::Json);
+
+    let config = parse_config(&args(&["csv_processor", "info", "a.csv"])).unwrap();
+    assert_eq!(config.output(), None);
+    assert_eq!(config.format(), csv_processor::OutputFormat::Table);
+}
+
+#[test]
+fn test_parse_config_errors() {
+    assert_eq!(
+        parse_config(&args(&["csv_processor", "frobnicate", "a.csv"])).unwrap_err(),
+        ConfigError::UnknownCommand("frobnicate".to_string())
+    );
+    assert_eq!(
+        parse_config(&args(&["csv_processor", "info", "a.csv", "--bogus"])).unwrap_err(),
+        ConfigError::UnknownOption("--bogus".to_string())
+    );
+    assert!(matches!(
+        parse_config(&args(&[
+            "csv_processor",
+            "info",
+            "a.csv",
+            "--format",
+            "yaml"
+        ])),
+        Err(ConfigError::InvalidValue { .. })
+    ));
+    assert!(matches!(
+        parse_config(&args(&["csv_processor", "freq"])),
+        Err(ConfigError::MissingArguments(_))
+    ));
+}
+
+#[test]
+fn test_parse_config_completions() {
+    let config = parse_config(&args(&["csv_processor", "completions", "zsh"])).unwrap();
+    assert_eq!(config.command(), &Command::Completions);
+    assert_eq!(config.completions(), Some(clap_complete::Shell::Zsh));
+
+    let mut script = Vec::new();
+    clap_complete::generate(
+        clap_complete::Shell::Fish,
+        &mut cli(),
+        "csv_processor",
+        &mut script,
+    );
+    let script = String::from_utf8(script).unwrap();
+    assert!(script.contains("complete -c csv_processor"));
+    assert!(script.contains("outliers"));
+}
+
+#[test]
+fn test_parse_config_row_commands() {
+    use csv_processor::io::RowSelection;
+
+    let config = parse_config(&args(&["csv_processor", "tail", "a.csv", "-n", "3"])).unwrap();
+    assert_eq!(config.command(), &Command::Tail);
+    assert_eq!(config.selection(), Some(RowSelection::Tail(3)));
+
+    let config = parse_config(&args(&[
+        "csv_processor",
+        "sample",
+        "a.csv",
+        "Name",
+        "--frac",
+        "0.25",
+        "--seed",
+        "9",
+    ]))
+    .unwrap();
+    assert_eq!(config.columns(), ["Name"]);
+    assert_eq!(
+        config.selection(),
+        Some(RowSelection::Fraction {
+            fraction: 0.25,
+            seed: Some(9)
+        })
+    );
+    assert!(parse_config(&args(&[
+        "csv_processor",
+        "sample",
+        "a.csv",
+        "-n",
+        "5",
+        "--frac",
+        "0.5"
+    ]))
+    .is_err());
+}
+
+#[test]
+fn test_parse_config_dedup() {
+    let config = parse_config(&args(&[
+        "csv_processor",
+        "dedup",
+        "crm.csv",
+        "email",
+        "--keep",
+        "none",
+        "--report",
+    ]))
+    .unwrap();
+    assert_eq!(config.command(), &Command::Dedup);
+    assert_eq!(config.columns(), ["email"]);
+    assert_eq!(config.keep(), csv_processor::frame::Keep::None);
+    assert!(config.report());
+}
+
+#[test]
+fn test_parse_config_null_commands() {
+    use csv_processor::frame::DropHow;
+    use csv_processor::series::FillStrategy;
+
+    let fill = parse_config(&args(&[
+        "csv_processor",
+        "fill",
+        "a.csv",
+        "Age",
+        "--value",
+        "-1",
+    ]))
+    .unwrap();
+    assert_eq!(fill.command(), &Command::Fill);
+    assert_eq!(
+        fill.fill(),
+        Some(&FillStrategy::Value(csv_processor::CellValue::Integer(-1)))
+    );
+
+    let fill = parse_config(&args(&[
+        "csv_processor",
+        "fill",
+        "a.csv",
+        "--strategy",
+        "bfill",
+    ]))
+    .unwrap();
+    assert_eq!(fill.fill(), Some(&FillStrategy::Backward));
+    assert!(parse_config(&args(&["csv_processor", "fill", "a.csv"])).is_err());
+
+    let drop = parse_config(&args(&[
+        "csv_processor",
+        "dropna",
+        "a.csv",
+        "--thresh",
+        "2",
+    ]))
+    .unwrap();
+    assert_eq!(drop.command(), &Command::DropNulls);
+    assert_eq!(drop.how(), DropHow::Any);
+    assert_eq!(drop.thresh(), Some(2));
+    assert!(matches!(
+        parse_config(&args(&[
+            "csv_processor",
+            "dropna",
+            "a.csv",
+            "--how",
+            "some"
+        ])),
+        Err(ConfigError::InvalidValue { .. })
+    ));
+}
+
+#[test]
+fn test_parse_config_na_detailed() {
+    let config = parse_config(&args(&[
+        "csv_processor",
+        "na",
+        "a.csv",
+        "--detailed",
+        "-n",
+        "3",
+    ]))
+    .unwrap();
+    assert_eq!(config.command(), &Command::CheckNAs);
+    assert!(config.detailed());
+    assert_eq!(config.top(), 3);
+
+    let config = parse_config(&args(&["csv_processor", "na", "a.csv", "b.csv"])).unwrap();
+    assert!(!config.detailed());
+    assert_eq!(config.files().len(), 2);
+}
@@ -232,3 +232,760 @@ fn test_create_df_from_heter_columns() {
     assert_eq!(df.get_column(3).unwrap().sum().unwrap(), 2.0);
     assert_eq!(df.get_column(4).unwrap().null_count(), 1);
 }
+
+fn numeric_frame() -> DataFrame {
+    let columns: Vec<Box<dyn ColumnArray>> = vec![
+        vec![Some(1i64), Some(2), Some(3), Some(4), None].into(),
+        vec![Some(2.0), Some(4.0), Some(6.0), Some(8.0), Some(100.0)].into(),
+        vec![Some(4i64), Some(3), Some(2), Some(1), Some(0)].into(),
+        vec![
+            "a".to_string(),
+            "b".to_string(),
+            "c".to_string(),
+            "d".to_string(),
+            "e".to_string(),
+        ]
+        .into(),
+    ];
+    let headers = ["x", "double", "reverse", "label"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    DataFrame::from_columns(Some(headers), columns).unwrap()
+}
+
+#[test]
+fn test_corr_pearson_pairwise_complete() {
+    let corr = numeric_frame().corr(frame::CorrMethod::Pearson).unwrap();
+    // Square matrix over the numeric columns, plus the label column
+    assert_eq!(corr.shape(), (3, 4));
+    assert_eq!(corr.headers(), ["column", "x", "double", "reverse"]);
+    // The outlier in `double` sits on the row where `x` is null
+    let x = corr.column("x").unwrap();
+    assert_eq!(x.get(1), Some(CellValue::Float(1.0)));
+    assert_eq!(x.get(2), Some(CellValue::Float(-1.0)));
+}
+
+#[test]
+fn test_corr_rank_methods() {
+    let df = numeric_frame();
+    let spearman = df.corr(frame::CorrMethod::Spearman).unwrap();
+    assert_eq!(
+        spearman.column("double").unwrap().get(2),
+        Some(CellValue::Float(-1.0))
+    );
+    let kendall = df.corr(frame::CorrMethod::Kendall).unwrap();
+    assert_eq!(
+        kendall.column("x").unwrap().get(1),
+        Some(CellValue::Float(1.0))
+    );
+
+    // ties in x, in y and in both; tau-b from the pairwise definition
+    let tied = DataFrame::from_columns(
+        Some(vec!["x".to_string(), "y".to_string()]),
+        vec![
+            vec![1i64, 2, 2, 3, 3, 3, 4, 5, 5, 6, 7, 8].into(),
+            vec![2i64, 1, 3, 3, 3, 5, 4, 4, 6, 6, 9, 7].into(),
+        ],
+    )
+    .unwrap()
+    .corr(frame::CorrMethod::Kendall)
+    .unwrap();
+    let tau = tied.column("y").unwrap().get_f64(0).unwrap();
+    assert_close(tau, 0.8032786885);
+
+    // enough rows that the pair counts overflow i64 when multiplied
+    let n = 100_000i64;
+    let large = DataFrame::from_columns(
+        Some(vec!["x".to_string(), "y".to_string()]),
+        vec![
+            (0..n).collect::<Vec<_>>().into(),
+            (0..n).map(|i| (n - i) / 2).collect::<Vec<_>>().into(),
+        ],
+    )
+    .unwrap()
+    .corr(frame::CorrMethod::Kendall)
+    .unwrap();
+    // every pair is discordant except the n/2 - 1 pairs tied in y
+    let tau = large.column("y").unwrap().get_f64(0).unwrap();
+    let all = (n * (n - 1) / 2) as f64;
+    let tied = (n / 2 - 1) as f64;
+    assert_close(tau, -(all - tied) / (all * (all - tied)).sqrt());
+    assert_eq!(
+        "Kendall".parse::<frame::CorrMethod>(),
+        Ok(frame::CorrMethod::Kendall)
+    );
+}
+
+#[test]
+fn test_cov_sample_denominator() {
+    let cov = numeric_frame()
+        .select(&["x", "reverse"])
+        .unwrap()
+        .cov()
+        .unwrap();
+    let x = cov.column("x").unwrap();
+    assert_eq!(x.get(0), Some(CellValue::Float(5.0 / 3.0)));
+    assert_eq!(x.get(1), Some(CellValue::Float(-5.0 / 3.0)));
+}
+
+#[test]
+fn test_select_unknown_column() {
+    assert!(numeric_frame().select(&["missing"]).is_err());
+}
+
+fn float_column(values: &[f64]) -> Box<dyn ColumnArray> {
+    values.to_vec().into()
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-6,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_ttests_match_reference_values() {
+    let a = float_column(&[5.1, 4.9, 6.2, 5.8, 6.0, 5.5, 5.3]);
+    let b = float_column(&[6.5, 6.0, 7.1, 6.8, 6.6]);
+
+    let one = stats::ttest_one_sample(a.as_ref(), 5.0).unwrap();
+    assert_close(one.statistic, 2.9979246);
+    assert_close(one.p_value, 0.0240726);
+    assert_eq!(one.df, Some(6.0));
+    let row = one.to_frame();
+    assert_eq!(row.headers(), ["test", "statistic", "p_value", "df"]);
+    assert_eq!(
+        row.get_column(3).unwrap().get(0),
+        Some(CellValue::Float(6.0))
+    );
+
+    let welch = stats::ttest_welch(a.as_ref(), b.as_ref()).unwrap();
+    assert_close(welch.statistic, -4.1215128);
+    assert_close(welch.p_value, 0.0022654);
+    assert_close(welch.df.unwrap(), 9.5876084);
+
+    // Rows beyond the shorter column are not paired
+    let paired = stats::ttest_paired(a.as_ref(), b.as_ref()).unwrap();
+    assert_close(paired.statistic, -1.0 / 0.017f64.sqrt());
+    assert_eq!(paired.df, Some(4.0));
+}
+
+#[test]
+fn test_chi_square_independence() {
+    let sex: Box<dyn ColumnArray> = ["m", "m", "m", "f", "f", "f", "m", "f"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>()
+        .into();
+    let survived: Box<dyn ColumnArray> =
+        vec![false, false, true, true, true, true, false, false].into();
+    let result = stats::chi_square(sex.as_ref(), survived.as_ref()).unwrap();
+    assert_close(result.statistic, 2.0);
+    assert_close(result.p_value, 0.1572992);
+    assert_eq!(result.df, Some(1.0));
+}
+
+#[test]
+fn test_rank_tests_and_groups() {
+    let values = float_column(&[1.0, 2.0, 3.0, 4.0, 10.0, 11.0, 12.0, 13.0]);
+    let by: Box<dyn ColumnArray> = vec![0i64, 0, 0, 0, 1, 1, 1, 1].as_slice().into();
+    let groups = stats::split_groups(values.as_ref(), by.as_ref());
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[1].0, "1");
+
+    let (a, b) = (groups[0].1.as_ref(), groups[1].1.as_ref());
+    let mwu = stats::mann_whitney_u(a, b).unwrap();
+    assert_eq!(mwu.statistic, 0.0);
+    assert_close(mwu.p_value, 0.0303828);
+
+    let ks = stats::ks_2samp(a, b).unwrap();
+    assert_eq!(ks.statistic, 1.0);
+    assert!(ks.p_value < 0.05);
+    assert_eq!(
+        "mwu".parse::<stats::TestKind>(),
+        Ok(stats::TestKind::MannWhitney)
+    );
+}
+
+#[test]
+fn test_outlier_report_flags_by_method() {
+    let mut values: Vec<Option<f64>> = (0..20).map(|i| Some(10.0 + (i % 5) as f64)).collect();
+    values[7] = Some(100.0);
+    values[12] = None;
+    let columns: Vec<Box<dyn ColumnArray>> = vec![values.into(), vec!["x".to_string(); 20].into()];
+    let df =
+        DataFrame::from_columns(Some(vec!["v".to_string(), "s".to_string()]), columns).unwrap();
+
+    let report = reporter::generate_outlier_report(&df, &reporter::OutlierOptions::default());
+    // String columns are skipped
+    assert_eq!(report.columns.len(), 1);
+    let v = &report.columns[0];
+    assert_eq!(v.iqr_rows, vec![7]);
+    assert_eq!(v.zscore_rows, vec![7]);
+    assert_eq!(v.mad_rows, vec![7]);
+    assert_eq!(report.flagged_rows(), vec![7]);
+    assert_eq!(report.to_frame().shape(), (1, 6));
+
+    let strict = reporter::OutlierOptions::new().with_iqr_factor(0.1);
+    let report = reporter::generate_outlier_report(&df, &strict);
+    assert!(report.columns[0].iqr_rows.len() > 1);
+}
+
+#[test]
+fn test_to_csv_writes_nulls_as_empty_fields() {
+    let columns: Vec<Box<dyn ColumnArray>> = vec![
+        vec![Some(1i64), None].into(),
+        vec![Some("a, b".to_string()), Some("c".to_string())].into(),
+    ];
+    let df =
+        DataFrame::from_columns(Some(vec!["n".to_string(), "s".to_string()]), columns).unwrap();
+    assert_eq!(df.to_csv().unwrap(), "n,s\n1,\"a, b\"\n,c\n");
+}
+
+#[test]
+fn test_with_column_derived_bonus() {
+    let columns: Vec<Box<dyn ColumnArray>> = vec![
+        vec![50_000i64, 64_000].as_slice().into(),
+        vec![10.0, 5.0].into(),
+    ];
+    let headers = vec!["salary".to_string(), "bonus_percentage".to_string()];
+    let df = DataFrame::from_columns(Some(headers), columns).unwrap();
+
+    let bonus = df
+        .column("salary")
+        .unwrap()
+        .mul(df.column("bonus_percentage").unwrap())
+        .unwrap()
+        .div(100)
+        .unwrap();
+    let df = df.with_column("bonus", bonus).unwrap();
+    assert_eq!(df.shape(), (2, 3));
+    assert_eq!(
+        df.column("bonus").unwrap().get(1),
+        Some(CellValue::Float(3200.0))
+    );
+
+    // Same name replaces, wrong length is rejected
+    let df = df
+        .with_column("bonus", vec![1i64, 2].as_slice().into())
+        .unwrap();
+    assert_eq!(df.shape(), (2, 3));
+    assert!(df.with_column("x", vec![1i64].as_slice().into()).is_err());
+}
+
+fn names_frame() -> DataFrame {
+    DataFrame::from_columns(
+        Some(vec!["name".to_string(), "score".to_string()]),
+        vec![
+            vec![
+                "Müller-Lüdenscheidt".to_string(),
+                "李小龙李小龙李小龙".to_string(),
+            ]
+            .into(),
+            vec![1.23456, 20.0].into(),
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_display_truncates_by_grapheme_width() {
+    let options = frame::DisplayOptions::new().with_max_col_width(7);
+    let text = names_frame().display(&options).to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    // wide characters count twice, so at most three fit before the ellipsis
+    assert_eq!(lines[3], "│ Müller… │ 1.23456 │");
+    assert_eq!(lines[4], "│ 李小龙… │      20 │");
+    assert_eq!(lines.last(), Some(&"2 rows × 2 columns"));
+
+    // every cut lands on a character boundary, however narrow
+    for width in 1..25 {
+        let options = frame::DisplayOptions::new()
+            .with_min_col_width(0)
+            .with_max_col_width(width);
+        names_frame().display(&options).to_string();
+    }
+}
+
+#[test]
+fn test_display_rows_columns_and_style() {
+    let df = DataFrame::from_columns(
+        Some((0..6).map(|i| format!("c{}", i)).collect()),
+        (0..6)
+            .map(|i| vec![i as i64; 30].into())
+            .collect::<Vec<_>>(),
+    )
+    .unwrap();
+    let options = frame::DisplayOptions::new()
+        .with_max_rows(4)
+        .with_tail_rows(2)
+        .with_max_columns(3)
+        .with_min_col_width(2)
+        .with_border(frame::BorderStyle::Ascii);
+    let text = df.display(&options).to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "+----+----+-----+----+");
+    assert_eq!(lines[1], "| c0 | c1 | ... | c5 |");
+    // two head rows, the gap, two tail rows
+    assert_eq!(lines.len(), 3 + 5 + 1 + 1);
+    assert_eq!(lines[5], "| :  | :  |  :  | :  |");
+    assert_eq!(lines.last(), Some(&"30 rows × 6 columns"));
+
+    let narrow = frame::DisplayOptions::new().with_width(30);
+    let text = df.display(&narrow).to_string();
+    assert!(text.lines().all(|line| line.chars().count() <= 30));
+    assert!(text.contains("│ … │"));
+
+    let precise = frame::DisplayOptions::new()
+        .with_float_precision(2)
+        .with_align(frame::Alignment::Left);
+    let text = names_frame().display(&precise).to_string();
+    assert!(text.contains("│ 1.23     │"));
+    assert!(text.contains("│ 20.00    │"));
+}
+
+fn crm_frame() -> DataFrame {
+    DataFrame::from_columns(
+        Some(vec![
+            "email".to_string(),
+            "score".to_string(),
+            "active".to_string(),
+        ]),
+        vec![
+            vec![
+                Some("a@x.com".to_string()),
+                None,
+                Some("a@x.com".to_string()),
+                None,
+                Some("b@x.com".to_string()),
+            ]
+            .into(),
+            vec![
+                Some(1.5),
+                Some(f64::NAN),
+                Some(1.5),
+                Some(f64::NAN),
+                Some(-0.0),
+            ]
+            .into(),
+            vec![Some(true), None, Some(false), None, Some(true)].into(),
+        ],
+    )
+    .unwrap()
+}
+
+fn flags(column: &dyn ColumnArray) -> Vec<bool> {
+    (0..column.len())
+        .map(|i| column.get(i) == Some(CellValue::Boolean(true)))
+        .collect()
+}
+
+#[test]
+fn test_duplicated_keep_modes() {
+    use csv_processor::frame::Keep;
+
+    let df = crm_frame();
+    // nulls are equal to each other and NaN to NaN, so rows 1 and 3 match
+    let all: &[&str] = &[];
+    assert_eq!(
+        flags(&df.duplicated(all, Keep::First).unwrap()),
+        [false, false, false, true, false]
+    );
+    assert_eq!(
+        flags(&df.duplicated(&["email", "score"], Keep::First).unwrap()),
+        [false, false, true, true, false]
+    );
+    assert_eq!(
+        flags(&df.duplicated(&["email"], Keep::Last).unwrap()),
+        [true, true, false, false, false]
+    );
+    assert_eq!(
+        flags(&df.duplicated(&["email"], Keep::None).unwrap()),
+        [true, true, true, true, false]
+    );
+    assert!(df.duplicated(&["phone"], Keep::First).is_err());
+    assert_eq!("LAST".parse::<Keep>(), Ok(Keep::Last));
+}
+
+#[test]
+fn test_unique_keeps_original_order() {
+    use csv_processor::frame::Keep;
+
+    let df = crm_frame();
+    let last = df.unique(&["email"], Keep::Last).unwrap();
+    assert_eq!(last.shape(), (3, 3));
+    assert_eq!(
+        last.column("active").unwrap().get(0),
+        Some(CellValue::Boolean(false))
+    );
+    assert_eq!(
+        last.column("email").unwrap().get(2),
+        Some(CellValue::Str("b@x.com".to_string()))
+    );
+    assert_eq!(df.unique(&["email"], Keep::None).unwrap().shape(), (1, 3));
+
+    // 0.0 and -0.0 differ in bit pattern, so both survive
+    let zeros =
+        DataFrame::from_columns(Some(vec!["x".to_string()]), vec![vec![0.0, -0.0].into()]).unwrap();
+    let all: &[&str] = &[];
+    assert_eq!(zeros.unique(all, Keep::First).unwrap().shape(), (2, 1));
+}
+
+#[test]
+fn test_quality_report_duplicates_section() {
+    let df = crm_frame();
+    let report = reporter::generate_quality_report(&df, &["email"]).unwrap();
+    assert_eq!(report.rows, 5);
+    assert_eq!(report.missing_cells, 4);
+    assert_eq!(report.empty_rows, 0);
+    assert_eq!(report.duplicate_groups, vec![vec![0, 2], vec![1, 3]]);
+    assert_eq!(report.duplicate_rows(), 2);
+
+    let summary = report.to_frame();
+    let measure = summary.column("measure").unwrap();
+    let row = (0..measure.len())
+        .find(|&i| measure.get(i) == Some(CellValue::Str("duplicate_rows".to_string())))
+        .unwrap();
+    assert_eq!(
+        summary.column("percent").unwrap().get(row),
+        Some(CellValue::Float(40.0))
+    );
+
+    let section = report.duplicates_to_frame();
+    assert_eq!(section.headers(), ["first_row", "copies", "rows"]);
+    assert_eq!(
+        section.column("rows").unwrap().get(1),
+        Some(CellValue::Str("1, 3".to_string()))
+    );
+}
+
+fn gappy_frame() -> DataFrame {
+    DataFrame::from_columns(
+        Some(vec!["a".to_string(), "b".to_string(), "c".to_string()]),
+        vec![
+            vec![Some(1), None, Some(3), None, Some(3)].into(),
+            vec![None, Some(2.0), None, None, Some(8.0)].into(),
+            vec![
+                Some("x".to_string()),
+                None,
+                Some("y".to_string()),
+                None,
+                Some("y".to_string()),
+            ]
+            .into(),
+        ],
+    )
+    .unwrap()
+}
+
+fn cells(column: &dyn ColumnArray) -> Vec<Option<CellValue>> {
+    (0..column.len()).map(|i| column.get(i)).collect()
+}
+
+#[test]
+fn test_fill_null_strategies() {
+    use csv_processor::series::FillStrategy;
+
+    let df = gappy_frame();
+    let a = df.column("a").unwrap();
+    let int = |n: i64| Some(CellValue::Integer(n));
+    let float = |x: f64| Some(CellValue::Float(x));
+
+    let forward = a.fill_null(&FillStrategy::Forward).unwrap();
+    assert_eq!(forward.dtype(), Dtype::Integer);
+    assert_eq!(
+        cells(forward.as_ref()),
+        [int(1), int(1), int(3), int(3), int(3)]
+    );
+
+    let b = df.column("b").unwrap();
+    let backward = b.fill_null(&FillStrategy::Backward).unwrap();
+    assert_eq!(
+        cells(backward.as_ref()),
+        [float(2.0), float(2.0), float(8.0), float(8.0), float(8.0)]
+    );
+    // nothing before the first value to carry forward
+    assert_eq!(b.fill_null(&FillStrategy::Forward).unwrap().get(0), None);
+
+    let mean = a.fill_null(&FillStrategy::Mean).unwrap();
+    assert_eq!(mean.dtype(), Dtype::Float);
+    assert_eq!(mean.get(1), float(7.0 / 3.0));
+    assert_eq!(
+        a.fill_null(&FillStrategy::Median).unwrap().get(3),
+        float(3.0)
+    );
+    assert_eq!(a.fill_null(&FillStrategy::Mode).unwrap().get(1), int(3));
+
+    let c = df.column("c").unwrap();
+    let mode = c.fill_null(&FillStrategy::Mode).unwrap();
+    assert_eq!(mode.get(3), Some(CellValue::Str("y".to_string())));
+    assert!(c.fill_null(&FillStrategy::Mean).is_err());
+
+    let zero = FillStrategy::Value(CellValue::Integer(0));
+    assert_eq!(a.fill_null(&zero).unwrap().dtype(), Dtype::Integer);
+    assert_eq!(b.fill_null(&zero).unwrap().get(0), float(0.0));
+    assert_eq!(
+        c.fill_null(&zero).unwrap().get(1),
+        Some(CellValue::Str("0".to_string()))
+    );
+    assert_eq!("ffill".parse::<FillStrategy>(), Ok(FillStrategy::Forward));
+}
+
+#[test]
+fn test_frame_fill_null_skips_unsupported_columns() {
+    use csv_processor::series::FillStrategy;
+
+    let df = gappy_frame();
+    let all: &[&str] = &[];
+    let filled = df.fill_null(all, &FillStrategy::Mean).unwrap();
+    assert_eq!(filled.column("a").unwrap().null_count(), 0);
+    assert_eq!(filled.column("b").unwrap().null_count(), 0);
+    assert_eq!(filled.column("c").unwrap().null_count(), 2);
+
+    let error = df.fill_null(&["c"], &FillStrategy::Median).unwrap_err();
+    assert!(error.to_string().contains("c:"));
+    assert!(df.fill_null(&["d"], &FillStrategy::Mode).is_err());
+}
+
+#[test]
+fn test_drop_nulls() {
+    use csv_processor::frame::DropHow;
+
+    let df = gappy_frame();
+    let all: &[&str] = &[];
+    assert_eq!(
+        df.drop_nulls(all, DropHow::Any, None).unwrap().shape(),
+        (1, 3)
+    );
+    assert_eq!(
+        df.drop_nulls(all, DropHow::All, None).unwrap().shape(),
+        (4, 3)
+    );
+    assert_eq!(
+        df.drop_nulls(&["a", "c"], DropHow::Any, None)
+            .unwrap()
+            .shape(),
+        (3, 3)
+    );
+    // thresh overrides how
+    let kept = df.drop_nulls(all, DropHow::Any, Some(2)).unwrap();
+    assert_eq!(
+        cells(kept.column("a").unwrap()),
+        [
+            Some(CellValue::Integer(1)),
+            Some(CellValue::Integer(3)),
+            Some(CellValue::Integer(3))
+        ]
+    );
+    assert_eq!("ALL".parse::<DropHow>(), Ok(DropHow::All));
+}
+
+#[test]
+fn test_interpolate() {
+    let df = gappy_frame();
+    let all: &[&str] = &[];
+    let interpolated = df.interpolate(all).unwrap();
+    let float = |x: f64| Some(CellValue::Float(x));
+    assert_eq!(
+        cells(interpolated.column("b").unwrap()),
+        [None, float(2.0), float(4.0), float(6.0), float(8.0)]
+    );
+    assert_eq!(interpolated.column("a").unwrap().get(1), float(2.0));
+    // non-numeric columns are left alone unless named
+    assert_eq!(interpolated.column("c").unwrap().null_count(), 2);
+    assert!(df.interpolate(&["c"]).is_err());
+}
+
+#[test]
+fn test_detailed_na_report() {
+    let df = DataFrame::from_columns(
+        Some(vec!["a".to_string(), "b".to_string(), "c".to_string()]),
+        vec![
+            vec![Some(1), None, Some(3), None, None].into(),
+            vec![Some(1.0), Some(2.0), None, Some(4.0), None].into(),
+            vec![Some(true), None, Some(true), None, None].into(),
+        ],
+    )
+    .unwrap();
+    let report = reporter::generate_detailed_na_report(&df);
+    assert_eq!(report.complete_rows, 1);
+    assert_eq!(report.rows_with_nulls(), 4);
+    assert_eq!(report.empty_rows, 1);
+    assert_eq!(report.missing_cells(), 8);
+    // rows 1 and 3 share a pattern, so it comes first
+    assert_eq!(
+        report.patterns[0],
+        reporter::NullPattern {
+            missing: vec![0, 2],
+            rows: 2
+        }
+    );
+    assert_eq!(report.patterns.len(), 4);
+
+    let columns = report.to_frame();
+    assert_eq!(
+        columns.column("percent").unwrap().get(0),
+        Some(CellValue::Float(60.0))
+    );
+
+    let patterns = report.patterns_to_frame(2);
+    assert_eq!(patterns.headers(), ["a", "b", "c", "rows", "percent"]);
+    assert_eq!(patterns.shape(), (2, 5));
+    assert_eq!(
+        patterns.column("a").unwrap().get(0),
+        Some(CellValue::Str("NA".to_string()))
+    );
+    assert_eq!(
+        patterns.column("b").unwrap().get(0),
+        Some(CellValue::Str(String::new()))
+    );
+
+    // a and c are always missing together
+    assert_eq!(report.correlated_columns, ["a", "b", "c"]);
+    let a_c = report.nullity_correlation[0][2].unwrap();
+    assert!((a_c - 1.0).abs() < 1e-12);
+    let matrix = report.correlation_to_frame();
+    assert_eq!(matrix.headers(), ["column", "a", "b", "c"]);
+
+    // the plain report is the per-column section
+    assert_eq!(reporter::generate_na_report(&df).shape(), (3, 3));
+}
+
+#[test]
+fn test_info_report_string_statistics() {
+    let df = DataFrame::from_columns(
+        Some(vec!["name".to_string(), "age".to_string()]),
+        vec![
+            vec![Some("Ada".to_string()), Some("Grace".to_string()), None].into(),
+            vec![Some(36), Some(45), None].into(),
+        ],
+    )
+    .unwrap();
+    let info = reporter::generate_info_report(&df);
+    assert_eq!(
+        info.column("max_length").unwrap().get(0),
+        Some(CellValue::Integer(5))
+    );
+    assert_eq!(
+        info.column("mean_length").unwrap().get(0),
+        Some(CellValue::Float(4.0))
+    );
+    assert_eq!(
+        info.column("distinct_count").unwrap().get(0),
+        Some(CellValue::Integer(2))
+    );
+    // numeric columns have no string statistics
+    assert_eq!(info.column("min_length").unwrap().get(1), None);
+}
+
+#[test]
+fn test_resample_groups_by_time_bucket() {
+    use csv_processor::lazy::{col, len};
+    use csv_processor::series::{parse_column, FillStrategy, Period};
+
+    let df = DataFrame::from_columns(
+        Some(vec!["at".to_string(), "amount".to_string()]),
+        vec![
+            parse_column(vec![
+                "2024-02-10 09:30:00",
+                "2024-01-05 12:00:00",
+                "",
+                "2024-02-01 00:00:00",
+                "2024-01-31 23:59:59",
+            ]),
+            vec![Some(5), Some(1), Some(7), Some(3), Some(2)].into(),
+        ],
+    )
+    .unwrap();
+    assert_eq!(df.column("at").unwrap().dtype(), Dtype::DateTime);
+
+    let monthly = df
+        .resample("at", Period::Month)
+        .unwrap()
+        .agg([col("amount").sum().alias("total"), len().alias("n")])
+        .collect()
+        .unwrap();
+    let at = monthly.column("at").unwrap();
+    assert_eq!(at.dtype(), Dtype::DateTime);
+    assert_eq!(
+        cells(at),
+        [
+            Some(CellValue::Date("2024-01-01 00:00:00".to_string())),
+            Some(CellValue::Date("2024-02-01 00:00:00".to_string())),
+            None
+        ]
+    );
+    let total = monthly.column("total").unwrap();
+    assert_eq!(total.get_f64(0), Some(3.0));
+    assert_eq!(total.get_f64(1), Some(8.0));
+    assert_eq!(total.get_f64(2), Some(7.0));
+
+    assert!(df.resample("amount", Period::Day).is_err());
+    assert!(df.resample("when", Period::Day).is_err());
+
+    // a date column is filled with text that reads as a date
+    let filled = df
+        .column("at")
+        .unwrap()
+        .fill_null(&FillStrategy::Value(CellValue::from("2024-03-01")))
+        .unwrap();
+    assert_eq!(filled.dtype(), Dtype::DateTime);
+    assert_eq!(
+        filled.get(2),
+        Some(CellValue::Date("2024-03-01 00:00:00".to_string()))
+    );
+}
+
+#[test]
+fn test_window_over_partitions() {
+    let df = DataFrame::from_columns(
+        Some(vec!["store".to_string(), "sales".to_string()]),
+        vec![
+            vec![
+                Some("a".to_string()),
+                Some("b".to_string()),
+                Some("a".to_string()),
+                None,
+                Some("b".to_string()),
+                Some("a".to_string()),
+            ]
+            .into(),
+            vec![Some(1), Some(10), Some(2), Some(7), Some(20), Some(3)].into(),
+        ],
+    )
+    .unwrap();
+
+    let running = df.over("sales", &["store"], |c| c.cum_sum()).unwrap();
+    assert_eq!(running.dtype(), Dtype::Integer);
+    let int = |n| Some(CellValue::Integer(n));
+    assert_eq!(
+        cells(running.as_ref()),
+        [int(1), int(10), int(3), int(7), int(30), int(6)]
+    );
+
+    let previous = df.over("sales", &["store"], |c| c.diff(1)).unwrap();
+    assert_eq!(previous.dtype(), Dtype::Integer);
+    assert_eq!(
+        cells(previous.as_ref()),
+        [None, None, int(1), None, int(10), int(1)]
+    );
+
+    let mean = df
+        .over("sales", &["store"], |c| {
+            Ok(c.rolling(2)?.with_min_periods(1).mean())
+        })
+        .unwrap();
+    assert_eq!(mean.get_f64(5), Some(2.5));
+
+    let all: &[&str] = &[];
+    let whole = df.over("sales", all, |c| c.cum_max()).unwrap();
+    assert_eq!(whole.get(5), int(20));
+
+    assert!(df.over("sales", &["region"], |c| c.cum_sum()).is_err());
+    assert!(df.over("store", &["sales"], |c| c.cum_sum()).is_err());
+    assert!(df.over("sales", &["store"], |c| Ok(c.take(&[0]))).is_err());
+}
@@ -0,0 +1,312 @@
+use csv_processor::io::*;
+use csv_processor::CellValue;
+
+#[test]
+fn test_utf8_bom_is_stripped_from_first_header() {
+    let bytes = b"\xEF\xBB\xBFid,name\n1,Alice\n";
+    let (df, report) = read_csv_from_bytes(bytes, &CsvReadOptions::default()).unwrap();
+    assert_eq!(df.headers()[0], "id");
+    assert_eq!(report.encoding, Encoding::Utf8);
+    assert!(report.had_bom);
+}
+
+#[test]
+fn test_detect_windows_1252() {
+    // "Müller" written by Excel on a western-locale machine
+    let bytes = b"id,name\n1,M\xFCller\n";
+    let (df, report) = read_csv_from_bytes(bytes, &CsvReadOptions::default()).unwrap();
+    assert_eq!(report.encoding, Encoding::Windows1252);
+    assert_eq!(
+        df.get_column(1).unwrap().get(0),
+        Some(CellValue::Str("Müller".to_string()))
+    );
+}
+
+#[test]
+fn test_detect_utf16le_with_bom() {
+    let mut bytes = vec![0xFF, 0xFE];
+    for unit in "a,b\n1,2\n".encode_utf16() {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+    let (df, report) = read_csv_from_bytes(&bytes, &CsvReadOptions::default()).unwrap();
+    assert_eq!(report.encoding, Encoding::Utf16Le);
+    assert_eq!(df.headers(), ["a", "b"]);
+    assert_eq!(df.get_column(1).unwrap().sum(), Some(2.0));
+}
+
+#[test]
+fn test_strict_decoding_reports_invalid_bytes() {
+    let bytes = b"id,name\n1,M\xFCller\n";
+    let options = CsvReadOptions::new().with_encoding(Encoding::Utf8);
+    assert!(read_csv_from_bytes(bytes, &options).is_err());
+}
+
+#[test]
+fn test_lossy_decoding_counts_invalid_bytes() {
+    let bytes = b"id,name\n1,M\xFCller\n2,\xFF\xFE\n";
+    let options = CsvReadOptions::new()
+        .with_encoding(Encoding::Utf8)
+        .with_lossy(true);
+    let (df, report) = read_csv_from_bytes(bytes, &options).unwrap();
+    assert_eq!(report.invalid_bytes, 3);
+    assert_eq!(
+        df.get_column(1).unwrap().get(0),
+        Some(CellValue::Str("M\u{FFFD}ller".to_string()))
+    );
+}
+
+#[test]
+fn test_parse_encoding_labels() {
+    assert_eq!("Latin-1".parse::<Encoding>(), Ok(Encoding::Latin1));
+    assert_eq!("cp1251".parse::<Encoding>(), Ok(Encoding::Windows1251));
+    assert_eq!("UTF-16BE".parse::<Encoding>(), Ok(Encoding::Utf16Be));
+    assert!("ebcdic".parse::<Encoding>().is_err());
+}
+
+#[test]
+fn test_sniff_semicolon_with_decimal_commas() {
+    let dialect = sniff("a;b;c\n1;2,5;x\n3;4,1;y\n");
+    assert_eq!(dialect.delimiter, b';');
+    assert!(dialect.has_header);
+}
+
+#[test]
+fn test_sniff_tab_crlf_without_header() {
+    let dialect = sniff("1\t2.5\ttrue\r\n3\t4.5\tfalse\r\n");
+    assert_eq!(dialect.delimiter, b'\t');
+    assert_eq!(dialect.terminator, LineTerminator::CrLf);
+    assert!(!dialect.has_header);
+}
+
+#[test]
+fn test_sniff_single_quotes() {
+    let dialect = sniff("'name'|'city'\n'Smith, J'|'Paris'\n'Doe'|'Rome'\n");
+    assert_eq!(dialect.delimiter, b'|');
+    assert_eq!(dialect.quote, b'\'');
+}
+
+#[test]
+fn test_read_without_header_generates_names() {
+    let (df, report) = read_csv_from_bytes(b"1;2\n3;4\n", &CsvReadOptions::default()).unwrap();
+    assert_eq!(report.dialect.delimiter, b';');
+    assert_eq!(df.headers(), ["column_1", "column_2"]);
+    assert_eq!(df.shape(), (2, 2));
+}
+
+#[test]
+fn test_explicit_delimiter_skips_sniffing() {
+    let options = CsvReadOptions::new().with_delimiter(b',');
+    let (df, _) = read_csv_from_bytes(b"a;b\n1;2\n", &options).unwrap();
+    assert_eq!(df.shape(), (1, 1));
+}
+
+#[test]
+fn test_read_dialect_applies_given_options() {
+    // UTF-16LE without a BOM, decoded as the options say
+    let bytes: Vec<u8> = "a;b\n1;2\n"
+        .encode_utf16()
+        .flat_map(|unit| unit.to_le_bytes())
+        .collect();
+    let path = temp_file("csv_processor_read_dialect.csv", &bytes);
+    let utf16 = CsvReadOptions::new().with_encoding(Encoding::Utf16Le);
+
+    let sniffed = read_dialect(&path, &utf16).unwrap();
+    let fixed = read_dialect(&path, &utf16.clone().with_delimiter(b',').with_quote(b'\'')).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(sniffed.delimiter, b';');
+    assert_eq!((fixed.delimiter, fixed.quote), (b',', b'\''));
+}
+
+fn generated_csv(rows: usize) -> String {
+    let mut text = String::from("id,name,score,flag,note\n");
+    for i in 0..rows {
+        let score = if i % 7 == 0 {
+            String::new()
+        } else {
+            format!("{}.{}", i % 100, i % 10)
+        };
+        text.push_str(&format!(
+            "{},\"Name, {}\",{},{},\"line one\nline \"\"two\"\" {}\"\n",
+            i,
+            i,
+            score,
+            i % 2 == 0,
+            i
+        ));
+    }
+    text
+}
+
+#[test]
+fn test_parallel_parsing_matches_single_thread() {
+    let text = generated_csv(60_000);
+    let (single, _) =
+        read_csv_from_bytes(text.as_bytes(), &CsvReadOptions::new().with_threads(1)).unwrap();
+    let (multi, _) =
+        read_csv_from_bytes(text.as_bytes(), &CsvReadOptions::new().with_threads(4)).unwrap();
+
+    assert_eq!(single.shape(), (60_000, 5));
+    assert_eq!(single.to_json().unwrap(), multi.to_json().unwrap());
+}
+
+#[test]
+fn test_parallel_row_length_error_reports_global_row() {
+    let mut text = generated_csv(60_000);
+    text.push_str("1,2\n");
+    let error = read_csv_from_bytes(text.as_bytes(), &CsvReadOptions::new().with_threads(4))
+        .unwrap_err()
+        .to_string();
+    assert_eq!(error, "Row 60001 has 2 columns but expected 5 ");
+}
+
+#[test]
+fn test_read_selected_columns_in_requested_order() {
+    let options = CsvReadOptions::default().with_columns(vec!["c".to_string(), "a".to_string()]);
+    let (df, _) = read_csv_from_bytes(b"a,b,c\n1,x,2.5\n2,y,3.5\n", &options).unwrap();
+    assert_eq!(df.headers(), ["c", "a"]);
+    assert_eq!(df.column("c").unwrap().get(1), Some(CellValue::Float(3.5)));
+
+    let missing = CsvReadOptions::default().with_columns(vec!["z".to_string()]);
+    assert!(read_csv_from_bytes(b"a,b\n1,2\n", &missing).is_err());
+}
+
+fn temp_file(name: &str, bytes: &[u8]) -> String {
+    let path = std::env::temp_dir().join(name);
+    std::fs::write(&path, bytes).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_streamed_head_and_tail_match_full_read() {
+    let text = generated_csv(20_000);
+    let path = temp_file("csv_processor_stream_rows.csv", text.as_bytes());
+    let options = CsvReadOptions::default();
+    let (full, _) = read_csv(&path, &options).unwrap();
+
+    let (head, _) = read_csv_rows(&path, &options, RowSelection::Head(5)).unwrap();
+    let (tail, _) = read_csv_rows(&path, &options, RowSelection::Tail(3)).unwrap();
+    let (all, _) = read_csv_rows(&path, &options, RowSelection::Tail(50_000)).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(head.to_csv().unwrap(), full.head(5).to_csv().unwrap());
+    assert_eq!(tail.to_csv().unwrap(), full.tail(3).to_csv().unwrap());
+    assert_eq!(all.shape(), (20_000, 5));
+    assert_eq!(
+        tail.column("note").unwrap().get(2),
+        Some(CellValue::Str("line one\nline \"two\" 19999".to_string()))
+    );
+}
+
+#[test]
+fn test_streamed_empty_selection_keeps_headers() {
+    let path = temp_file(
+        "csv_processor_stream_empty.csv",
+        b"id,name,city\n1,Ann,Oslo\n",
+    );
+    let header_only = temp_file("csv_processor_stream_header_only.csv", b"id,name,city\n");
+    let options = CsvReadOptions::default();
+    let columns = options
+        .clone()
+        .with_columns(vec!["city".to_string(), "id".to_string()]);
+
+    let (none, _) = read_csv_rows(&path, &options, RowSelection::Head(0)).unwrap();
+    let (selected, _) = read_csv_rows(&path, &columns, RowSelection::Head(0)).unwrap();
+    let (empty, _) = read_csv_rows(&header_only, &options, RowSelection::Tail(5)).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&header_only).unwrap();
+
+    assert_eq!(none.shape(), (0, 3));
+    assert_eq!(none.to_csv().unwrap(), "id,name,city\n");
+    assert_eq!(selected.headers(), ["city", "id"]);
+    assert_eq!(selected.shape(), (0, 2));
+    assert_eq!(empty.shape(), (0, 3));
+}
+
+#[test]
+fn test_reservoir_sample_is_seeded_and_in_file_order() {
+    let path = temp_file(
+        "csv_processor_stream_sample.csv",
+        generated_csv(5_000).as_bytes(),
+    );
+    let options = CsvReadOptions::default().with_columns(vec!["id".to_string()]);
+    let sample = |seed| {
+        let selection = RowSelection::Sample { n: 50, seed };
+        let (df, _) = read_csv_rows(&path, &options, selection).unwrap();
+        let ids = df.column("id").unwrap();
+        (0..ids.len())
+            .map(|i| ids.get_f64(i).unwrap() as i64)
+            .collect::<Vec<_>>()
+    };
+    let first = sample(Some(7));
+    let again = sample(Some(7));
+    let other = sample(Some(8));
+    let fraction = RowSelection::Fraction {
+        fraction: 0.1,
+        seed: Some(1),
+    };
+    let (tenth, _) = read_csv_rows(&path, &options, fraction).unwrap();
+    let (everything, _) = read_csv_rows(
+        &path,
+        &options,
+        RowSelection::Sample {
+            n: 9_999,
+            seed: None,
+        },
+    )
+    .unwrap();
+    let bad = RowSelection::Fraction {
+        fraction: 1.5,
+        seed: None,
+    };
+    assert!(read_csv_rows(&path, &options, bad).is_err());
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(first.len(), 50);
+    assert_eq!(first, again);
+    assert_ne!(first, other);
+    assert!(first.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!((350..650).contains(&tenth.shape().0));
+    assert_eq!(everything.shape(), (5_000, 1));
+}
+
+#[test]
+fn test_streamed_rows_decode_other_encodings() {
+    // UTF-16 spanning several read blocks, with a BOM and a surrogate pair
+    let mut text = String::from("name,n\n");
+    for i in 0..10_000 {
+        text.push_str(&format!("Zoë 𝄞,{}\n", i));
+    }
+    let mut bytes = vec![0xFF, 0xFE];
+    for unit in text.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+    let path = temp_file("csv_processor_stream_utf16.csv", &bytes);
+    let (tail, report) =
+        read_csv_rows(&path, &CsvReadOptions::default(), RowSelection::Tail(1)).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(report.encoding, Encoding::Utf16Le);
+    assert!(report.had_bom);
+    assert_eq!(tail.headers(), ["name", "n"]);
+    assert_eq!(
+        tail.column("name").unwrap().get(0),
+        Some(CellValue::Str("Zoë 𝄞".to_string()))
+    );
+    assert_eq!(
+        tail.column("n").unwrap().get(0),
+        Some(CellValue::Integer(9_999))
+    );
+
+    let path = temp_file("csv_processor_stream_1252.csv", b"id,name\n1,M\xFCller\n");
+    let (head, report) =
+        read_csv_rows(&path, &CsvReadOptions::default(), RowSelection::Head(1)).unwrap();
+    let strict = CsvReadOptions::new().with_encoding(Encoding::Utf8);
+    let error = read_csv_rows(&path, &strict, RowSelection::Head(1)).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(report.encoding, Encoding::Windows1252);
+    assert_eq!(
+        head.column("name").unwrap().get(0),
+        Some(CellValue::Str("Müller".to_string()))
+    );
+    assert!(error.to_string().contains("byte 11"));
+}
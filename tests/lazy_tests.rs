@@ -0,0 +1,244 @@
+use csv_processor::lazy::*;
+use csv_processor::series::ColumnArray;
+use csv_processor::{CellValue, DataFrame};
+
+const TITANIC: &str = "samples/titanic-parquet.csv";
+
+fn people() -> DataFrame {
+    let names: Vec<String> = ["Ann", "Bob", "Cid", "Dee", "Eve"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let cities: Vec<String> = ["Oslo", "Rome", "Oslo", "Rome", "Oslo"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let columns: Vec<Box<dyn ColumnArray>> = vec![
+        names.into(),
+        cities.into(),
+        vec![25i64, 41, 35, 30, 52].into(),
+        vec![3000.0, 4200.0, 3900.0, 3100.0, 5000.0].into(),
+    ];
+    let headers = ["name", "city", "age", "salary"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    DataFrame::from_columns(Some(headers), columns).unwrap()
+}
+
+#[test]
+fn test_filter_and_select() {
+    let df = people()
+        .lazy()
+        .filter(col("age").gt(30))
+        .select([col("name"), (col("age") + 1).alias("next_age")])
+        .collect()
+        .unwrap();
+    assert_eq!(df.headers(), ["name", "next_age"]);
+    assert_eq!(df.shape(), (3, 2));
+    assert_eq!(
+        df.column("name").unwrap().get(0),
+        Some(CellValue::Str("Bob".to_string()))
+    );
+    assert_eq!(
+        df.column("next_age").unwrap().get(2),
+        Some(CellValue::Integer(53))
+    );
+}
+
+#[test]
+fn test_combined_predicates() {
+    let df = people()
+        .lazy()
+        .filter(col("city").eq("Oslo").and(col("salary").lt_eq(3900.0)))
+        .collect()
+        .unwrap();
+    assert_eq!(df.shape().0, 2);
+    let df = people()
+        .lazy()
+        .filter(!col("city").eq("Oslo").or(col("age").gt_eq(41)))
+        .collect()
+        .unwrap();
+    assert_eq!(
+        df.column("name").unwrap().get(0),
+        Some(CellValue::Str("Dee".to_string()))
+    );
+}
+
+#[test]
+fn test_filter_drops_null_comparisons() {
+    let df = DataFrame::from_columns(
+        Some(vec!["x".to_string()]),
+        vec![vec![Some(1i64), None, Some(3)].into()],
+    )
+    .unwrap();
+    let kept = df.clone().lazy().filter(col("x").gt(0)).collect().unwrap();
+    assert_eq!(kept.shape().0, 2);
+    let nulls = df.lazy().filter(col("x").is_null()).collect().unwrap();
+    assert_eq!(nulls.shape().0, 1);
+}
+
+#[test]
+fn test_group_by_aggregations() {
+    let df = people()
+        .lazy()
+        .group_by([col("city")])
+        .agg([
+            len(),
+            col("age").max().alias("oldest"),
+            col("salary").mean().alias("avg_salary"),
+        ])
+        .collect()
+        .unwrap();
+    assert_eq!(df.headers(), ["city", "len", "oldest", "avg_salary"]);
+    assert_eq!(df.shape(), (2, 4));
+    assert_eq!(
+        df.column("city").unwrap().get(0),
+        Some(CellValue::Str("Oslo".to_string()))
+    );
+    assert_eq!(
+        df.column("len").unwrap().get(0),
+        Some(CellValue::Integer(3))
+    );
+    assert_eq!(
+        df.column("oldest").unwrap().get(1),
+        Some(CellValue::Integer(41))
+    );
+    assert_eq!(
+        df.column("avg_salary").unwrap().get(1),
+        Some(CellValue::Float(3650.0))
+    );
+}
+
+#[test]
+fn test_select_broadcasts_aggregates() {
+    let df = people()
+        .lazy()
+        .select([col("name"), (col("age") - col("age").mean()).alias("delta")])
+        .collect()
+        .unwrap();
+    assert_eq!(df.shape(), (5, 2));
+    let delta = df.column("delta").unwrap().get_f64(0).unwrap();
+    assert!((delta + 11.6).abs() < 1e-9);
+}
+
+#[test]
+fn test_with_columns_replaces_column() {
+    let df = people()
+        .lazy()
+        .with_column(col("salary") * 2)
+        .filter(col("salary").gt(9000.0))
+        .collect()
+        .unwrap();
+    assert_eq!(df.shape(), (1, 4));
+}
+
+#[test]
+fn test_duplicate_select_names_error() {
+    let result = people()
+        .lazy()
+        .select([col("age"), col("age") * 2])
+        .collect();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_scan_csv_matches_eager() {
+    let df = LazyFrame::scan_csv(TITANIC)
+        .filter(col("Pclass").eq(1).and(col("Sex").eq("female")))
+        .select([col("Name")])
+        .collect()
+        .unwrap();
+    assert_eq!(df.shape(), (94, 1));
+}
+
+#[test]
+fn test_scan_group_by() {
+    let df = LazyFrame::scan_csv(TITANIC)
+        .filter(col("Fare").gt(50))
+        .group_by([col("Pclass")])
+        .agg([len(), col("Survived").sum().alias("survivors")])
+        .collect()
+        .unwrap();
+    assert_eq!(df.shape(), (3, 3));
+    let survivors: Vec<Option<CellValue>> = (0..3)
+        .map(|i| df.column("survivors").unwrap().get(i))
+        .collect();
+    assert_eq!(
+        survivors,
+        vec![
+            Some(CellValue::Integer(102)),
+            Some(CellValue::Integer(2)),
+            Some(CellValue::Integer(5))
+        ]
+    );
+}
+
+#[test]
+fn test_explain_shows_pushdown() {
+    let plan = LazyFrame::scan_csv(TITANIC)
+        .filter(col("Age").gt(30))
+        .select([col("Name"), col("Fare")])
+        .explain();
+    let expected = format!(
+        "SELECT [col(\"Name\"), col(\"Fare\")]\n  CSV SCAN {}\n    PROJECT [Name, Fare, Age]\n    SELECTION (col(\"Age\") > 30)\n",
+        TITANIC
+    );
+    assert_eq!(plan, expected);
+}
+
+#[test]
+fn test_filter_stays_above_redefined_column() {
+    let plan = people()
+        .lazy()
+        .with_column((col("age") * 2).alias("age"))
+        .filter(col("age").gt(70))
+        .explain();
+    assert!(plan.starts_with("FILTER"));
+}
+
+#[test]
+fn test_expr_display() {
+    let expr = (col("a") + 1).gt_eq(col("b").mean()).alias("flag");
+    assert_eq!(
+        expr.to_string(),
+        "((col(\"a\") + 1) >= mean(col(\"b\"))) AS flag"
+    );
+    assert_eq!(expr.output_name(), "flag");
+}
+
+#[test]
+fn test_aggregate_predicates_see_the_rows_of_their_input() {
+    // Oslo salaries average 3966.67; all salaries average 3840
+    let above_oslo_mean = people()
+        .lazy()
+        .filter(col("city").eq("Oslo"))
+        .filter(col("salary").gt(col("salary").mean()))
+        .collect()
+        .unwrap();
+    assert_eq!(above_oslo_mean.shape().0, 1);
+
+    let oslo_above_mean = people()
+        .lazy()
+        .filter(col("salary").gt(col("salary").mean()))
+        .filter(col("city").eq("Oslo"))
+        .collect()
+        .unwrap();
+    assert_eq!(oslo_above_mean.shape().0, 2);
+
+    let scanned = LazyFrame::scan_csv(TITANIC)
+        .filter(col("Sex").eq("female"))
+        .filter(col("Fare").gt(col("Fare").mean()));
+    assert!(scanned.explain().contains("FILTER"));
+    let female = DataFrame::from_csv(TITANIC)
+        .unwrap()
+        .lazy()
+        .filter(col("Sex").eq("female"))
+        .collect()
+        .unwrap()
+        .lazy()
+        .filter(col("Fare").gt(col("Fare").mean()))
+        .collect()
+        .unwrap();
+    assert_eq!(scanned.collect().unwrap().shape(), female.shape());
+}
@@ -0,0 +1,104 @@
+use std::io::Write;
+
+use csv_processor::frame::DataFrameError;
+use csv_processor::output::{write_frame, FrameWriter};
+use csv_processor::{DataFrame, OutputFormat};
+
+fn frame() -> DataFrame {
+    DataFrame::from_columns(
+        Some(vec!["name".to_string(), "score".to_string()]),
+        vec![
+            vec!["a|b".to_string(), "<R&D>_1".to_string()].into(),
+            vec![1.5, 20.0].into(),
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_format_names() {
+    assert_eq!("Markdown".parse(), Ok(OutputFormat::Markdown));
+    assert_eq!("md".parse(), Ok(OutputFormat::Markdown));
+    assert_eq!("tex".parse(), Ok(OutputFormat::Latex));
+    assert_eq!(OutputFormat::Ndjson.to_string(), "ndjson");
+    assert!("yaml".parse::<OutputFormat>().is_err());
+}
+
+#[test]
+fn test_delimited_and_json_lines() {
+    let df = frame();
+    assert_eq!(
+        df.render(OutputFormat::Tsv).unwrap(),
+        "name\tscore\na|b\t1.5\n<R&D>_1\t20\n"
+    );
+    assert_eq!(
+        df.render(OutputFormat::Ndjson).unwrap(),
+        "{\"name\":\"a|b\",\"score\":1.5}\n{\"name\":\"<R&D>_1\",\"score\":20.0}\n"
+    );
+    assert_eq!(
+        df.render(OutputFormat::Json).unwrap(),
+        df.to_json().unwrap() + "\n"
+    );
+}
+
+#[test]
+fn test_json_lines_keep_column_order_and_repeated_names() {
+    let df = DataFrame::from_columns(
+        Some(vec![
+            "z".to_string(),
+            "a".to_string(),
+            "z".to_string(),
+            "z_2".to_string(),
+        ]),
+        vec![
+            vec![1i64].into(),
+            vec![2i64].into(),
+            vec![3i64].into(),
+            vec![4i64].into(),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        df.render(OutputFormat::Ndjson).unwrap(),
+        "{\"z\":1,\"a\":2,\"z_2\":3,\"z_2_2\":4}\n"
+    );
+}
+
+#[test]
+fn test_markup_formats_escape_cells() {
+    let df = frame();
+    assert_eq!(
+        df.render(OutputFormat::Markdown).unwrap(),
+        "| name | score |\n| --- | ---: |\n| a\\|b | 1.5 |\n| <R&D>_1 | 20 |\n"
+    );
+
+    let html = df.render(OutputFormat::Html).unwrap();
+    assert!(html.starts_with("<table>\n  <thead>\n    <tr><th>name</th><th>score</th></tr>"));
+    assert!(html.contains("<td>&lt;R&amp;D&gt;_1</td><td align=\"right\">20</td>"));
+
+    let latex = df.render(OutputFormat::Latex).unwrap();
+    assert!(latex.starts_with("\\begin{tabular}{lr}\n\\hline\nname & score \\\\\n"));
+    assert!(latex.contains("<R\\&D>\\_1 & 20 \\\\"));
+    assert!(latex.ends_with("\\end{tabular}\n"));
+}
+
+struct RowCount;
+
+impl FrameWriter for RowCount {
+    fn write_frame(&self, df: &DataFrame, writer: &mut dyn Write) -> Result<(), DataFrameError> {
+        writeln!(writer, "{} rows", df.shape().0)
+            .map_err(|e| DataFrameError::IoError(e.to_string()))
+    }
+}
+
+#[test]
+fn test_custom_writer_and_write_frame() {
+    let df = frame();
+    let mut buffer = Vec::new();
+    RowCount.write_frame(&df, &mut buffer).unwrap();
+    assert_eq!(buffer, b"2 rows\n");
+
+    let mut buffer = Vec::new();
+    write_frame(&df, OutputFormat::Csv, &mut buffer).unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap(), df.to_csv().unwrap());
+}
@@ -0,0 +1,98 @@
+use csv_processor::shell::{Shell, ShellOutput};
+use csv_processor::{CellValue, DataFrame};
+
+const TITANIC: &str = "samples/titanic-parquet.csv";
+
+fn shell() -> Shell {
+    Shell::new(DataFrame::from_csv(TITANIC).unwrap())
+}
+
+fn text(output: ShellOutput) -> String {
+    match output {
+        ShellOutput::Text(text) => text,
+        ShellOutput::Quit => panic!("unexpected quit"),
+    }
+}
+
+#[test]
+fn test_filter_select_sort_reset() {
+    let mut shell = shell();
+    shell
+        .execute("filter Pclass = 1 AND Sex = 'female'")
+        .unwrap();
+    assert_eq!(shell.frame().shape(), (94, 12));
+
+    let preview = text(shell.execute("select Name Fare").unwrap());
+    assert!(preview.contains("(showing 10 of 94 rows)"));
+    assert_eq!(shell.frame().headers(), ["Name", "Fare"]);
+
+    shell.execute("sort Fare desc").unwrap();
+    let fare = shell.frame().column("Fare").unwrap();
+    assert_eq!(fare.get_f64(0), Some(512.3292));
+
+    shell.execute("reset").unwrap();
+    assert_eq!(shell.frame().shape(), (891, 12));
+}
+
+#[test]
+fn test_head_freq_and_reports() {
+    let mut shell = shell();
+    let head = text(shell.execute("head 3").unwrap());
+    assert!(head.contains("3 rows"));
+    let freq = text(shell.execute("freq Embarked 2").unwrap());
+    assert!(freq.starts_with("Embarked (4 distinct values)"));
+    assert!(freq.contains("644"));
+    assert!(!text(shell.execute("info").unwrap()).is_empty());
+    assert!(!text(shell.execute("na").unwrap()).is_empty());
+}
+
+#[test]
+fn test_errors_keep_the_view() {
+    let mut shell = shell();
+    assert!(shell.execute("select Missing").is_err());
+    assert!(shell.execute("sort Fare sideways").is_err());
+    assert!(shell.execute("filter Fare >").is_err());
+    assert!(shell.execute("frobnicate").is_err());
+    assert_eq!(shell.frame().shape(), (891, 12));
+    assert_eq!(shell.execute("quit").unwrap(), ShellOutput::Quit);
+}
+
+#[test]
+fn test_save_writes_current_rows() {
+    let path = std::env::temp_dir().join("csv_processor_shell_save.csv");
+    let mut shell = shell();
+    shell.execute("filter Survived = 1").unwrap();
+    let message = text(
+        shell
+            .execute(&format!("save \"{}\"", path.display()))
+            .unwrap(),
+    );
+    assert_eq!(message, format!("Saved 342 rows to {}", path.display()));
+    let saved = DataFrame::from_csv(path.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(saved.shape(), (342, 12));
+    assert_eq!(
+        saved.column("Survived").unwrap().get(0),
+        Some(CellValue::Integer(1))
+    );
+}
+
+#[test]
+fn test_completion() {
+    let shell = Shell::new(
+        DataFrame::from_columns(
+            Some(vec!["Home Town".to_string(), "Height".to_string()]),
+            vec![vec![1i64].into(), vec![2i64].into()],
+        )
+        .unwrap(),
+    );
+    assert_eq!(shell.complete("fi", 2), (0, vec!["filter".to_string()]));
+    assert_eq!(
+        shell.complete("select h", 8),
+        (7, vec!["\"Home Town\"".to_string(), "Height".to_string()])
+    );
+    assert_eq!(
+        shell.complete("freq \"Ho", 8),
+        (6, vec!["Home Town".to_string()])
+    );
+}
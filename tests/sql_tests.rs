@@ -0,0 +1,234 @@
+use csv_processor::series::ColumnArray;
+use csv_processor::{parse_config, CellValue, Command, DataFrame, SqlContext};
+
+const TITANIC: &str = "samples/titanic-parquet.csv";
+
+fn frame(headers: &[&str], columns: Vec<Box<dyn ColumnArray>>) -> DataFrame {
+    let headers = headers.iter().map(|s| s.to_string()).collect();
+    DataFrame::from_columns(Some(headers), columns).unwrap()
+}
+
+fn strings(values: &[&str]) -> Box<dyn ColumnArray> {
+    values
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>()
+        .into()
+}
+
+fn context() -> SqlContext {
+    let people = frame(
+        &["id", "name", "city", "age"],
+        vec![
+            vec![1i64, 2, 3, 4, 5].into(),
+            strings(&["Ann", "Bob", "Cid", "Dee", "Eve"]),
+            strings(&["Oslo", "Rome", "Oslo", "Rome", "Oslo"]),
+            vec![25i64, 41, 35, 30, 52].into(),
+        ],
+    );
+    let orders = frame(
+        &["id", "person_id", "total"],
+        vec![
+            vec![10i64, 11, 12, 13].into(),
+            vec![1i64, 1, 3, 9].into(),
+            vec![20.0, 5.5, 12.0, 99.0].into(),
+        ],
+    );
+    let mut context = SqlContext::new();
+    context.register("people", people);
+    context.register("orders", orders);
+    context
+}
+
+fn run(query: &str) -> DataFrame {
+    context().execute(query).unwrap().collect().unwrap()
+}
+
+fn values(df: &DataFrame, name: &str) -> Vec<Option<CellValue>> {
+    let column = df.column(name).unwrap();
+    (0..column.len()).map(|i| column.get(i)).collect()
+}
+
+fn text(s: &str) -> Option<CellValue> {
+    Some(CellValue::Str(s.to_string()))
+}
+
+#[test]
+fn test_where_order_limit() {
+    let df =
+        run("SELECT name, age + 1 AS next FROM people WHERE age > 28 ORDER BY age DESC LIMIT 2");
+    assert_eq!(df.headers(), ["name", "next"]);
+    assert_eq!(values(&df, "name"), vec![text("Eve"), text("Bob")]);
+    assert_eq!(
+        values(&df, "next"),
+        vec![Some(CellValue::Integer(53)), Some(CellValue::Integer(42))]
+    );
+}
+
+#[test]
+fn test_group_by_having() {
+    let df = run("SELECT city, COUNT(*), MAX(age) AS oldest FROM people \
+         GROUP BY city HAVING COUNT(*) > 2");
+    assert_eq!(df.headers(), ["city", "COUNT(*)", "oldest"]);
+    assert_eq!(values(&df, "city"), vec![text("Oslo")]);
+    assert_eq!(values(&df, "COUNT(*)"), vec![Some(CellValue::Integer(3))]);
+    assert_eq!(values(&df, "oldest"), vec![Some(CellValue::Integer(52))]);
+}
+
+#[test]
+fn test_integer_sum_is_exact() {
+    let mut context = SqlContext::new();
+    context.register(
+        "t",
+        frame(&["a"], vec![vec![9007199254740993i64, 0].into()]),
+    );
+    let df = context
+        .execute("SELECT SUM(a) AS total FROM t")
+        .unwrap()
+        .collect()
+        .unwrap();
+    assert_eq!(
+        values(&df, "total"),
+        vec![Some(CellValue::Integer(9007199254740993))]
+    );
+}
+
+#[test]
+fn test_order_by_aggregate_alias() {
+    let df = run("SELECT city, AVG(age) AS mean_age FROM people GROUP BY 1 ORDER BY mean_age");
+    assert_eq!(values(&df, "city"), vec![text("Rome"), text("Oslo")]);
+}
+
+#[test]
+fn test_ungrouped_column_is_rejected() {
+    let error = context()
+        .execute("SELECT name, COUNT(*) FROM people GROUP BY city")
+        .unwrap_err();
+    assert!(error.to_string().contains("GROUP BY"));
+}
+
+#[test]
+fn test_inner_and_left_join() {
+    let df = run(
+        "SELECT p.name, o.total FROM people p JOIN orders o ON p.id = o.person_id \
+         ORDER BY o.total",
+    );
+    assert_eq!(df.headers(), ["name", "total"]);
+    assert_eq!(
+        values(&df, "name"),
+        vec![text("Ann"), text("Cid"), text("Ann")]
+    );
+
+    let df = run(
+        "SELECT name, SUM(total) AS spent FROM people LEFT JOIN orders \
+         ON people.id = orders.person_id GROUP BY name ORDER BY name",
+    );
+    assert_eq!(df.shape(), (5, 2));
+    // Bob has no orders; like the lazy API, the sum of nothing is zero.
+    assert_eq!(
+        values(&df, "spent")[..2],
+        [Some(CellValue::Float(25.5)), Some(CellValue::Float(0.0))]
+    );
+}
+
+#[test]
+fn test_ambiguous_column() {
+    let error = context()
+        .execute("SELECT id FROM people JOIN orders ON people.id = orders.person_id")
+        .unwrap_err();
+    assert!(error.to_string().contains("ambiguous"));
+}
+
+#[test]
+fn test_scalar_functions() {
+    let df = run(
+        "SELECT UPPER(name) AS upper, LENGTH(city) AS len FROM people \
+         WHERE name LIKE '_e%' OR age BETWEEN 30 AND 35",
+    );
+    assert_eq!(values(&df, "upper"), vec![text("CID"), text("DEE")]);
+    assert_eq!(values(&df, "len")[0], Some(CellValue::Integer(4)));
+}
+
+#[test]
+fn test_distinct_and_subquery() {
+    let df = run("SELECT DISTINCT city FROM people ORDER BY city");
+    assert_eq!(values(&df, "city"), vec![text("Oslo"), text("Rome")]);
+
+    let df = run(
+        "WITH adults AS (SELECT name, age FROM people WHERE age >= 35) \
+         SELECT COUNT(*) AS n FROM (SELECT * FROM adults) a WHERE a.age < 50",
+    );
+    assert_eq!(values(&df, "n"), vec![Some(CellValue::Integer(2))]);
+}
+
+#[test]
+fn test_sql_over_csv_file() {
+    let df = DataFrame::sql(
+        &[TITANIC],
+        "SELECT Pclass, COUNT(*) AS n, SUM(Survived) AS survivors \
+         FROM \"titanic-parquet\" WHERE Fare > 50 GROUP BY Pclass ORDER BY Pclass",
+    )
+    .unwrap();
+    assert_eq!(
+        values(&df, "n"),
+        vec![
+            Some(CellValue::Integer(139)),
+            Some(CellValue::Integer(7)),
+            Some(CellValue::Integer(14))
+        ]
+    );
+    assert_eq!(
+        values(&df, "survivors"),
+        vec![
+            Some(CellValue::Integer(102)),
+            Some(CellValue::Integer(2)),
+            Some(CellValue::Integer(5))
+        ]
+    );
+}
+
+#[test]
+fn test_sql_plan_reaches_the_scan() {
+    let mut context = SqlContext::new();
+    let name = context.register_csv(TITANIC, &Default::default()).unwrap();
+    assert_eq!(context.table_names(), ["titanic-parquet"]);
+    let plan = context
+        .execute(&format!(
+            "SELECT Name FROM \"{}\" WHERE Pclass = 1 AND Sex = 'female'",
+            name
+        ))
+        .unwrap();
+    let explain = plan.explain();
+    assert!(explain.contains("PROJECT [Name, Pclass, Sex]"));
+    assert!(explain.contains("SELECTION"));
+    assert_eq!(plan.collect().unwrap().shape(), (94, 1));
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(context().execute("SELEC name FROM people").is_err());
+    assert!(context().execute("DELETE FROM people").is_err());
+    assert!(context().execute("SELECT * FROM missing").is_err());
+}
+
+#[test]
+fn test_parse_sql_command() {
+    let args: Vec<String> = [
+        "csv_processor",
+        "sql",
+        "SELECT * FROM a JOIN b ON a.x = b.x",
+        "a.csv",
+        "b.csv",
+        "--csv",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    let config = parse_config(&args).unwrap();
+    assert_eq!(*config.command(), Command::Sql);
+    assert_eq!(config.query(), Some("SELECT * FROM a JOIN b ON a.x = b.x"));
+    assert_eq!(config.files(), ["a.csv", "b.csv"]);
+    assert_eq!(config.filename(), "a.csv");
+    assert!(config.columns().is_empty());
+    assert!(config.csv_rows());
+}